@@ -0,0 +1,312 @@
+use std::ops::Range;
+
+use rue_compiler::DiagnosticData;
+use rue_parser::{line_col, LineCol};
+use serde::Serialize;
+
+use crate::report::{Report, Severity};
+
+/// A single line of output when using `--message-format json`.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Message {
+    Diagnostic(DiagnosticMessage),
+    Build(BuildMessage),
+}
+
+impl Message {
+    pub fn print(&self) {
+        println!("{}", serde_json::to_string(self).unwrap());
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DiagnosticMessage {
+    pub severity: Severity,
+    pub code: &'static str,
+    pub message: String,
+    pub file: String,
+    #[serde(flatten)]
+    pub location: Location,
+    pub labels: Vec<LabelMessage>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub suggestion: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data: Option<DataMessage>,
+}
+
+impl DiagnosticMessage {
+    pub fn new(file: &str, source: &str, report: &Report) -> Self {
+        Self {
+            severity: report.severity,
+            code: report.code,
+            message: report.message.clone(),
+            file: file.to_string(),
+            location: Location::new(source, &report.span),
+            labels: report
+                .labels
+                .iter()
+                .map(|label| LabelMessage {
+                    message: label.message.clone(),
+                    location: Location::new(source, &label.span),
+                })
+                .collect(),
+            suggestion: report.suggestion.clone(),
+            note: report.note.clone(),
+            data: report
+                .data
+                .as_ref()
+                .map(|data| DataMessage::new(source, data)),
+        }
+    }
+}
+
+/// Structured information which tooling can use to fix the diagnostic, with spans as locations.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum DataMessage {
+    Remove {
+        #[serde(flatten)]
+        location: Location,
+    },
+    Replace {
+        #[serde(flatten)]
+        location: Location,
+        replacement: String,
+    },
+    MissingFields {
+        #[serde(flatten)]
+        location: Location,
+        needs_comma: bool,
+        fields: Vec<FieldMessage>,
+    },
+    Imports {
+        paths: Vec<String>,
+    },
+}
+
+impl DataMessage {
+    pub fn new(source: &str, data: &DiagnosticData) -> Self {
+        match data {
+            DiagnosticData::Remove(span) => Self::Remove {
+                location: Location::new(source, span),
+            },
+            DiagnosticData::Replace(span, replacement) => Self::Replace {
+                location: Location::new(source, span),
+                replacement: replacement.clone(),
+            },
+            DiagnosticData::MissingFields {
+                offset,
+                needs_comma,
+                fields,
+            } => Self::MissingFields {
+                location: Location::new(source, &(*offset..*offset)),
+                needs_comma: *needs_comma,
+                fields: fields
+                    .iter()
+                    .map(|(name, ty)| FieldMessage {
+                        name: name.clone(),
+                        ty: ty.clone(),
+                    })
+                    .collect(),
+            },
+            DiagnosticData::Imports(paths) => Self::Imports {
+                paths: paths.clone(),
+            },
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct FieldMessage {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub ty: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct LabelMessage {
+    pub message: String,
+    #[serde(flatten)]
+    pub location: Location,
+}
+
+/// The byte range of a span, along with its one-based start and end positions.
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct Location {
+    pub byte_start: usize,
+    pub byte_end: usize,
+    pub start: Position,
+    pub end: Position,
+}
+
+impl Location {
+    pub fn new(source: &str, span: &Range<usize>) -> Self {
+        Self {
+            byte_start: span.start,
+            byte_end: span.end,
+            start: Position::new(source, span.start),
+            end: Position::new(source, span.end),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct Position {
+    pub line: usize,
+    pub column: usize,
+}
+
+impl Position {
+    pub fn new(source: &str, index: usize) -> Self {
+        let LineCol { line, col } = line_col(source, index);
+
+        Self {
+            line: line + 1,
+            column: col + 1,
+        }
+    }
+}
+
+/// The result of the build, which is always the last message.
+/// Compilation and run details are only included if they were requested and there were no errors.
+#[derive(Debug, Default, Clone, Serialize)]
+pub struct BuildMessage {
+    pub success: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hex: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tree_hash: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub size: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cost: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub output: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use rue_compiler::{Diagnostic, DiagnosticKind, ErrorKind, Label, WarningKind};
+    use serde_json::{json, Value};
+
+    use super::*;
+
+    const SOURCE: &str = "fun main() -> Int {\n    let value = 1;\n    valeu\n}\n";
+
+    fn message(diagnostic: &Diagnostic) -> Value {
+        let report = Report::from_diagnostic(diagnostic);
+        serde_json::to_value(Message::Diagnostic(DiagnosticMessage::new(
+            "main.rue", SOURCE, &report,
+        )))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_diagnostic_schema() {
+        let mut diagnostic = Diagnostic::new(
+            DiagnosticKind::Error(ErrorKind::UnknownSymbol("valeu".to_string())),
+            43..48,
+        );
+        diagnostic
+            .with_label(Label::new(28..33, "similar name defined here"))
+            .with_suggestion("value")
+            .with_data(DiagnosticData::Imports(vec!["std::valeu".to_string()]));
+
+        // Byte offsets are zero-based, whereas lines and columns are one-based.
+        assert_eq!(
+            message(&diagnostic),
+            json!({
+                "type": "diagnostic",
+                "severity": "error",
+                "code": "E0005",
+                "message": "Reference to unknown symbol `valeu`",
+                "file": "main.rue",
+                "byte_start": 43,
+                "byte_end": 48,
+                "start": { "line": 3, "column": 5 },
+                "end": { "line": 3, "column": 10 },
+                "labels": [{
+                    "message": "similar name defined here",
+                    "byte_start": 28,
+                    "byte_end": 33,
+                    "start": { "line": 2, "column": 9 },
+                    "end": { "line": 2, "column": 14 },
+                }],
+                "suggestion": "value",
+                "data": {
+                    "kind": "imports",
+                    "paths": ["std::valeu"],
+                },
+            })
+        );
+    }
+
+    #[test]
+    fn test_optional_fields() {
+        let diagnostic = Diagnostic::new(
+            DiagnosticKind::Warning(WarningKind::UnusedLet("value".to_string())),
+            24..33,
+        );
+
+        let value = message(&diagnostic);
+        let fields = value.as_object().unwrap();
+
+        assert_eq!(value["severity"], "warning");
+        assert_eq!(value["code"], "W0006");
+        assert_eq!(value["labels"], json!([]));
+        assert!(!fields.contains_key("suggestion"));
+        assert!(!fields.contains_key("note"));
+        assert!(!fields.contains_key("data"));
+    }
+
+    #[test]
+    fn test_data_schema() {
+        let data =
+            |data: DiagnosticData| serde_json::to_value(DataMessage::new(SOURCE, &data)).unwrap();
+
+        assert_eq!(
+            data(DiagnosticData::Remove(20..39)),
+            json!({
+                "kind": "remove",
+                "byte_start": 20,
+                "byte_end": 39,
+                "start": { "line": 2, "column": 1 },
+                "end": { "line": 3, "column": 1 },
+            })
+        );
+
+        assert_eq!(
+            data(DiagnosticData::Replace(43..48, "value".to_string())),
+            json!({
+                "kind": "replace",
+                "byte_start": 43,
+                "byte_end": 48,
+                "start": { "line": 3, "column": 5 },
+                "end": { "line": 3, "column": 10 },
+                "replacement": "value",
+            })
+        );
+
+        assert_eq!(
+            data(DiagnosticData::MissingFields {
+                offset: 48,
+                needs_comma: true,
+                fields: vec![("amount".to_string(), "Int".to_string())],
+            }),
+            json!({
+                "kind": "missing_fields",
+                "byte_start": 48,
+                "byte_end": 48,
+                "start": { "line": 3, "column": 10 },
+                "end": { "line": 3, "column": 10 },
+                "needs_comma": true,
+                "fields": [{ "name": "amount", "type": "Int" }],
+            })
+        );
+    }
+}
@@ -1,12 +1,19 @@
 #![allow(clippy::option_option)]
 
-use std::fs;
+use std::{fs, path::Path, process};
 
-use clap::Parser;
+use clap::{Parser, ValueEnum};
+use clvm_utils::tree_hash;
 use clvmr::{serde::node_to_bytes, Allocator, NodePtr};
+use json::{BuildMessage, DiagnosticMessage, Message};
+use report::{Renderer, Report, Severity};
 use rue_clvm::{parse_clvm, run_clvm, stringify_clvm};
-use rue_compiler::{compile_raw, Diagnostic, DiagnosticKind};
-use rue_parser::{line_col, parse, LineCol};
+use rue_compiler::{compile_raw, explain, LintConfig};
+use rue_fmt::format;
+use rue_parser::parse;
+
+mod json;
+mod report;
 
 /// CLI tools for working with the Rue compiler.
 #[derive(Parser, Debug)]
@@ -24,6 +31,14 @@ enum Command {
         /// Whether to exclude the standard library.
         #[clap(long, short = 'n')]
         no_std: bool,
+
+        /// How diagnostics and the build output are printed.
+        #[clap(long, value_enum, default_value_t = MessageFormat::Human)]
+        message_format: MessageFormat,
+
+        /// Treat warnings as errors.
+        #[clap(long)]
+        deny_warnings: bool,
     },
 
     /// Check a Rue source file for errors.
@@ -34,82 +49,224 @@ enum Command {
         /// Whether to exclude the standard library.
         #[clap(long, short = 'n')]
         no_std: bool,
+
+        /// How diagnostics are printed.
+        #[clap(long, value_enum, default_value_t = MessageFormat::Human)]
+        message_format: MessageFormat,
+
+        /// Treat warnings as errors.
+        #[clap(long)]
+        deny_warnings: bool,
+    },
+
+    /// Format Rue source files in place.
+    Fmt {
+        /// The source files to format.
+        #[clap(required = true)]
+        files: Vec<String>,
+
+        /// Check whether the files are formatted, without changing them.
+        #[clap(long)]
+        check: bool,
+    },
+
+    /// Explain a diagnostic code, such as `E0010`, with an example of how to fix it.
+    Explain {
+        /// The code to explain.
+        code: String,
     },
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum MessageFormat {
+    /// Diagnostics are rendered with source snippets.
+    Human,
+    /// Each diagnostic, followed by the build result, is printed as a line of JSON.
+    Json,
+}
+
 fn main() {
     match Command::parse() {
-        Command::Build { file, run, no_std } => build(file, true, &run, no_std),
-        Command::Check { file, no_std } => build(file, false, &None, no_std),
+        Command::Build {
+            file,
+            run,
+            no_std,
+            message_format,
+            deny_warnings,
+        } => build(
+            &file,
+            true,
+            run.as_ref(),
+            no_std,
+            message_format,
+            deny_warnings,
+        ),
+        Command::Check {
+            file,
+            no_std,
+            message_format,
+            deny_warnings,
+        } => build(&file, false, None, no_std, message_format, deny_warnings),
+        Command::Fmt { files, check } => {
+            if !fmt(&files, check) {
+                process::exit(1);
+            }
+        }
+        Command::Explain { code } => {
+            if let Some(explanation) = explain(&code.to_uppercase()) {
+                print!("{explanation}");
+            } else {
+                eprintln!("Unknown diagnostic code `{code}`");
+                process::exit(1);
+            }
+        }
     }
 }
 
-fn build(file: String, should_compile: bool, run: &Option<Option<String>>, no_std: bool) {
-    let source = fs::read_to_string(file).expect("could not read source file");
-    let (ast, errors) = parse(&source);
+/// Formats each file, returning whether they were all formatted successfully.
+/// When checking, the files are left unchanged, and any which would change are reported instead.
+fn fmt(files: &[String], check: bool) -> bool {
+    let mut success = true;
+
+    for file in files {
+        let source = fs::read_to_string(file).expect("could not read source file");
 
-    for error in &errors {
-        let LineCol { line, col } = line_col(&source, error.span().start);
-        let line = line + 1;
-        let col = col + 1;
+        let formatted = match format(&source) {
+            Ok(formatted) => formatted,
+            Err(errors) => {
+                let reports: Vec<Report> = errors.iter().map(Report::from_parser_error).collect();
+                print_reports(file, &source, &reports);
+                success = false;
+                continue;
+            }
+        };
 
-        eprintln!("Error: {} ({line}:{col})", error.kind());
+        if formatted == source {
+            continue;
+        }
+
+        if check {
+            eprintln!("Not formatted: {file}");
+            success = false;
+        } else {
+            fs::write(file, formatted).expect("could not write source file");
+        }
     }
 
+    success
+}
+
+fn build(
+    file: &str,
+    should_compile: bool,
+    run: Option<&Option<String>>,
+    no_std: bool,
+    message_format: MessageFormat,
+    deny_warnings: bool,
+) {
+    let source = fs::read_to_string(file).expect("could not read source file");
+    let (ast, errors) = parse(&source);
+
+    // Lint levels are read from the project manifest, if there is one.
+    let mut lints = LintConfig::load(Path::new(file)).unwrap_or_else(|error| {
+        eprintln!("Error: {error}");
+        process::exit(1);
+    });
+    lints.set_deny_warnings(deny_warnings);
+
     let mut allocator = Allocator::new();
     let output = compile_raw(
         &mut allocator,
         &ast,
         should_compile && errors.is_empty(),
         !no_std,
+        &lints,
     );
 
-    if print_diagnostics(&source, &output.diagnostics) {
-        return;
-    }
+    let reports: Vec<Report> = errors
+        .iter()
+        .map(Report::from_parser_error)
+        .chain(output.diagnostics.iter().map(Report::from_diagnostic))
+        .collect();
 
-    if should_compile {
-        let bytes = node_to_bytes(&allocator, output.node_ptr).unwrap();
-        println!("{}", hex::encode(bytes));
-    } else {
-        println!("No errors found.");
-    }
+    let mut result = BuildMessage {
+        success: !reports
+            .iter()
+            .any(|report| report.severity == Severity::Error),
+        ..Default::default()
+    };
 
-    if let Some(run) = run {
-        let environment = run.as_ref().map_or(NodePtr::NIL, |run| {
-            parse_clvm(&mut allocator, run).expect("could not parse input")
-        });
+    if result.success {
+        if should_compile {
+            let bytes = node_to_bytes(&allocator, output.node_ptr).unwrap();
+            result.hex = Some(hex::encode(&bytes));
+            result.tree_hash = Some(hex::encode(tree_hash(&allocator, output.node_ptr)));
+            result.size = Some(bytes.len());
+        }
 
-        match run_clvm(&mut allocator, output.node_ptr, environment, u64::MAX) {
-            Ok((result, cost)) => {
-                eprintln!("Result: {}", stringify_clvm(&allocator, result).unwrap());
-                eprintln!("Cost: {cost}");
-            }
-            Err(error) => {
-                eprintln!("Error: {}", stringify_clvm(&allocator, error.0).unwrap());
+        if let Some(run) = run {
+            let environment = run.as_ref().map_or(NodePtr::NIL, |run| {
+                parse_clvm(&mut allocator, run).expect("could not parse input")
+            });
+
+            match run_clvm(&mut allocator, output.node_ptr, environment, u64::MAX) {
+                Ok((value, cost)) => {
+                    result.output = Some(stringify_clvm(&allocator, value).unwrap());
+                    result.cost = Some(cost);
+                }
+                Err(error) => {
+                    result.error = Some(stringify_clvm(&allocator, error.0).unwrap());
+                }
             }
         }
     }
-}
 
-fn print_diagnostics(source: &str, diagnostics: &[Diagnostic]) -> bool {
-    let mut has_error = false;
+    match message_format {
+        MessageFormat::Human => {
+            print_reports(file, &source, &reports);
 
-    for error in diagnostics {
-        let LineCol { line, col } = line_col(source, error.span().start);
-        let line = line + 1;
-        let col = col + 1;
+            if !result.success {
+                process::exit(1);
+            }
 
-        match error.kind() {
-            DiagnosticKind::Error(kind) => {
-                has_error = true;
-                eprintln!("Error: {kind} ({line}:{col})");
+            if let Some(hex) = result.hex {
+                println!("{hex}");
+            } else {
+                println!("No errors found.");
             }
-            DiagnosticKind::Warning(kind) => {
-                eprintln!("Warning: {kind} ({line}:{col})");
+
+            if let Some(output) = result.output {
+                eprintln!("Result: {output}");
+            }
+
+            if let Some(cost) = result.cost {
+                eprintln!("Cost: {cost}");
+            }
+
+            if let Some(error) = result.error {
+                eprintln!("Error: {error}");
+            }
+        }
+        MessageFormat::Json => {
+            for report in &reports {
+                Message::Diagnostic(DiagnosticMessage::new(file, &source, report)).print();
+            }
+
+            let success = result.success;
+            Message::Build(result).print();
+
+            if !success {
+                process::exit(1);
             }
         }
     }
+}
+
+/// Renders each report to stderr.
+fn print_reports(file: &str, source: &str, reports: &[Report]) {
+    let renderer = Renderer::new();
 
-    has_error
+    for report in reports {
+        eprintln!("{}", renderer.render(file, source, report));
+    }
 }
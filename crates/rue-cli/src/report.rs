@@ -0,0 +1,427 @@
+use std::{env, fmt::Write, io::IsTerminal, ops::Range};
+
+use rue_compiler::{Diagnostic, DiagnosticData, DiagnosticKind, Label};
+use rue_parser::ParserError;
+use serde::Serialize;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+/// A diagnostic from either the parser or the compiler, which can be rendered in the same way.
+#[derive(Debug, Clone)]
+pub struct Report {
+    pub severity: Severity,
+    pub code: &'static str,
+    pub message: String,
+    pub span: Range<usize>,
+    pub labels: Vec<Label>,
+    pub suggestion: Option<String>,
+    pub note: Option<String>,
+    pub data: Option<DiagnosticData>,
+}
+
+impl Report {
+    pub fn from_parser_error(error: &ParserError) -> Self {
+        Self {
+            severity: Severity::Error,
+            code: error.kind().code(),
+            message: error.kind().to_string(),
+            span: error.span().clone(),
+            labels: Vec::new(),
+            suggestion: None,
+            note: None,
+            data: None,
+        }
+    }
+
+    pub fn from_diagnostic(diagnostic: &Diagnostic) -> Self {
+        let message = match diagnostic.kind() {
+            DiagnosticKind::Error(kind) => kind.to_string(),
+            DiagnosticKind::Warning(kind) => kind.to_string(),
+        };
+
+        // Warnings which are denied by their lint level are reported as errors.
+        let severity = if diagnostic.is_error() {
+            Severity::Error
+        } else {
+            Severity::Warning
+        };
+
+        Self {
+            severity,
+            code: diagnostic.kind().code(),
+            message,
+            span: diagnostic.span().clone(),
+            labels: diagnostic.labels().to_vec(),
+            suggestion: diagnostic.suggestion().map(str::to_string),
+            note: diagnostic.note().map(str::to_string),
+            data: diagnostic.data().cloned(),
+        }
+    }
+}
+
+const RED: &str = "\x1b[1;31m";
+const YELLOW: &str = "\x1b[1;33m";
+const BLUE: &str = "\x1b[1;34m";
+const BOLD: &str = "\x1b[1m";
+const RESET: &str = "\x1b[0m";
+
+/// Spans longer than this many lines only show the first and last lines.
+const MAX_SPAN_LINES: usize = 4;
+
+/// Renders reports with the source code they point to, similarly to rustc.
+#[derive(Debug, Clone, Copy)]
+pub struct Renderer {
+    color: bool,
+}
+
+/// A single underline on one line of the source.
+#[derive(Debug, Clone)]
+struct Mark {
+    line: usize,
+    start: usize,
+    end: usize,
+    primary: bool,
+    message: Option<String>,
+}
+
+impl Renderer {
+    /// Colors are only used when writing to a terminal, and `NO_COLOR` isn't set.
+    pub fn new() -> Self {
+        Self {
+            color: std::io::stderr().is_terminal() && env::var_os("NO_COLOR").is_none(),
+        }
+    }
+
+    fn paint(self, color: &str, text: &str) -> String {
+        if self.color {
+            format!("{color}{text}{RESET}")
+        } else {
+            text.to_string()
+        }
+    }
+
+    pub fn render(self, file: &str, source: &str, report: &Report) -> String {
+        let lines = line_starts(source);
+        let (severity, color) = match report.severity {
+            Severity::Error => ("error", RED),
+            Severity::Warning => ("warning", YELLOW),
+        };
+
+        let mut marks = span_marks(source, &lines, &report.span, true, None);
+
+        for label in &report.labels {
+            marks.extend(span_marks(
+                source,
+                &lines,
+                &label.span,
+                false,
+                Some(label.message.clone()),
+            ));
+        }
+
+        marks.sort_by_key(|mark| (mark.line, !mark.primary, mark.start));
+
+        let mut shown: Vec<usize> = marks.iter().map(|mark| mark.line).collect();
+        shown.dedup();
+
+        let gutter = (shown.last().copied().unwrap_or(0) + 1).to_string().len();
+        let blank = " ".repeat(gutter);
+
+        let (line, column) = line_column(source, &lines, report.span.start);
+
+        let mut output = String::new();
+
+        writeln!(
+            output,
+            "{}{}",
+            self.paint(color, &format!("{severity}[{}]", report.code)),
+            self.paint(BOLD, &format!(": {}", report.message))
+        )
+        .unwrap();
+
+        writeln!(
+            output,
+            "{blank}{} {file}:{}:{}",
+            self.paint(BLUE, "-->"),
+            line + 1,
+            column + 1
+        )
+        .unwrap();
+
+        writeln!(output, "{blank} {}", self.paint(BLUE, "|")).unwrap();
+
+        let mut previous: Option<usize> = None;
+
+        for &line in &shown {
+            if previous.is_some_and(|previous| line > previous + 1) {
+                writeln!(output, "{}", self.paint(BLUE, "...")).unwrap();
+            }
+            previous = Some(line);
+
+            let text = expand_tabs(line_text(source, &lines, line));
+
+            writeln!(
+                output,
+                "{} {} {}",
+                self.paint(BLUE, &format!("{:>gutter$}", line + 1)),
+                self.paint(BLUE, "|"),
+                text
+            )
+            .unwrap();
+
+            for mark in marks.iter().filter(|mark| mark.line == line) {
+                let (symbol, color) = if mark.primary {
+                    ("^", color)
+                } else {
+                    ("-", BLUE)
+                };
+
+                let mut underline = symbol.repeat(mark.end.saturating_sub(mark.start).max(1));
+
+                if let Some(message) = &mark.message {
+                    underline.push(' ');
+                    underline.push_str(message);
+                }
+
+                writeln!(
+                    output,
+                    "{blank} {} {}{}",
+                    self.paint(BLUE, "|"),
+                    " ".repeat(mark.start),
+                    self.paint(color, &underline)
+                )
+                .unwrap();
+            }
+        }
+
+        if let Some(note) = &report.note {
+            writeln!(
+                output,
+                "{blank} {} {}: {note}",
+                self.paint(BLUE, "="),
+                self.paint(BOLD, "note")
+            )
+            .unwrap();
+        }
+
+        if let Some(suggestion) = &report.suggestion {
+            writeln!(
+                output,
+                "{blank} {} {}: did you mean `{suggestion}`?",
+                self.paint(BLUE, "="),
+                self.paint(BOLD, "help")
+            )
+            .unwrap();
+        }
+
+        output
+    }
+}
+
+/// The underlines for every line covered by the span, with the message on the last one.
+fn span_marks(
+    source: &str,
+    lines: &[usize],
+    span: &Range<usize>,
+    primary: bool,
+    message: Option<String>,
+) -> Vec<Mark> {
+    let start = span.start.min(source.len());
+    let mut end = span.end.clamp(start, source.len());
+
+    // Syntax nodes include the trivia after them, which shouldn't be underlined.
+    while end > start && source[..end].ends_with(char::is_whitespace) {
+        end = source[..end].trim_end().len().max(start);
+    }
+
+    let (first_line, first_column) = line_column(source, lines, start);
+    let (end_line, end_column) = line_column(source, lines, end);
+
+    // Spans which end at the start of a line don't include that line.
+    let last_line = if end_line > first_line && end_column == 0 {
+        end_line - 1
+    } else {
+        end_line
+    };
+
+    let mut marks = Vec::new();
+
+    for line in first_line..=last_line {
+        if last_line - first_line >= MAX_SPAN_LINES
+            && line >= first_line + MAX_SPAN_LINES / 2
+            && line < last_line
+        {
+            continue;
+        }
+
+        let text = line_text(source, lines, line);
+        let width = display_width(text);
+        let start = if line == first_line {
+            first_column
+        } else {
+            display_width(&text[..text.len() - text.trim_start().len()])
+        };
+        let end = if line == end_line { end_column } else { width };
+
+        marks.push(Mark {
+            line,
+            start,
+            end: end.max(start),
+            primary,
+            message: None,
+        });
+    }
+
+    if let Some(mark) = marks.last_mut() {
+        mark.message = message;
+    }
+
+    marks
+}
+
+fn line_starts(source: &str) -> Vec<usize> {
+    let mut starts = vec![0];
+
+    for (index, character) in source.char_indices() {
+        if character == '\n' {
+            starts.push(index + 1);
+        }
+    }
+
+    starts
+}
+
+fn line_text<'a>(source: &'a str, lines: &[usize], line: usize) -> &'a str {
+    let start = lines[line];
+    let end = lines.get(line + 1).copied().unwrap_or(source.len());
+    source[start..end].trim_end_matches(['\n', '\r'])
+}
+
+/// The zero-based line, and the display column of the offset within that line.
+fn line_column(source: &str, lines: &[usize], offset: usize) -> (usize, usize) {
+    let mut offset = offset.min(source.len());
+
+    while !source.is_char_boundary(offset) {
+        offset -= 1;
+    }
+
+    let line = lines.partition_point(|&start| start <= offset) - 1;
+    let column = display_width(&source[lines[line]..offset]);
+
+    (line, column)
+}
+
+fn display_width(text: &str) -> usize {
+    text.chars()
+        .map(|character| if character == '\t' { 4 } else { 1 })
+        .sum()
+}
+
+fn expand_tabs(text: &str) -> String {
+    text.replace('\t', "    ")
+}
+
+#[cfg(test)]
+mod tests {
+    use indoc::indoc;
+
+    use super::*;
+
+    const SOURCE: &str = indoc! {"
+        fun main() -> Int {
+            let a = 1;
+            let b = 2;
+            let c = 3;
+            let d = 4;
+            let e = 5;
+            let f = 6;
+            let g = 7;
+            let h = 8;
+            let total = a + b + c + d + e + f + g + h;
+            total
+        }
+    "};
+
+    fn report(span: Range<usize>) -> Report {
+        Report {
+            severity: Severity::Error,
+            code: "E0000",
+            message: "something went wrong".to_string(),
+            span,
+            labels: Vec::new(),
+            suggestion: None,
+            note: None,
+            data: None,
+        }
+    }
+
+    fn span_of(needle: &str) -> Range<usize> {
+        let start = SOURCE.find(needle).expect("missing text in source");
+        start..start + needle.len()
+    }
+
+    fn render(report: &Report) -> String {
+        Renderer { color: false }.render("main.rue", SOURCE, report)
+    }
+
+    #[test]
+    fn test_render_labels() {
+        let mut report = report(span_of("total\n"));
+        report
+            .labels
+            .push(Label::new(span_of("a = 1"), "first defined here"));
+        report.labels.push(Label::new(
+            span_of("a + b + c + d + e + f + g + h"),
+            "summed here",
+        ));
+        report.note = Some("a note about the error".to_string());
+        report.suggestion = Some("other".to_string());
+
+        // The gutter fits the widest line number, and lines between the marks are elided.
+        assert_eq!(
+            render(&report),
+            indoc! {"
+                error[E0000]: something went wrong
+                  --> main.rue:11:5
+                   |
+                 2 |     let a = 1;
+                   |         ----- first defined here
+                ...
+                10 |     let total = a + b + c + d + e + f + g + h;
+                   |                 ----------------------------- summed here
+                11 |     total
+                   |     ^^^^^
+                   = note: a note about the error
+                   = help: did you mean `other`?
+            "}
+        );
+    }
+
+    #[test]
+    fn test_render_long_span() {
+        let start = SOURCE.find("let b").unwrap();
+        let end = SOURCE.find("let h").unwrap();
+
+        // Long spans only show their first two lines and their last line.
+        assert_eq!(
+            render(&report(start..end)),
+            indoc! {"
+                error[E0000]: something went wrong
+                 --> main.rue:3:5
+                  |
+                3 |     let b = 2;
+                  |     ^^^^^^^^^^
+                4 |     let c = 3;
+                  |     ^^^^^^^^^^
+                ...
+                8 |     let g = 7;
+                  |     ^^^^^^^^^^
+            "}
+        );
+    }
+}
@@ -8,6 +8,9 @@ pub enum Error {
     #[error("Eval error: {0}")]
     Eval(#[from] EvalErr),
 
+    #[error("Parse error: {0}")]
+    Parse(String),
+
     #[error("Io error: {0}")]
     Io(#[from] io::Error),
 }
@@ -4,7 +4,7 @@ use clvmr::{
     Allocator, NodePtr,
 };
 
-use crate::Result;
+use crate::{Error, Result};
 
 pub fn stringify_clvm(allocator: &Allocator, ptr: NodePtr) -> Result<String> {
     let mut old_allocator = clvmr_old::allocator::Allocator::new();
@@ -15,7 +15,8 @@ pub fn stringify_clvm(allocator: &Allocator, ptr: NodePtr) -> Result<String> {
 
 pub fn parse_clvm(allocator: &mut Allocator, source: &str) -> Result<NodePtr> {
     let mut old_allocator = clvmr_old::Allocator::new();
-    let input_ptr = binutils::assemble(&mut old_allocator, source).unwrap();
+    let input_ptr =
+        binutils::assemble(&mut old_allocator, source).map_err(|error| Error::Parse(error.1))?;
     let input_bytes = clvmr_old::serde::node_to_bytes(&old_allocator, input_ptr).unwrap();
     Ok(node_from_bytes(allocator, &input_bytes)?)
 }
@@ -1,9 +1,11 @@
 mod error;
 mod ir;
+mod merkle;
 mod path;
 mod run;
 
 pub use error::*;
 pub use ir::*;
+pub use merkle::*;
 pub use path::*;
 pub use run::*;
@@ -0,0 +1,212 @@
+use clvmr::{
+    reduction::EvalErr,
+    sha2::{Digest, Sha256},
+    Allocator, NodePtr,
+};
+use num_bigint::BigInt;
+
+const LEAF_PREFIX: u8 = 1;
+const NODE_PREFIX: u8 = 2;
+
+/// A merkle tree in the format used by Chia puzzles, such as `DataLayer` and offers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MerkleTree {
+    root: [u8; 32],
+    proofs: Vec<MerkleProof>,
+}
+
+/// An inclusion proof for a single leaf, which is represented as `(path . hashes)` in CLVM.
+/// Each bit of the path is set if the corresponding hash is on the left.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MerkleProof {
+    pub path: u64,
+    pub hashes: Vec<[u8; 32]>,
+}
+
+impl MerkleTree {
+    /// Builds a tree by splitting the leaves in half, rounding up, until each half is a single leaf.
+    /// Returns `None` if there are no leaves, since the tree would have no root.
+    pub fn new(leaves: &[[u8; 32]]) -> Option<Self> {
+        if leaves.is_empty() {
+            return None;
+        }
+        let (root, proofs) = build(leaves);
+        Some(Self { root, proofs })
+    }
+
+    pub fn root(&self) -> [u8; 32] {
+        self.root
+    }
+
+    /// The proof for the leaf at the given index.
+    pub fn proof(&self, index: usize) -> Option<&MerkleProof> {
+        self.proofs.get(index)
+    }
+}
+
+impl MerkleProof {
+    /// Calculates the root of the tree that the leaf is in, if the proof is valid.
+    pub fn root(&self, leaf: [u8; 32]) -> [u8; 32] {
+        let mut hash = hash_leaf(leaf);
+        for (i, &sibling) in self.hashes.iter().enumerate() {
+            hash = if self.path >> i & 1 == 1 {
+                hash_node(sibling, hash)
+            } else {
+                hash_node(hash, sibling)
+            };
+        }
+        hash
+    }
+
+    pub fn to_clvm(&self, allocator: &mut Allocator) -> Result<NodePtr, EvalErr> {
+        let mut hashes = NodePtr::NIL;
+        for hash in self.hashes.iter().rev() {
+            let hash = allocator.new_atom(hash)?;
+            hashes = allocator.new_pair(hash, hashes)?;
+        }
+        let path = allocator.new_number(BigInt::from(self.path))?;
+        allocator.new_pair(path, hashes)
+    }
+}
+
+fn build(leaves: &[[u8; 32]]) -> ([u8; 32], Vec<MerkleProof>) {
+    if let [leaf] = leaves {
+        let proof = MerkleProof {
+            path: 0,
+            hashes: Vec::new(),
+        };
+        return (hash_leaf(*leaf), vec![proof]);
+    }
+
+    let (left, right) = leaves.split_at(leaves.len().div_ceil(2));
+    let (left_root, mut left_proofs) = build(left);
+    let (right_root, mut right_proofs) = build(right);
+
+    for proof in &mut left_proofs {
+        proof.hashes.push(right_root);
+    }
+
+    for proof in &mut right_proofs {
+        proof.path |= 1 << proof.hashes.len();
+        proof.hashes.push(left_root);
+    }
+
+    left_proofs.extend(right_proofs);
+    (hash_node(left_root, right_root), left_proofs)
+}
+
+fn hash_leaf(leaf: [u8; 32]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update([LEAF_PREFIX]);
+    hasher.update(leaf);
+    hasher.finalize().into()
+}
+
+fn hash_node(left: [u8; 32], right: [u8; 32]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update([NODE_PREFIX]);
+    hasher.update(left);
+    hasher.update(right);
+    hasher.finalize().into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn leaves(count: u8) -> Vec<[u8; 32]> {
+        (0..count).map(|i| [i; 32]).collect()
+    }
+
+    fn hash(text: &str) -> [u8; 32] {
+        hex::decode(text).unwrap().try_into().unwrap()
+    }
+
+    #[test]
+    fn test_empty_tree() {
+        assert_eq!(MerkleTree::new(&[]), None);
+    }
+
+    #[test]
+    fn test_single_leaf() {
+        let leaf = [42; 32];
+        let tree = MerkleTree::new(&[leaf]).unwrap();
+        let proof = tree.proof(0).unwrap();
+
+        assert_eq!(tree.root(), hash_leaf(leaf));
+        assert_eq!(proof.path, 0);
+        assert!(proof.hashes.is_empty());
+        assert_eq!(proof.root(leaf), tree.root());
+        assert_eq!(tree.proof(1), None);
+    }
+
+    #[test]
+    fn test_odd_leaves() {
+        let leaves = leaves(3);
+        let tree = MerkleTree::new(&leaves).unwrap();
+
+        // The left half gets the extra leaf.
+        let left = hash_node(hash_leaf(leaves[0]), hash_leaf(leaves[1]));
+        assert_eq!(tree.root(), hash_node(left, hash_leaf(leaves[2])));
+
+        for (index, &leaf) in leaves.iter().enumerate() {
+            assert_eq!(tree.proof(index).unwrap().root(leaf), tree.root());
+        }
+    }
+
+    #[test]
+    fn test_first_and_last_proofs() {
+        let leaves = leaves(5);
+        let tree = MerkleTree::new(&leaves).unwrap();
+
+        let first = tree.proof(0).unwrap();
+        assert_eq!(first.path, 0);
+        assert_eq!(first.hashes.len(), 3);
+        assert_eq!(first.root(leaves[0]), tree.root());
+
+        let last = tree.proof(4).unwrap();
+        assert_eq!(last.path, 0b11);
+        assert_eq!(last.hashes.len(), 2);
+        assert_eq!(last.root(leaves[4]), tree.root());
+
+        // A proof only holds for its own leaf.
+        assert_ne!(first.root(leaves[4]), tree.root());
+        assert_ne!(last.root(leaves[0]), tree.root());
+    }
+
+    #[test]
+    fn test_proof_to_clvm() {
+        let leaves = leaves(3);
+        let tree = MerkleTree::new(&leaves).unwrap();
+        let proof = tree.proof(2).unwrap();
+
+        let mut allocator = Allocator::new();
+        let ptr = proof.to_clvm(&mut allocator).unwrap();
+
+        let (path, hashes) = allocator.next(ptr).unwrap();
+        assert_eq!(allocator.number(path), BigInt::from(proof.path));
+
+        let (hash, rest) = allocator.next(hashes).unwrap();
+        assert_eq!(allocator.atom(hash).as_ref(), proof.hashes[0]);
+        assert!(allocator.next(rest).is_none());
+    }
+
+    #[test]
+    fn test_chia_proof() {
+        // An inclusion proof from the Chia bridge's `merkle_tree_a2c` puzzle tests,
+        // which is checked with `simplify_merkle_proof` from `merkle_utils.clib`.
+        let leaf = hash("47194347579b7aa1ede51c52ddfd4200d8b560828051608ce599c763fd99291a");
+        let proof = MerkleProof {
+            path: 0,
+            hashes: vec![
+                hash("fb3b5605bc59e423b7df9c3bcfa7f559d6cdfcb9a49645dd801b3b24d6e9c439"),
+                hash("e925a16b925dc355611f46c900ff0c182a3ed29a32d76394ea85b14d760d91c6"),
+            ],
+        };
+
+        assert_eq!(
+            proof.root(leaf),
+            hash("79539b34c33bc90bdaa6f9a28d3993a1e34025e5f2061fc57f8ff3edb9fb3b85")
+        );
+    }
+}
@@ -1,4 +1,4 @@
-use clvmr::{Allocator, NodePtr};
+use clvmr::{serde::node_from_bytes, Allocator, NodePtr};
 use num_bigint::BigInt;
 
 use crate::{
@@ -123,6 +123,7 @@ impl<'a> Codegen<'a> {
             Lir::GtBytes(lhs, rhs) => self.gen_gt_bytes(lhs, rhs),
             Lir::Ash(lhs, rhs) => self.gen_ash(lhs, rhs),
             Lir::Rem(lhs, rhs) => self.gen_rem(lhs, rhs),
+            Lir::Clvm(bytes, args) => self.gen_clvm(&bytes, args),
         }
     }
 
@@ -367,6 +368,15 @@ impl<'a> Codegen<'a> {
         self.list(&[self.ops.ash, lhs, rhs])
     }
 
+    fn gen_clvm(&mut self, bytes: &[u8], args: Vec<LirId>) -> NodePtr {
+        // The operands are passed as the environment, so they can be referenced by path.
+        let program = node_from_bytes(self.allocator, bytes).unwrap();
+        let program = self.quote(program);
+        let args: Vec<NodePtr> = args.into_iter().map(|arg| self.gen_lir(arg)).collect();
+        let args = self.runtime_list(&args, NodePtr::NIL);
+        self.list(&[self.ops.a, program, args])
+    }
+
     fn gen_closure_wrapper(&mut self, body: NodePtr, captures: &[NodePtr]) -> NodePtr {
         let runtime_a = self.quote(self.ops.a);
         let runtime_quoted_body = self.runtime_quote(body);
@@ -4,30 +4,47 @@ use rue_typing::{HashMap, TypePath};
 
 pub(crate) use builtins::Builtins;
 
-use rowan::TextRange;
-use rue_typing::{Comparison, TypeId, TypeSystem};
-use symbol_table::SymbolTable;
+use rowan::{TextRange, TextSize};
+use rue_parser::SyntaxNode;
+use rue_typing::{Comparison, Type, TypeId, TypeSystem};
+pub(crate) use symbol_table::SymbolTable;
 
 use crate::{
-    database::{Database, HirId, ScopeId, SymbolId},
+    database::{Database, HirId, ScopeId, SymbolId, VisibleScopes},
     hir::{Hir, Op},
     scope::Scope,
     symbol::{Function, Symbol},
     value::{GuardPath, Value},
-    ErrorKind,
+    ErrorKind, Label,
 };
 
 mod block;
 mod builtins;
+mod conditions;
+mod constant;
 mod context;
 mod expr;
 mod item;
+mod mismatch;
 mod path;
 mod stmt;
+mod suggestion;
 mod symbol_table;
 mod ty;
 
+pub use conditions::*;
+pub(crate) use constant::ConstEvaluator;
 pub use context::*;
+pub(crate) use suggestion::similar_name;
+
+/// The state of the compiler in between compiling modules.
+#[derive(Debug, Clone)]
+pub(crate) struct CompilerSnapshot {
+    scope_stack: Vec<ScopeId>,
+    sym: SymbolTable,
+    builtins: Builtins,
+    generic_instances: HashMap<TypeId, (TypeId, Vec<TypeId>)>,
+}
 
 /// Responsible for lowering the AST into the HIR.
 /// Performs name resolution and type checking.
@@ -65,6 +82,12 @@ pub struct Compiler<'a> {
 
     // Common types and other values that are built-in to the compiler.
     builtins: Builtins,
+
+    // The root of the file currently being compiled, since labels can only point within it.
+    root: Option<SyntaxNode>,
+
+    // Types created by passing generic arguments to an alias, so they can be named after it.
+    generic_instances: HashMap<TypeId, (TypeId, Vec<TypeId>)>,
 }
 
 impl<'a> Compiler<'a> {
@@ -81,9 +104,34 @@ impl<'a> Compiler<'a> {
             is_callee: false,
             sym: SymbolTable::default(),
             builtins,
+            root: None,
+            generic_instances: HashMap::new(),
+        }
+    }
+
+    /// Saves the state which persists between modules, so that compilation can be resumed later.
+    pub(crate) fn snapshot(&self) -> CompilerSnapshot {
+        CompilerSnapshot {
+            scope_stack: self.scope_stack.clone(),
+            sym: self.sym.clone(),
+            builtins: self.builtins,
+            generic_instances: self.generic_instances.clone(),
         }
     }
 
+    /// Resumes compilation from a snapshot, using a copy of the database and type system it was taken from.
+    pub(crate) fn restore(
+        db: &'a mut Database,
+        ty: &'a mut TypeSystem,
+        snapshot: CompilerSnapshot,
+    ) -> Self {
+        let mut compiler = Self::new(db, ty, snapshot.builtins);
+        compiler.scope_stack = snapshot.scope_stack;
+        compiler.sym = snapshot.sym;
+        compiler.generic_instances = snapshot.generic_instances;
+        compiler
+    }
+
     /// Lowering is completed, extract the diagnostics.
     pub fn finish(self) -> SymbolTable {
         self.sym
@@ -124,29 +172,106 @@ impl<'a> Compiler<'a> {
         let mut names = HashMap::new();
 
         for &scope_id in &self.scope_stack {
-            for type_id in self.db.scope(scope_id).local_types() {
-                if let Some(name) = self.db.scope(scope_id).type_name(type_id) {
-                    names.insert(type_id, name.to_string());
+            let scope = self.db.scope(scope_id);
+            for type_id in scope
+                .local_types()
+                .into_iter()
+                .chain(scope.imported_types())
+            {
+                let Some(name) = scope.type_name(type_id) else {
+                    continue;
+                };
+
+                // Variants are named after the enum they belong to.
+                if let Type::Enum(enum_type) = self.ty.get(type_id) {
+                    for (variant_name, &variant_type_id) in &enum_type.variants {
+                        names.insert(variant_type_id, format!("{name}::{variant_name}"));
+                    }
                 }
+
+                names.insert(type_id, name.to_string());
             }
         }
 
-        self.ty.stringify_named(type_id, names)
+        self.ty
+            .stringify_with(type_id, &|type_id| self.named_type(&names, type_id))
+    }
+
+    /// Names a type after its declaration, or after the generic alias it's an instance of, such as `List<Int>`.
+    fn named_type(&self, names: &HashMap<TypeId, String>, type_id: TypeId) -> Option<String> {
+        if let Some(name) = names.get(&type_id) {
+            return Some(name.clone());
+        }
+
+        let (alias_type_id, args) = self.generic_instances.get(&type_id)?;
+        let alias_name = names.get(alias_type_id)?;
+
+        let args: Vec<String> = args
+            .iter()
+            .map(|&arg| {
+                self.ty
+                    .stringify_with(arg, &|type_id| self.named_type(names, type_id))
+            })
+            .collect();
+
+        Some(format!("{alias_name}<{}>", args.join(", ")))
     }
 
     fn type_check(&mut self, from: TypeId, to: TypeId, range: TextRange) {
-        let comparison = if self.allow_generic_inference_stack.last().copied().unwrap() {
+        self.type_check_annotated(from, to, range, None);
+    }
+
+    /// Type checks against an annotation, which is labeled if the expected type has no declaration to point to.
+    fn type_check_annotated(
+        &mut self,
+        from: TypeId,
+        to: TypeId,
+        range: TextRange,
+        annotation: Option<Label>,
+    ) {
+        let infer_generics = self.allow_generic_inference_stack.last().copied().unwrap();
+
+        let comparison = if infer_generics {
             self.ty
                 .compare_with_generics(from, to, &mut self.generic_type_stack, true)
         } else {
             self.ty.compare(from, to)
         };
 
-        if comparison > Comparison::Assignable {
-            self.db.error(
-                ErrorKind::TypeMismatch(self.type_name(from), self.type_name(to)),
-                range,
-            );
+        if comparison <= Comparison::Assignable {
+            return;
+        }
+
+        let mismatch = if infer_generics {
+            self.ty
+                .explain_mismatch(from, to, &self.generic_type_stack, true)
+        } else {
+            self.ty.explain_mismatch(from, to, &[], false)
+        };
+
+        let note = mismatch
+            .as_ref()
+            .and_then(|mismatch| self.mismatch_note(mismatch));
+
+        let expected_types: Vec<TypeId> = mismatch
+            .iter()
+            .map(|mismatch| mismatch.expected)
+            .chain([to])
+            .collect();
+
+        let label = self.declaration_label(&expected_types).or(annotation);
+
+        let diagnostic = self.db.error(
+            ErrorKind::TypeMismatch(self.type_name(from), self.type_name(to)),
+            range,
+        );
+
+        if let Some(note) = note {
+            diagnostic.with_note(note);
+        }
+
+        if let Some(label) = label {
+            diagnostic.with_label(label);
         }
     }
 
@@ -216,6 +341,15 @@ impl<'a> Compiler<'a> {
         }
     }
 
+    /// Records the current scope stack, so tooling can find which names are visible at a given offset.
+    fn record_visible_scopes(&mut self, node: &SyntaxNode, offset: TextSize) {
+        self.db.insert_visible_scopes(VisibleScopes {
+            node: node.clone(),
+            offset,
+            scope_ids: self.scope_stack.clone(),
+        });
+    }
+
     fn scope(&self) -> &Scope {
         self.db
             .scope(self.scope_stack.last().copied().expect("no scope found"))
@@ -3,8 +3,9 @@ use rue_typing::TypeId;
 
 use crate::{
     hir::{Hir, Op},
+    trimmed_range,
     value::Value,
-    ErrorKind,
+    ErrorKind, WarningKind,
 };
 
 use super::{stmt::Statement, Compiler};
@@ -28,6 +29,8 @@ impl Compiler<'_> {
         // Compile all of the items in the block first.
         // This means that statements can use item symbols in any order,
         // but items cannot use statement symbols.
+        self.record_visible_scopes(block.syntax(), block.syntax().text_range().start());
+
         let items = block.items();
         let declarations = self.declare_items(&items);
         self.compile_items(&items, declarations);
@@ -35,8 +38,18 @@ impl Compiler<'_> {
         let mut statements = Vec::new();
         let mut terminator = BlockTerminator::Implicit;
         let mut is_terminated = block.expr().is_some();
+        let mut is_unreachable_reported = false;
 
         for stmt in block.stmts() {
+            let end = stmt.syntax().text_range().end();
+
+            // Only the first statement after a return or raise is reported, to avoid noise.
+            if terminator != BlockTerminator::Implicit && !is_unreachable_reported {
+                self.db
+                    .warning(WarningKind::UnreachableCode, trimmed_range(stmt.syntax()));
+                is_unreachable_reported = true;
+            }
+
             match stmt {
                 Stmt::LetStmt(let_stmt) => {
                     let Some(scope_id) = self.compile_let_stmt(&let_stmt) else {
@@ -101,6 +114,13 @@ impl Compiler<'_> {
                         assert_stmt.syntax().text_range(),
                     );
 
+                    if let Some(condition_expr) = assert_stmt.expr() {
+                        self.check_constant_condition(
+                            &condition,
+                            trimmed_range(condition_expr.syntax()),
+                        );
+                    }
+
                     // If the condition is false, we raise an error.
                     // So we can assume that the condition is true from this point on.
                     // This will be popped in reverse order later after all statements have been lowered.
@@ -132,6 +152,15 @@ impl Compiler<'_> {
                     statements.push(Statement::Assume);
                 }
             }
+
+            self.record_visible_scopes(block.syntax(), end);
+        }
+
+        if let Some(expr) = block.expr() {
+            if terminator != BlockTerminator::Implicit && !is_unreachable_reported {
+                self.db
+                    .warning(WarningKind::UnreachableCode, trimmed_range(expr.syntax()));
+            }
         }
 
         // Compile the expression of the block, if present.
@@ -10,6 +10,7 @@ use crate::{
 };
 
 /// These are the built-in types and most commonly used HIR nodes.
+#[derive(Debug, Clone, Copy)]
 pub struct Builtins {
     pub scope_id: ScopeId,
     pub nil: HirId,
@@ -0,0 +1,206 @@
+use num_bigint::BigInt;
+use rowan::TextRange;
+use rue_typing::{HashMap, Type, TypeId, TypeSystem};
+
+use crate::{hir::Hir, symbol::Symbol, Database, HirId, SymbolId, WarningKind};
+
+use super::ConstEvaluator;
+
+/// Checks the conditions returned by the main function for problems that can be detected statically.
+/// Only conditions which are initialized directly in the returned list are checked.
+pub fn check_conditions(db: &mut Database, ty: &TypeSystem, stdlib: SymbolId, main: SymbolId) {
+    let Some(condition_type_id) = condition_type(db, stdlib) else {
+        return;
+    };
+
+    let Type::Enum(enum_type) = ty.get(condition_type_id) else {
+        return;
+    };
+
+    let variants = enum_type
+        .variants
+        .iter()
+        .map(|(name, &type_id)| (type_id, name.clone()))
+        .collect();
+
+    let Symbol::Function(function) = db.symbol(main).clone() else {
+        return;
+    };
+
+    let mut checker = ConditionChecker {
+        db: &*db,
+        constants: ConstEvaluator::new(db),
+        variants,
+        warnings: Vec::new(),
+    };
+    checker.check_list(function.hir_id, &mut ListState::default());
+
+    for (warning, text_range) in checker.warnings {
+        db.warning(warning, text_range);
+    }
+}
+
+fn condition_type(db: &Database, stdlib: SymbolId) -> Option<TypeId> {
+    let Symbol::Module(module) = db.symbol(stdlib) else {
+        return None;
+    };
+    let condition_id = db.scope(module.scope_id).symbol("condition")?;
+    let Symbol::Module(condition) = db.symbol(condition_id) else {
+        return None;
+    };
+    db.scope(condition.scope_id).ty("Condition")
+}
+
+/// The conditions that have been seen so far in a given branch of the list.
+#[derive(Debug, Default, Clone)]
+struct ListState {
+    my_amounts: Vec<(HirId, Option<BigInt>)>,
+}
+
+struct ConditionChecker<'a> {
+    db: &'a Database,
+    constants: ConstEvaluator<'a>,
+    variants: HashMap<TypeId, String>,
+    warnings: Vec<(WarningKind, TextRange)>,
+}
+
+impl ConditionChecker<'_> {
+    fn check_list(&mut self, hir_id: HirId, state: &mut ListState) {
+        match self.db.hir(hir_id).clone() {
+            Hir::Pair(first, rest) => {
+                self.check_item(first, state);
+                self.check_list(rest, state);
+            }
+            Hir::Definition(_scope_id, hir_id) => self.check_list(hir_id, state),
+            Hir::If(_condition, then_block, else_block) => {
+                // Each branch is checked separately, since only one of them will be returned.
+                let mut else_state = state.clone();
+                self.check_list(then_block, state);
+                self.check_list(else_block, &mut else_state);
+            }
+            Hir::Reference(symbol_id, _) => {
+                if let Symbol::Let(value) = self.db.symbol(symbol_id) {
+                    self.check_list(value.hir_id, state);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn check_item(&mut self, hir_id: HirId, state: &mut ListState) {
+        let Some(initializer) = self.db.variant_initializer(hir_id) else {
+            return;
+        };
+
+        let Some(variant) = self.variants.get(&initializer.type_id) else {
+            return;
+        };
+
+        match variant.as_str() {
+            "CreateCoin" => {
+                let Some(&(amount, text_range)) = initializer.fields.get("amount") else {
+                    return;
+                };
+
+                if let Some(amount) = self.constants.int(amount) {
+                    if amount < BigInt::ZERO {
+                        self.warnings.push((
+                            WarningKind::NegativeCoinAmount(amount.to_string()),
+                            text_range,
+                        ));
+                    }
+                }
+            }
+            "CreateCoinAnnouncement" | "CreatePuzzleAnnouncement" => {
+                let Some(&(message, text_range)) = initializer.fields.get("message") else {
+                    return;
+                };
+
+                if self
+                    .constants
+                    .bytes(message)
+                    .is_some_and(|message| message.is_empty())
+                {
+                    self.warnings
+                        .push((WarningKind::EmptyAnnouncementMessage, text_range));
+                }
+            }
+            "AssertMyAmount" => {
+                let Some(&(amount, _)) = initializer.fields.get("amount") else {
+                    return;
+                };
+
+                let value = self.constants.int(amount);
+
+                let warning = state
+                    .my_amounts
+                    .iter()
+                    .find_map(|previous| self.my_amount_warning(previous, amount, value.as_ref()));
+
+                if let Some(warning) = warning {
+                    self.warnings.push((warning, initializer.text_range));
+                }
+
+                state.my_amounts.push((amount, value));
+            }
+            _ => {}
+        }
+    }
+
+    /// Compares an `AssertMyAmount` condition with an earlier one in the same list.
+    /// Constant amounts can be compared, but otherwise only identical expressions are known to be equal.
+    fn my_amount_warning(
+        &self,
+        (previous, previous_value): &(HirId, Option<BigInt>),
+        amount: HirId,
+        value: Option<&BigInt>,
+    ) -> Option<WarningKind> {
+        match (previous_value, value) {
+            (Some(previous_value), Some(value)) if previous_value != value => {
+                Some(WarningKind::ConflictingAssertMyAmount(
+                    previous_value.to_string(),
+                    value.to_string(),
+                ))
+            }
+            (Some(_), Some(_)) => Some(WarningKind::DuplicateAssertMyAmount),
+            _ if self.is_same_expr(*previous, amount) => Some(WarningKind::DuplicateAssertMyAmount),
+            _ => None,
+        }
+    }
+
+    /// Whether two expressions are written the same way, and therefore have the same value.
+    fn is_same_expr(&self, lhs: HirId, rhs: HirId) -> bool {
+        if lhs == rhs {
+            return true;
+        }
+
+        match (self.db.hir(lhs), self.db.hir(rhs)) {
+            (Hir::Atom(lhs), Hir::Atom(rhs)) => lhs == rhs,
+            (Hir::Reference(lhs, _), Hir::Reference(rhs, _)) => lhs == rhs,
+            (Hir::Pair(lhs_first, lhs_rest), Hir::Pair(rhs_first, rhs_rest)) => {
+                self.is_same_expr(*lhs_first, *rhs_first) && self.is_same_expr(*lhs_rest, *rhs_rest)
+            }
+            (Hir::Op(lhs_op, lhs), Hir::Op(rhs_op, rhs)) => {
+                lhs_op == rhs_op && self.is_same_expr(*lhs, *rhs)
+            }
+            (Hir::BinaryOp(lhs_op, lhs_a, lhs_b), Hir::BinaryOp(rhs_op, rhs_a, rhs_b)) => {
+                lhs_op == rhs_op
+                    && self.is_same_expr(*lhs_a, *rhs_a)
+                    && self.is_same_expr(*lhs_b, *rhs_b)
+            }
+            (
+                Hir::FunctionCall(lhs_callee, lhs_args, lhs_spread),
+                Hir::FunctionCall(rhs_callee, rhs_args, rhs_spread),
+            ) => {
+                lhs_spread == rhs_spread
+                    && lhs_args.len() == rhs_args.len()
+                    && self.is_same_expr(*lhs_callee, *rhs_callee)
+                    && lhs_args
+                        .iter()
+                        .zip(rhs_args)
+                        .all(|(lhs, rhs)| self.is_same_expr(*lhs, *rhs))
+            }
+            _ => false,
+        }
+    }
+}
@@ -0,0 +1,167 @@
+use num_bigint::BigInt;
+use rowan::TextRange;
+use rue_typing::bigint_to_bytes;
+
+use crate::{
+    hir::{BinOp, Hir, Op},
+    symbol::Symbol,
+    value::Value,
+    Database, DiagnosticKind, HirId, SymbolId, WarningKind,
+};
+
+use super::Compiler;
+
+/// Evaluates HIR which only depends on literals and other values known at compile time.
+/// Anything which can't be evaluated simply, such as a function call, is treated as unknown.
+pub(crate) struct ConstEvaluator<'a> {
+    db: &'a Database,
+    constants: bool,
+}
+
+impl<'a> ConstEvaluator<'a> {
+    pub fn new(db: &'a Database) -> Self {
+        Self {
+            db,
+            constants: true,
+        }
+    }
+
+    /// Treats references to constants as unknown, since they're often used as flags.
+    pub fn without_constants(mut self) -> Self {
+        self.constants = false;
+        self
+    }
+
+    pub fn int(&self, hir_id: HirId) -> Option<BigInt> {
+        self.bytes(hir_id)
+            .map(|atom| BigInt::from_signed_bytes_be(&atom))
+    }
+
+    pub fn bytes(&self, hir_id: HirId) -> Option<Vec<u8>> {
+        self.eval(hir_id, &mut Vec::new())
+    }
+
+    pub fn bool(&self, hir_id: HirId) -> Option<bool> {
+        self.bytes(hir_id).map(|atom| !atom.is_empty())
+    }
+
+    fn eval(&self, hir_id: HirId, references: &mut Vec<SymbolId>) -> Option<Vec<u8>> {
+        match self.db.hir(hir_id).clone() {
+            Hir::Atom(atom) => Some(atom),
+            Hir::Definition(_scope_id, hir_id) => self.eval(hir_id, references),
+            Hir::Reference(symbol_id, _) => {
+                // Constants which reference themselves are reported elsewhere.
+                if references.contains(&symbol_id) {
+                    return None;
+                }
+
+                let value = match self.db.symbol(symbol_id) {
+                    Symbol::Let(value) => value.hir_id,
+                    Symbol::Const(value) | Symbol::InlineConst(value) if self.constants => {
+                        value.hir_id
+                    }
+                    _ => return None,
+                };
+
+                references.push(symbol_id);
+                let atom = self.eval(value, references);
+                references.pop().unwrap();
+                atom
+            }
+            Hir::Op(Op::Not, value) => Some(bool_atom(self.eval(value, references)?.is_empty())),
+            Hir::If(condition, then_block, else_block) => {
+                if self.eval(condition, references)?.is_empty() {
+                    self.eval(else_block, references)
+                } else {
+                    self.eval(then_block, references)
+                }
+            }
+            Hir::BinaryOp(BinOp::LogicalAnd, lhs, rhs) => {
+                if self.eval(lhs, references)?.is_empty() {
+                    Some(Vec::new())
+                } else {
+                    self.eval(rhs, references)
+                }
+            }
+            Hir::BinaryOp(BinOp::LogicalOr, lhs, rhs) => {
+                if self.eval(lhs, references)?.is_empty() {
+                    self.eval(rhs, references)
+                } else {
+                    Some(bool_atom(true))
+                }
+            }
+            Hir::BinaryOp(op, lhs, rhs) => {
+                let lhs = self.eval(lhs, references)?;
+                let rhs = self.eval(rhs, references)?;
+                binary_op(op, lhs, rhs)
+            }
+            _ => None,
+        }
+    }
+}
+
+impl Compiler<'_> {
+    /// Warns if the condition of an `if` or `assert` can be evaluated at compile time.
+    pub(super) fn check_constant_condition(&mut self, condition: &Value, text_range: TextRange) {
+        // If there's already a diagnostic for the condition, it doesn't need to be reported again.
+        // Type guards include the whitespace before them, so they only need to overlap the condition.
+        if self.db.diagnostics().iter().any(|diagnostic| {
+            let span = diagnostic.span();
+            let start = usize::from(text_range.start());
+            let end = usize::from(text_range.end());
+
+            let is_inside = span.start >= start && span.end <= end;
+            let is_type_guard = matches!(
+                diagnostic.kind(),
+                DiagnosticKind::Warning(WarningKind::UnnecessaryTypeCheck(..))
+            ) && span.start < end
+                && span.end > start;
+
+            is_inside || is_type_guard
+        }) {
+            return;
+        }
+
+        let Some(value) = ConstEvaluator::new(self.db)
+            .without_constants()
+            .bool(condition.hir_id)
+        else {
+            return;
+        };
+
+        self.db
+            .warning(WarningKind::ConstantCondition(value), text_range);
+    }
+}
+
+fn binary_op(op: BinOp, lhs: Vec<u8>, rhs: Vec<u8>) -> Option<Vec<u8>> {
+    let int = |atom: &[u8]| BigInt::from_signed_bytes_be(atom);
+
+    let atom = match op {
+        BinOp::Add => bigint_to_bytes(int(&lhs) + int(&rhs)),
+        BinOp::Subtract => bigint_to_bytes(int(&lhs) - int(&rhs)),
+        BinOp::Multiply => bigint_to_bytes(int(&lhs) * int(&rhs)),
+        BinOp::LessThan => bool_atom(int(&lhs) < int(&rhs)),
+        BinOp::GreaterThan => bool_atom(int(&lhs) > int(&rhs)),
+        BinOp::LessThanEquals => bool_atom(int(&lhs) <= int(&rhs)),
+        BinOp::GreaterThanEquals => bool_atom(int(&lhs) >= int(&rhs)),
+        BinOp::LessThanBytes => bool_atom(lhs < rhs),
+        BinOp::GreaterThanBytes => bool_atom(lhs > rhs),
+        BinOp::LessThanBytesEquals => bool_atom(lhs <= rhs),
+        BinOp::GreaterThanBytesEquals => bool_atom(lhs >= rhs),
+        BinOp::Equals => bool_atom(lhs == rhs),
+        BinOp::NotEquals => bool_atom(lhs != rhs),
+        BinOp::Concat => [lhs, rhs].concat(),
+        _ => return None,
+    };
+
+    Some(atom)
+}
+
+fn bool_atom(value: bool) -> Vec<u8> {
+    if value {
+        vec![1]
+    } else {
+        Vec::new()
+    }
+}
@@ -12,10 +12,12 @@ use crate::{
     optimizer::Optimizer,
     scope::Scope,
     symbol::{Module, Symbol},
-    Database, SymbolId,
+    Database, SymbolId, STANDARD_LIBRARY,
 };
 
-use super::{builtins::builtins, item::Declarations, symbol_table::SymbolTable, Compiler};
+use super::{
+    builtins::builtins, item::Declarations, symbol_table::SymbolTable, Compiler, CompilerSnapshot,
+};
 
 pub struct CompilerContext<'a> {
     compiler: Compiler<'a>,
@@ -31,8 +33,26 @@ pub fn setup_compiler<'a>(db: &'a mut Database, ty: &'a mut TypeSystem) -> Compi
     }
 }
 
+/// Resumes compiling from a snapshot, such as one taken after the standard library was loaded.
+pub(crate) fn restore_compiler<'a>(
+    db: &'a mut Database,
+    ty: &'a mut TypeSystem,
+    snapshot: CompilerSnapshot,
+) -> CompilerContext<'a> {
+    CompilerContext {
+        compiler: Compiler::restore(db, ty, snapshot),
+        roots: IndexMap::new(),
+    }
+}
+
+impl CompilerContext<'_> {
+    pub(crate) fn snapshot(&self) -> CompilerSnapshot {
+        self.compiler.snapshot()
+    }
+}
+
 pub fn load_standard_library(ctx: &mut CompilerContext<'_>) -> SymbolId {
-    let (root, parser_errors) = parse(include_str!("../../stdlib.rue"));
+    let (root, parser_errors) = parse(STANDARD_LIBRARY);
     assert_eq!(parser_errors, Vec::new());
 
     let (module_id, declarations) = ctx.compiler.declare_root(&root);
@@ -42,13 +62,26 @@ pub fn load_standard_library(ctx: &mut CompilerContext<'_>) -> SymbolId {
         unreachable!();
     };
 
+    let prelude_id = ctx
+        .compiler
+        .db
+        .scope(module.scope_id)
+        .symbol("prelude")
+        .expect("missing prelude module");
+
+    let Symbol::Module(prelude) = ctx.compiler.db.symbol_mut(prelude_id).clone() else {
+        unreachable!();
+    };
+
     let mut scope = Scope::default();
+    scope.define_symbol("std".to_string(), module_id);
 
-    for &symbol_id in &module.exported_symbols {
+    // Everything exported from the prelude is available without an import.
+    for &symbol_id in &prelude.exported_symbols {
         scope.define_symbol(
             ctx.compiler
                 .db
-                .scope(module.scope_id)
+                .scope(prelude.scope_id)
                 .symbol_name(symbol_id)
                 .unwrap()
                 .to_string(),
@@ -56,11 +89,11 @@ pub fn load_standard_library(ctx: &mut CompilerContext<'_>) -> SymbolId {
         );
     }
 
-    for &type_id in &module.exported_types {
+    for &type_id in &prelude.exported_types {
         scope.define_type(
             ctx.compiler
                 .db
-                .scope(module.scope_id)
+                .scope(prelude.scope_id)
                 .type_name(type_id)
                 .unwrap()
                 .to_string(),
@@ -95,13 +128,24 @@ pub fn build_graph(
 ) -> DependencyGraph {
     let mut ignored_symbols = HashSet::new();
     let mut ignored_types = HashSet::new();
+    let mut library_symbols = Vec::new();
+
+    let mut module_ids = library_module_ids.to_vec();
 
-    for &module_id in library_module_ids {
+    // Nested modules of the library are ignored too, since they can be imported.
+    while let Some(module_id) = module_ids.pop() {
         let Symbol::Module(module) = db.symbol_mut(module_id).clone() else {
             unreachable!();
         };
         ignored_symbols.extend(module.exported_symbols.iter().copied());
         ignored_types.extend(module.exported_types.iter().copied());
+
+        for symbol_id in db.scope(module.scope_id).local_symbols() {
+            if let Symbol::Module(..) = db.symbol(symbol_id) {
+                module_ids.push(symbol_id);
+            }
+            library_symbols.push(symbol_id);
+        }
     }
 
     for type_id in ignored_types.clone() {
@@ -111,7 +155,8 @@ pub fn build_graph(
         ignored_types.extend(enum_type.variants.values());
     }
 
-    for symbol_id in ignored_symbols.clone() {
+    // Generic types of library functions are ignored, even if the functions aren't exported.
+    for symbol_id in library_symbols {
         let Symbol::Function(function) = db.symbol_mut(symbol_id).clone() else {
             continue;
         };
@@ -8,6 +8,7 @@ use super::Compiler;
 mod binary_expr;
 mod block_expr;
 mod cast_expr;
+mod clvm_expr;
 mod field_access_expr;
 mod function_call_expr;
 mod group_expr;
@@ -46,10 +47,14 @@ impl Compiler<'_> {
             Expr::IfExpr(if_expr) => self.compile_if_expr(if_expr, expected_type),
             Expr::FunctionCallExpr(call) => self.compile_function_call_expr(call),
             Expr::FieldAccessExpr(field_access) => self.compile_field_access_expr(field_access),
+            Expr::ClvmExpr(clvm) => self.compile_clvm_expr(clvm),
         };
 
         self.is_callee = false;
 
+        self.db
+            .insert_expr_type(expr.syntax().clone(), value.type_id);
+
         value
     }
 }
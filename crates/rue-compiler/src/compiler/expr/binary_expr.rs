@@ -6,7 +6,7 @@ use crate::{
     compiler::Compiler,
     hir::{BinOp, Hir, Op},
     value::{Guard, Value},
-    ErrorKind, HirId,
+    ErrorKind, HirId, WarningKind,
 };
 
 impl Compiler<'_> {
@@ -25,7 +25,7 @@ impl Compiler<'_> {
         let rhs_expr = binary.rhs();
         let rhs = rhs_expr.as_ref();
 
-        match op {
+        let value = match op {
             BinaryOp::Add => self.op_add(&lhs, rhs, text_range),
             BinaryOp::Subtract => self.op_subtract(&lhs, rhs, text_range),
             BinaryOp::Multiply => self.op_multiply(&lhs, rhs, text_range),
@@ -44,6 +44,66 @@ impl Compiler<'_> {
             BinaryOp::BitwiseXor => self.op_bitwise_xor(&lhs, rhs, text_range),
             BinaryOp::LeftArithShift => self.op_left_arith_shift(&lhs, rhs, text_range),
             BinaryOp::RightArithShift => self.op_right_arith_shift(&lhs, rhs, text_range),
+        };
+
+        if matches!(
+            op,
+            BinaryOp::Equals
+                | BinaryOp::NotEquals
+                | BinaryOp::GreaterThan
+                | BinaryOp::LessThan
+                | BinaryOp::GreaterThanEquals
+                | BinaryOp::LessThanEquals
+        ) {
+            self.check_self_comparison(binary, value.hir_id);
+        }
+
+        value
+    }
+
+    fn check_self_comparison(&mut self, binary: &BinaryExpr, hir_id: HirId) {
+        // Not equals is compiled as the inverse of equals.
+        let hir_id = match self.db.hir(hir_id) {
+            Hir::Op(Op::Not, hir_id) => *hir_id,
+            _ => hir_id,
+        };
+
+        let Hir::BinaryOp(_, lhs, rhs) = self.db.hir(hir_id).clone() else {
+            return;
+        };
+
+        if !self.is_same_place(lhs, rhs) {
+            return;
+        }
+
+        let name = binary
+            .lhs()
+            .map(|lhs| lhs.syntax().text().to_string().trim().to_string())
+            .unwrap_or_default();
+
+        self.db.warning(
+            WarningKind::SelfComparison(name),
+            binary.syntax().text_range(),
+        );
+    }
+
+    /// Whether both values refer to the same symbol, or the same field of it.
+    fn is_same_place(&self, lhs: HirId, rhs: HirId) -> bool {
+        match (self.db.hir(lhs), self.db.hir(rhs)) {
+            (Hir::Reference(lhs, _), Hir::Reference(rhs, _)) => lhs == rhs,
+            (Hir::Op(lhs_op @ (Op::First | Op::Rest), lhs), Hir::Op(rhs_op, rhs)) => {
+                lhs_op == rhs_op && self.is_same_place(*lhs, *rhs)
+            }
+            _ => false,
+        }
+    }
+
+    /// Warns if the divisor is a literal zero, since the operation will always raise an error.
+    fn check_division_by_zero(&mut self, rhs: &Value, text_range: TextRange) {
+        if let Hir::Atom(atom) = self.db.hir(rhs.hir_id) {
+            if atom.iter().all(|&byte| byte == 0) {
+                self.db.warning(WarningKind::DivisionByZero, text_range);
+            }
         }
     }
 
@@ -121,6 +181,7 @@ impl Compiler<'_> {
 
         self.type_check(lhs.type_id, self.ty.std().int, text_range);
         self.type_check(rhs.type_id, self.ty.std().int, text_range);
+        self.check_division_by_zero(&rhs, text_range);
         self.binary_op(BinOp::Divide, lhs.hir_id, rhs.hir_id, self.ty.std().int)
     }
 
@@ -131,6 +192,7 @@ impl Compiler<'_> {
 
         self.type_check(lhs.type_id, self.ty.std().int, text_range);
         self.type_check(rhs.type_id, self.ty.std().int, text_range);
+        self.check_division_by_zero(&rhs, text_range);
         self.binary_op(BinOp::Remainder, lhs.hir_id, rhs.hir_id, self.ty.std().int)
     }
 
@@ -0,0 +1,53 @@
+use clvmr::{serde::node_to_bytes, Allocator};
+use rue_clvm::{parse_clvm, Error};
+use rue_parser::ClvmExpr;
+
+use crate::{compiler::Compiler, hir::Hir, value::Value, ErrorKind};
+
+impl Compiler<'_> {
+    pub fn compile_clvm_expr(&mut self, clvm: &ClvmExpr) -> Value {
+        // The annotated type is trusted, since the assembly can't be type checked.
+        let type_id = clvm
+            .ty()
+            .map_or(self.ty.std().unknown, |ty| self.compile_type(ty));
+
+        // The operands are compiled without an expected type, since they can be anything.
+        let args: Vec<_> = clvm
+            .args()
+            .into_iter()
+            .map(|arg| self.compile_expr(&arg, None).hir_id)
+            .collect();
+
+        // If the assembly is missing, it's a parser error.
+        let Some(assembly) = clvm.assembly() else {
+            return self.unknown();
+        };
+
+        // Remove the surrounding quotes, if present, and assemble the program.
+        // Quotes inside of the assembly are kept, since they can delimit CLVM strings.
+        // It's stored serialized, since the database doesn't have access to an allocator.
+        let text = assembly.text();
+        let quote = text.chars().next().unwrap_or('"');
+        let source = text.strip_prefix(quote).unwrap_or(text);
+        let source = source.strip_suffix(quote).unwrap_or(source);
+
+        let mut allocator = Allocator::new();
+
+        let bytes = match parse_clvm(&mut allocator, source) {
+            Ok(ptr) => node_to_bytes(&allocator, ptr).expect("failed to serialize assembly"),
+            Err(error) => {
+                let message = match error {
+                    Error::Parse(message) => message,
+                    error => error.to_string(),
+                };
+                self.db.error(
+                    ErrorKind::InvalidClvmAssembly(message),
+                    assembly.text_range(),
+                );
+                return Value::new(self.builtins.unknown, type_id);
+            }
+        };
+
+        Value::new(self.db.alloc_hir(Hir::Clvm(bytes, args)), type_id)
+    }
+}
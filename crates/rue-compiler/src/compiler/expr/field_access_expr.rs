@@ -2,10 +2,10 @@ use rue_parser::{FieldAccessExpr, SyntaxToken};
 use rue_typing::{deconstruct_items, index_to_path, Struct, Type, TypeId, TypePath, Variant};
 
 use crate::{
-    compiler::Compiler,
+    compiler::{similar_name, Compiler},
     hir::{Hir, Op},
     value::Value,
-    ErrorKind,
+    ErrorKind, Resolution,
 };
 
 impl Compiler<'_> {
@@ -108,11 +108,21 @@ impl Compiler<'_> {
                 .unwrap();
 
         let Some(index) = ty.field_names.get_index_of(name.text()) else {
-            self.db
+            let diagnostic = self
+                .db
                 .error(ErrorKind::UnknownField(name.to_string()), name.text_range());
+
+            if let Some(suggestion) =
+                similar_name(name.text(), ty.field_names.iter().map(String::as_str))
+            {
+                diagnostic.with_suggestion(suggestion);
+            }
             return None;
         };
 
+        self.db
+            .insert_reference(name.clone(), Resolution::Field(ty.original_type_id, index));
+
         let type_id = fields[index];
 
         let path_items = index_to_path(
@@ -157,11 +167,21 @@ impl Compiler<'_> {
         };
 
         let Some(index) = field_names.get_index_of(name.text()) else {
-            self.db
+            let diagnostic = self
+                .db
                 .error(ErrorKind::UnknownField(name.to_string()), name.text_range());
+
+            if let Some(suggestion) =
+                similar_name(name.text(), field_names.iter().map(String::as_str))
+            {
+                diagnostic.with_suggestion(suggestion);
+            }
             return None;
         };
 
+        self.db
+            .insert_reference(name.clone(), Resolution::Field(ty.original_type_id, index));
+
         let type_id = fields[index];
 
         let path_items = index_to_path(index + 1, index != fields.len() - 1 || ty.nil_terminated);
@@ -1,13 +1,30 @@
-use rue_parser::{AstNode, GuardExpr};
+use rue_parser::{AstNode, GuardExpr, SyntaxKind};
 use rue_typing::{bigint_to_bytes, Check, TypeId};
 
 use crate::{
     compiler::Compiler,
     hir::{BinOp, Hir, Op},
+    removal_range, trimmed_range,
     value::{Guard, Value},
-    ErrorKind, HirId, WarningKind,
+    DiagnosticData, ErrorKind, HirId, WarningKind,
 };
 
+/// An assertion of a check which is always true can be removed, otherwise the check is replaced with `true`.
+fn removed_check(guard: &GuardExpr) -> DiagnosticData {
+    if let Some(stmt) = guard.syntax().parent().filter(|parent| {
+        matches!(
+            parent.kind(),
+            SyntaxKind::AssertStmt | SyntaxKind::AssumeStmt
+        )
+    }) {
+        let range = removal_range(&stmt);
+        return DiagnosticData::Remove(range.start().into()..range.end().into());
+    }
+
+    let range = trimmed_range(guard.syntax());
+    DiagnosticData::Replace(range.start().into()..range.end().into(), "true".to_string())
+}
+
 impl Compiler<'_> {
     pub fn compile_guard_expr(
         &mut self,
@@ -35,13 +52,15 @@ impl Compiler<'_> {
 
         match check {
             Check::True => {
-                self.db.warning(
-                    WarningKind::UnnecessaryTypeCheck(
-                        self.type_name(expr.type_id),
-                        self.type_name(rhs),
-                    ),
-                    guard.syntax().text_range(),
-                );
+                self.db
+                    .warning(
+                        WarningKind::UnnecessaryTypeCheck(
+                            self.type_name(expr.type_id),
+                            self.type_name(rhs),
+                        ),
+                        guard.syntax().text_range(),
+                    )
+                    .with_data(removed_check(guard));
             }
             Check::False => {
                 self.db.error(
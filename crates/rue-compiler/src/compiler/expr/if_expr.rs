@@ -1,7 +1,7 @@
 use rue_parser::{AstNode, IfExpr};
 use rue_typing::TypeId;
 
-use crate::{compiler::Compiler, hir::Hir, value::Value};
+use crate::{compiler::Compiler, hir::Hir, trimmed_range, value::Value};
 
 impl Compiler<'_> {
     pub fn compile_if_expr(&mut self, if_expr: &IfExpr, expected_type: Option<TypeId>) -> Value {
@@ -38,12 +38,16 @@ impl Compiler<'_> {
             self.type_overrides.pop().unwrap();
         }
 
-        if let Some(condition_type) = condition.as_ref().map(|condition| condition.type_id) {
+        if let Some(condition) = condition.as_ref() {
+            let condition_expr = if_expr.condition().unwrap();
+
             self.type_check(
-                condition_type,
+                condition.type_id,
                 self.ty.std().bool,
-                if_expr.condition().unwrap().syntax().text_range(),
+                condition_expr.syntax().text_range(),
             );
+
+            self.check_constant_condition(condition, trimmed_range(condition_expr.syntax()));
         }
 
         if let (Some(then_block), Some(else_block)) = (&then_block, &else_block) {
@@ -1,20 +1,26 @@
-use rue_typing::HashMap;
-
 use indexmap::IndexMap;
 use rowan::TextRange;
-use rue_parser::{AstNode, InitializerExpr, InitializerField};
+use rue_parser::{AstNode, InitializerExpr, SyntaxKind};
 use rue_typing::{bigint_to_bytes, deconstruct_items, Type, TypeId};
 
-use crate::{compiler::Compiler, hir::Hir, value::Value, ErrorKind, HirId};
+use crate::{
+    compiler::{similar_name, Compiler},
+    hir::{Hir, VariantInitializer},
+    is_trivia,
+    value::Value,
+    DiagnosticData, ErrorKind, HirId, Resolution,
+};
 
 impl Compiler<'_> {
     pub fn compile_initializer_expr(&mut self, initializer: &InitializerExpr) -> Value {
-        let ty = initializer
-            .path()
-            .map(|path| self.compile_path_type(&path.items(), path.syntax().text_range()));
+        let Some(path) = initializer.path() else {
+            return self.unknown();
+        };
+
+        let ty = self.compile_path_type(&path.items(), path.syntax().text_range());
 
-        match ty.map(|ty| self.ty.get_unaliased(ty)).cloned() {
-            Some(Type::Struct(struct_type)) => {
+        match self.ty.get_unaliased(ty).clone() {
+            Type::Struct(struct_type) => {
                 let fields = deconstruct_items(
                     self.ty,
                     struct_type.type_id,
@@ -23,19 +29,16 @@ impl Compiler<'_> {
                 )
                 .expect("invalid variant type");
 
-                let hir_id = self.compile_initializer_fields(
+                let (hir_id, _) = self.compile_initializer_fields(
                     &struct_type.field_names.into_iter().zip(fields).collect(),
                     struct_type.nil_terminated,
-                    initializer.fields(),
-                    initializer.syntax().text_range(),
+                    struct_type.original_type_id,
+                    initializer,
                 );
 
-                match ty {
-                    Some(struct_type) => Value::new(hir_id, struct_type),
-                    None => self.unknown(),
-                }
+                Value::new(hir_id, ty)
             }
-            Some(Type::Variant(enum_variant)) => {
+            Type::Variant(enum_variant) => {
                 if let Some(field_names) = enum_variant.field_names {
                     let Type::Enum(enum_type) = self.ty.get(enum_variant.original_enum_type_id)
                     else {
@@ -60,11 +63,11 @@ impl Compiler<'_> {
                         Vec::new()
                     };
 
-                    let fields_hir_id = self.compile_initializer_fields(
+                    let (fields_hir_id, specified_fields) = self.compile_initializer_fields(
                         &field_names.into_iter().zip(fields).collect(),
                         enum_variant.nil_terminated,
-                        initializer.fields(),
-                        initializer.syntax().text_range(),
+                        enum_variant.original_type_id,
+                        initializer,
                     );
 
                     let discriminant = self
@@ -73,26 +76,32 @@ impl Compiler<'_> {
 
                     let hir_id = self.db.alloc_hir(Hir::Pair(discriminant, fields_hir_id));
 
-                    match ty {
-                        Some(struct_type) => Value::new(hir_id, struct_type),
-                        None => self.unknown(),
-                    }
+                    // Keep track of the fields, so that conditions can be checked later.
+                    self.db.insert_variant_initializer(
+                        hir_id,
+                        VariantInitializer {
+                            type_id: ty,
+                            fields: specified_fields,
+                            text_range: initializer.syntax().text_range(),
+                        },
+                    );
+
+                    Value::new(hir_id, ty)
                 } else {
                     self.db.error(
-                        ErrorKind::InvalidEnumVariantInitializer(self.type_name(ty.unwrap())),
-                        initializer.path().unwrap().syntax().text_range(),
+                        ErrorKind::InvalidEnumVariantInitializer(self.type_name(ty)),
+                        path.syntax().text_range(),
                     );
                     self.unknown()
                 }
             }
-            Some(..) => {
+            _ => {
                 self.db.error(
-                    ErrorKind::UninitializableType(self.type_name(ty.unwrap())),
-                    initializer.path().unwrap().syntax().text_range(),
+                    ErrorKind::UninitializableType(self.type_name(ty)),
+                    path.syntax().text_range(),
                 );
                 self.unknown()
             }
-            _ => self.unknown(),
         }
     }
 
@@ -100,18 +109,23 @@ impl Compiler<'_> {
         &mut self,
         struct_fields: &IndexMap<String, TypeId>,
         nil_terminated: bool,
-        initializer_fields: Vec<InitializerField>,
-        text_range: TextRange,
-    ) -> HirId {
-        let mut specified_fields = HashMap::new();
+        owner_type_id: TypeId,
+        initializer: &InitializerExpr,
+    ) -> (HirId, IndexMap<String, (HirId, TextRange)>) {
+        let mut specified_fields = IndexMap::new();
 
-        for field in initializer_fields {
+        for field in initializer.fields() {
             let Some(name) = field.name() else {
                 continue;
             };
 
             let expected_type = struct_fields.get(name.text()).copied();
 
+            if let Some(index) = struct_fields.get_index_of(name.text()) {
+                self.db
+                    .insert_reference(name.clone(), Resolution::Field(owner_type_id, index));
+            }
+
             let value = field
                 .expr()
                 .map(|expr| self.compile_expr(&expr, expected_type))
@@ -131,12 +145,26 @@ impl Compiler<'_> {
                     name.text_range(),
                 );
             } else if !struct_fields.contains_key(name.text()) {
-                self.db.error(
+                let suggestion = similar_name(
+                    name.text(),
+                    struct_fields
+                        .keys()
+                        .filter(|field| !specified_fields.contains_key(*field))
+                        .map(String::as_str),
+                );
+                let diagnostic = self.db.error(
                     ErrorKind::UnknownInitializerField(name.to_string()),
                     name.text_range(),
                 );
+
+                if let Some(suggestion) = suggestion {
+                    diagnostic.with_suggestion(suggestion);
+                }
             } else {
-                specified_fields.insert(name.to_string(), value.hir_id);
+                specified_fields.insert(
+                    name.to_string(),
+                    (value.hir_id, field.syntax().text_range()),
+                );
             }
         }
 
@@ -148,17 +176,23 @@ impl Compiler<'_> {
             .collect();
 
         if !missing_fields.is_empty() {
-            self.db.error(
+            let data = self.missing_fields_data(initializer, struct_fields, &missing_fields);
+
+            let diagnostic = self.db.error(
                 ErrorKind::MissingInitializerFields(missing_fields),
-                text_range,
+                initializer.syntax().text_range(),
             );
+
+            if let Some(data) = data {
+                diagnostic.with_data(data);
+            }
         }
 
         let mut hir_id = self.builtins.nil;
 
         // Construct a nil-terminated list from the arguments.
         for (i, field) in struct_fields.keys().rev().enumerate() {
-            let value = specified_fields.get(field).copied();
+            let value = specified_fields.get(field).map(|(hir_id, _)| *hir_id);
 
             let field = value.unwrap_or(self.builtins.unknown);
 
@@ -169,6 +203,38 @@ impl Compiler<'_> {
             }
         }
 
-        hir_id
+        (hir_id, specified_fields)
+    }
+
+    /// Where the missing fields can be inserted, just before the closing brace of the initializer.
+    fn missing_fields_data(
+        &self,
+        initializer: &InitializerExpr,
+        struct_fields: &IndexMap<String, TypeId>,
+        missing_fields: &[String],
+    ) -> Option<DiagnosticData> {
+        let close_brace = initializer
+            .syntax()
+            .children_with_tokens()
+            .filter_map(rowan::NodeOrToken::into_token)
+            .find(|token| token.kind() == SyntaxKind::CloseBrace)?;
+
+        // The fields are inserted directly after the last token before the brace.
+        let mut previous = close_brace.prev_token()?;
+
+        while is_trivia(previous.kind()) {
+            previous = previous.prev_token()?;
+        }
+
+        let needs_comma = !matches!(previous.kind(), SyntaxKind::OpenBrace | SyntaxKind::Comma);
+
+        Some(DiagnosticData::MissingFields {
+            offset: previous.text_range().end().into(),
+            needs_comma,
+            fields: missing_fields
+                .iter()
+                .map(|name| (name.clone(), self.type_name(struct_fields[name])))
+                .collect(),
+        })
     }
 }
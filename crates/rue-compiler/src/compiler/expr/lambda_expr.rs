@@ -102,7 +102,7 @@ impl Compiler<'_> {
                 self.db.insert_symbol_token(symbol_id, name);
             } else {
                 param_names.insert(format!("#{i}"));
-            };
+            }
 
             let last = i + 1 == len;
             let spread = param.spread().is_some();
@@ -127,6 +127,8 @@ impl Compiler<'_> {
             .map(|ty| self.compile_type(ty))
             .or(expected.map(|expected| expected.return_type));
 
+        self.record_visible_scopes(lambda_expr.syntax(), body.syntax().text_range().start());
+
         self.allow_generic_inference_stack.push(false);
         let body = self.compile_expr(&body, expected_return_type);
         self.allow_generic_inference_stack.pop().unwrap();
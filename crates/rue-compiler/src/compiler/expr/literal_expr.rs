@@ -1,7 +1,7 @@
 use rue_parser::{LiteralExpr, SyntaxKind, SyntaxToken};
 use rue_typing::bigint_to_bytes;
 
-use crate::{compiler::Compiler, hir::Hir, value::Value};
+use crate::{compiler::Compiler, hir::Hir, value::Value, ErrorKind};
 
 impl Compiler<'_> {
     pub fn compile_literal_expr(&mut self, literal: &LiteralExpr) -> Value {
@@ -46,14 +46,17 @@ impl Compiler<'_> {
 
     fn compile_hex_literal(&mut self, hex: &SyntaxToken) -> Value {
         // Parse the hex literal into bytes.
-        // It should not be possible to have a syntax error at this point.
-        let bytes = hex::decode(
+        // The lexer only allows hex digits, but there may be an odd number of them.
+        let Ok(bytes) = hex::decode(
             hex.text()
                 .replace("0x", "")
                 .replace("0X", "")
                 .replace('_', ""),
-        )
-        .expect("failed to parse hex literal");
+        ) else {
+            self.db
+                .error(ErrorKind::InvalidHexLiteral, hex.text_range());
+            return self.unknown();
+        };
 
         let bytes_len = bytes.len();
 
@@ -1,19 +1,23 @@
-use rue_typing::HashSet;
+use rowan::TextRange;
+use rue_typing::{HashMap, HashSet};
 
-use rue_parser::Item;
+use rue_parser::{Item, SyntaxToken};
 use rue_typing::{Type, TypeId};
 
-use crate::{symbol::Symbol, ErrorKind, ScopeId, SymbolId};
+use crate::{symbol::Symbol, ErrorKind, Label, ScopeId, SymbolId};
 
 use super::Compiler;
 
 mod const_item;
 mod enum_item;
 mod function_item;
+mod import_item;
 mod module_item;
 mod struct_item;
 mod type_alias_item;
 
+pub use import_item::Imports;
+
 #[derive(Debug, Clone)]
 pub struct Declarations {
     pub type_ids: Vec<TypeId>,
@@ -21,12 +25,26 @@ pub struct Declarations {
     pub scope_ids: Vec<ScopeId>,
     pub exported_types: Vec<TypeId>,
     pub exported_symbols: Vec<SymbolId>,
+    pub modules: Vec<Declarations>,
 }
 
 impl Compiler<'_> {
     /// Declare all items into scope without compiling their body.
     /// This ensures no circular references are resolved at this time.
+    ///
+    /// Every name is declared first, including those within nested modules, so that imports can refer
+    /// to items which are declared later on. The signatures of functions and constants are compiled last,
+    /// since they can reference imported types.
     pub fn declare_items(&mut self, items: &[Item]) -> Declarations {
+        let mut declarations = self.declare_names(items);
+        self.declare_imports(items, &mut declarations);
+        self.declare_signatures(items, &declarations);
+        declarations
+    }
+
+    /// Declare the types, modules, functions and constants in the current scope, but not imports.
+    /// Functions and constants are only given a name, since their signatures may reference imports.
+    fn declare_names(&mut self, items: &[Item]) -> Declarations {
         self.check_item_names(items);
 
         let mut type_ids = Vec::new();
@@ -57,13 +75,21 @@ impl Compiler<'_> {
             }
         }
 
+        let mut modules = Vec::new();
+
         for item in items {
             match item {
-                Item::ModuleItem(module) => symbol_ids.push(self.declare_module_item(module)),
+                Item::ModuleItem(module) => {
+                    let (symbol_id, declarations) = self.declare_module_item(module);
+                    symbol_ids.push(symbol_id);
+                    modules.push(declarations);
+                }
                 Item::FunctionItem(function) => {
-                    symbol_ids.push(self.declare_function_item(function));
+                    symbol_ids.push(self.reserve_symbol(function.name()));
+                }
+                Item::ConstItem(const_item) => {
+                    symbol_ids.push(self.reserve_symbol(const_item.name()));
                 }
-                Item::ConstItem(const_item) => symbol_ids.push(self.declare_const_item(const_item)),
                 Item::TypeAliasItem(..)
                 | Item::StructItem(..)
                 | Item::EnumItem(..)
@@ -81,6 +107,74 @@ impl Compiler<'_> {
             scope_ids,
             exported_types,
             exported_symbols,
+            modules,
+        }
+    }
+
+    /// Define a symbol with the given name in the current scope, which is filled in once its signature is compiled.
+    fn reserve_symbol(&mut self, name: Option<SyntaxToken>) -> SymbolId {
+        let symbol_id = self.db.alloc_symbol(Symbol::Unknown);
+
+        if let Some(name) = name {
+            self.scope_mut().define_symbol(name.to_string(), symbol_id);
+            self.db.insert_symbol_token(symbol_id, name);
+        }
+
+        symbol_id
+    }
+
+    /// Resolve the imports of the items, and then those of any modules within them.
+    /// The imports which are exported again are returned.
+    fn declare_imports(&mut self, items: &[Item], declarations: &mut Declarations) -> Imports {
+        let mut exported_imports = Imports::default();
+
+        for item in items {
+            let Item::ImportItem(import_item) = item else {
+                continue;
+            };
+
+            let imports = self.declare_import_item(import_item);
+
+            if item.export().is_some() {
+                exported_imports.symbol_ids.extend(imports.symbol_ids);
+                exported_imports.type_ids.extend(imports.type_ids);
+            }
+        }
+
+        declarations
+            .exported_symbols
+            .extend(exported_imports.symbol_ids.iter().copied());
+        declarations
+            .exported_types
+            .extend(exported_imports.type_ids.iter().copied());
+
+        let mut modules = declarations.modules.iter_mut();
+
+        for (item, &symbol_id) in symbol_items(items).zip(&declarations.symbol_ids) {
+            if let Item::ModuleItem(module) = item {
+                self.declare_module_imports(module, symbol_id, modules.next().unwrap());
+            }
+        }
+
+        exported_imports
+    }
+
+    /// Compile the signatures of the functions and constants, and then those within any modules.
+    fn declare_signatures(&mut self, items: &[Item], declarations: &Declarations) {
+        let mut modules = declarations.modules.iter();
+
+        for (item, &symbol_id) in symbol_items(items).zip(&declarations.symbol_ids) {
+            match item {
+                Item::FunctionItem(function) => self.declare_function_item(function, symbol_id),
+                Item::ConstItem(const_item) => self.declare_const_item(const_item, symbol_id),
+                Item::ModuleItem(module) => {
+                    self.declare_module_signatures(module, symbol_id, modules.next().unwrap());
+                }
+                Item::TypeAliasItem(..)
+                | Item::StructItem(..)
+                | Item::EnumItem(..)
+                | Item::ImportItem(..) => unreachable!(),
+            }
         }
     }
 
@@ -128,8 +222,9 @@ impl Compiler<'_> {
                     self.compile_const_item(const_item, symbol_id);
                     self.symbol_stack.pop().unwrap();
                 }
-                Item::ModuleItem(..) => {
-                    declarations.symbol_ids.remove(0);
+                Item::ModuleItem(module) => {
+                    let symbol_id = declarations.symbol_ids.remove(0);
+                    self.compile_module_item(module, symbol_id, declarations.modules.remove(0));
                 }
                 Item::TypeAliasItem(..)
                 | Item::StructItem(..)
@@ -139,19 +234,47 @@ impl Compiler<'_> {
         }
     }
 
+    /// Reports a name which has already been defined, along with where it was first defined.
+    fn check_duplicate(
+        &mut self,
+        names: &mut HashMap<String, Option<TextRange>>,
+        name: &SyntaxToken,
+        kind: ErrorKind,
+    ) {
+        let Some(&previous) = names.get(name.text()) else {
+            names.insert(name.to_string(), Some(name.text_range()));
+            return;
+        };
+
+        let diagnostic = self.db.error(kind, name.text_range());
+
+        if let Some(previous) = previous {
+            diagnostic.with_label(Label::new(
+                previous.start().into()..previous.end().into(),
+                "first defined here",
+            ));
+        }
+    }
+
     fn check_item_names(&mut self, items: &[Item]) {
-        let mut type_names: HashSet<String> = self
+        // Names which were already defined aren't labeled, since they may be from another module.
+        let mut type_names: HashMap<String, Option<TextRange>> = self
             .scope()
             .local_types()
             .into_iter()
-            .map(|type_id| self.scope().type_name(type_id).unwrap().to_string())
+            .map(|type_id| (self.scope().type_name(type_id).unwrap().to_string(), None))
             .collect();
 
-        let mut symbol_names: HashSet<String> = self
+        let mut symbol_names: HashMap<String, Option<TextRange>> = self
             .scope()
             .local_symbols()
             .into_iter()
-            .map(|symbol_id| self.scope().symbol_name(symbol_id).unwrap().to_string())
+            .map(|symbol_id| {
+                (
+                    self.scope().symbol_name(symbol_id).unwrap().to_string(),
+                    None,
+                )
+            })
             .collect();
 
         let mut symbol_namespaces: HashSet<String> = self
@@ -187,36 +310,33 @@ impl Compiler<'_> {
                         continue;
                     };
 
-                    if !symbol_names.insert(name.to_string()) {
-                        self.db.error(
-                            ErrorKind::DuplicateSymbol(name.to_string()),
-                            name.text_range(),
-                        );
-                    }
+                    self.check_duplicate(
+                        &mut symbol_names,
+                        &name,
+                        ErrorKind::DuplicateSymbol(name.to_string()),
+                    );
                 }
                 Item::FunctionItem(item) => {
                     let Some(name) = item.name() else {
                         continue;
                     };
 
-                    if !symbol_names.insert(name.to_string()) {
-                        self.db.error(
-                            ErrorKind::DuplicateSymbol(name.to_string()),
-                            name.text_range(),
-                        );
-                    }
+                    self.check_duplicate(
+                        &mut symbol_names,
+                        &name,
+                        ErrorKind::DuplicateSymbol(name.to_string()),
+                    );
                 }
                 Item::ModuleItem(item) => {
                     let Some(name) = item.name() else {
                         continue;
                     };
 
-                    if !symbol_names.insert(name.to_string()) {
-                        self.db.error(
-                            ErrorKind::DuplicateSymbol(name.to_string()),
-                            name.text_range(),
-                        );
-                    }
+                    self.check_duplicate(
+                        &mut symbol_names,
+                        &name,
+                        ErrorKind::DuplicateSymbol(name.to_string()),
+                    );
 
                     if type_namespaces.contains(&name.to_string()) {
                         self.db.error(
@@ -232,12 +352,11 @@ impl Compiler<'_> {
                         continue;
                     };
 
-                    if !type_names.insert(name.to_string()) {
-                        self.db.error(
-                            ErrorKind::DuplicateType(name.to_string()),
-                            name.text_range(),
-                        );
-                    }
+                    self.check_duplicate(
+                        &mut type_names,
+                        &name,
+                        ErrorKind::DuplicateType(name.to_string()),
+                    );
 
                     if symbol_namespaces.contains(&name.to_string()) {
                         self.db.error(
@@ -253,27 +372,35 @@ impl Compiler<'_> {
                         continue;
                     };
 
-                    if !type_names.insert(name.to_string()) {
-                        self.db.error(
-                            ErrorKind::DuplicateType(name.to_string()),
-                            name.text_range(),
-                        );
-                    }
+                    self.check_duplicate(
+                        &mut type_names,
+                        &name,
+                        ErrorKind::DuplicateType(name.to_string()),
+                    );
                 }
                 Item::TypeAliasItem(item) => {
                     let Some(name) = item.name() else {
                         continue;
                     };
 
-                    if !type_names.insert(name.to_string()) {
-                        self.db.error(
-                            ErrorKind::DuplicateType(name.to_string()),
-                            name.text_range(),
-                        );
-                    }
+                    self.check_duplicate(
+                        &mut type_names,
+                        &name,
+                        ErrorKind::DuplicateType(name.to_string()),
+                    );
                 }
-                Item::ImportItem(_item) => todo!(),
+                Item::ImportItem(..) => {}
             }
         }
     }
 }
+
+/// The modules, functions and constants, which are declared as symbols in the same order.
+fn symbol_items(items: &[Item]) -> impl Iterator<Item = &Item> {
+    items.iter().filter(|item| {
+        matches!(
+            item,
+            Item::ModuleItem(..) | Item::FunctionItem(..) | Item::ConstItem(..)
+        )
+    })
+}
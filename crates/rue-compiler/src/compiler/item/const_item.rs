@@ -3,10 +3,10 @@ use rue_parser::{AstNode, ConstItem};
 use crate::{compiler::Compiler, hir::Hir, symbol::Symbol, value::Value, SymbolId};
 
 impl Compiler<'_> {
-    /// Define a constant in the current scope, but don't lower its body.
-    pub fn declare_const_item(&mut self, const_item: &ConstItem) -> SymbolId {
+    /// Compile the type of a constant, whose symbol has already been defined in the current scope,
+    /// but don't lower its body.
+    pub fn declare_const_item(&mut self, const_item: &ConstItem, symbol_id: SymbolId) {
         // Add the symbol to the stack early so you can track type references.
-        let symbol_id = self.db.alloc_symbol(Symbol::Unknown);
         self.symbol_stack.push(symbol_id);
 
         let type_id = const_item
@@ -21,12 +21,7 @@ impl Compiler<'_> {
             *self.db.symbol_mut(symbol_id) = Symbol::Const(Value::new(hir_id, type_id));
         }
 
-        if let Some(name) = const_item.name() {
-            self.scope_mut().define_symbol(name.to_string(), symbol_id);
-            self.db.insert_symbol_token(symbol_id, name);
-        }
-
-        self.symbol_stack.pop().unwrap()
+        self.symbol_stack.pop().unwrap();
     }
 
     /// Compiles a constant's value.
@@ -101,7 +101,7 @@ impl Compiler<'_> {
             // Compile the fields of the variant.
             let (fields, nil_terminated) = variant
                 .fields()
-                .map(|ast| self.compile_struct_fields(ast.fields()))
+                .map(|ast| self.compile_struct_fields(ast.fields(), variant_type_id))
                 .unwrap_or_default();
 
             // Get the discriminant of the variant.
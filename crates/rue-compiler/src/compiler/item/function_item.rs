@@ -6,13 +6,13 @@ use crate::{
     hir::Hir,
     scope::Scope,
     symbol::{Function, Symbol},
-    ErrorKind, SymbolId,
+    trimmed_range, ErrorKind, Label, SymbolId,
 };
 
 impl Compiler<'_> {
-    pub fn declare_function_item(&mut self, function_item: &FunctionItem) -> SymbolId {
+    /// Compile the signature of a function, whose symbol has already been defined in the current scope.
+    pub fn declare_function_item(&mut self, function_item: &FunctionItem, symbol_id: SymbolId) {
         // Add the symbol to the stack so you can track type references.
-        let symbol_id = self.db.alloc_symbol(Symbol::Unknown);
         self.symbol_stack.push(symbol_id);
 
         // Add the scope so you can track generic types.
@@ -145,14 +145,12 @@ impl Compiler<'_> {
             });
         }
 
-        // Add the function to the scope and define the token for the function and scope.
+        // Define the token for the function's scope.
         if let Some(name) = function_item.name() {
-            self.scope_mut().define_symbol(name.to_string(), symbol_id);
-            self.db.insert_scope_token(scope_id, name.clone());
-            self.db.insert_symbol_token(symbol_id, name);
+            self.db.insert_scope_token(scope_id, name);
         }
 
-        self.symbol_stack.pop().unwrap()
+        self.symbol_stack.pop().unwrap();
     }
 
     pub fn compile_function_item(&mut self, function: &FunctionItem, symbol_id: SymbolId) {
@@ -185,12 +183,25 @@ impl Compiler<'_> {
         self.allow_generic_inference_stack.pop().unwrap();
         self.scope_stack.pop().unwrap();
 
-        // Ensure that the body is assignable to the return type.
-        if let Some(return_type) = return_type {
-            self.type_check(
+        // Ensure that the body's final expression is assignable to the return type.
+        // Explicit return statements have already been checked against it.
+        if let (Some(return_type), Some(expr)) = (return_type, body.expr()) {
+            let annotation = function.return_type().map(|ty| {
+                let range = trimmed_range(ty.syntax());
+                Label::new(
+                    range.start().into()..range.end().into(),
+                    format!(
+                        "`{}` is the declared return type",
+                        self.type_name(return_type)
+                    ),
+                )
+            });
+
+            self.type_check_annotated(
                 value.type_id,
                 return_type,
-                function.body().unwrap().syntax().text_range(),
+                trimmed_range(expr.syntax()),
+                annotation,
             );
         }
 
@@ -0,0 +1,235 @@
+use rue_parser::{ImportItem, ImportPath, SyntaxToken};
+use rue_typing::{HashSet, TypeId};
+
+use crate::{compiler::Compiler, symbol::Symbol, ErrorKind, Resolution, SymbolId};
+
+#[derive(Debug, Default)]
+pub struct Imports {
+    pub symbol_ids: Vec<SymbolId>,
+    pub type_ids: Vec<TypeId>,
+}
+
+impl Compiler<'_> {
+    /// Resolve an import and add the items it refers to into the current scope.
+    /// The imported items are returned, so that they can be exported again.
+    pub fn declare_import_item(&mut self, import_item: &ImportItem) -> Imports {
+        let mut imports = Imports::default();
+        if let Some(path) = import_item.path() {
+            self.import_path(None, &path, &mut imports);
+        }
+        imports
+    }
+
+    fn import_path(&mut self, base: Option<SymbolId>, path: &ImportPath, imports: &mut Imports) {
+        let mut idents = path.idents();
+        let group = path.group();
+
+        // Unless this is a group, the last name is the item being imported.
+        let last = if group.is_none() { idents.pop() } else { None };
+
+        let mut module_id = base;
+
+        for name in idents {
+            let Some(symbol_id) = self.resolve_import_module(module_id, &name) else {
+                return;
+            };
+            module_id = Some(symbol_id);
+        }
+
+        if let Some(group) = group {
+            for path in group.paths() {
+                self.import_path(module_id, &path, imports);
+            }
+        } else if let Some(name) = last {
+            self.import_name(module_id, &name, imports);
+        }
+    }
+
+    fn resolve_import_module(
+        &mut self,
+        module_id: Option<SymbolId>,
+        name: &SyntaxToken,
+    ) -> Option<SymbolId> {
+        let (symbol_id, _type_id) = self.resolve_import(module_id, name)?;
+
+        let Some(symbol_id) = symbol_id else {
+            self.db.error(
+                ErrorKind::InvalidSymbolPath(Some(name.to_string())),
+                name.text_range(),
+            );
+            return None;
+        };
+
+        if !matches!(self.db.symbol(symbol_id), Symbol::Module(..)) {
+            self.db.error(
+                ErrorKind::InvalidSymbolPath(Some(name.to_string())),
+                name.text_range(),
+            );
+            return None;
+        }
+
+        Some(symbol_id)
+    }
+
+    fn import_name(
+        &mut self,
+        module_id: Option<SymbolId>,
+        name: &SyntaxToken,
+        imports: &mut Imports,
+    ) {
+        let Some((symbol_id, type_id)) = self.resolve_import(module_id, name) else {
+            return;
+        };
+
+        if let Some(symbol_id) = symbol_id {
+            if let Some(existing_id) = self.scope().symbol(name.text()) {
+                // A local function or constant would silently shadow the import, so it's reported instead.
+                let range = self
+                    .scope()
+                    .is_local(existing_id)
+                    .then(|| self.db.symbol_token(existing_id))
+                    .flatten()
+                    .map_or(name.text_range(), SyntaxToken::text_range);
+
+                self.db
+                    .error(ErrorKind::DuplicateSymbol(name.to_string()), range);
+            } else {
+                self.scope_mut().import_symbol(name.to_string(), symbol_id);
+                imports.symbol_ids.push(symbol_id);
+            }
+        }
+
+        if let Some(type_id) = type_id {
+            if self.scope().ty(name.text()).is_some() {
+                self.db.error(
+                    ErrorKind::DuplicateType(name.to_string()),
+                    name.text_range(),
+                );
+            } else {
+                self.scope_mut().import_type(name.to_string(), type_id);
+                imports.type_ids.push(type_id);
+            }
+        }
+    }
+
+    /// Find the symbol and type with the given name, either in a module or the enclosing scopes.
+    fn resolve_import(
+        &mut self,
+        module_id: Option<SymbolId>,
+        name: &SyntaxToken,
+    ) -> Option<(Option<SymbolId>, Option<TypeId>)> {
+        let Some(module_id) = module_id else {
+            for &scope_id in self.scope_stack.iter().rev() {
+                let symbol_id = self.db.scope(scope_id).symbol(name.text());
+                let type_id = self.db.scope(scope_id).ty(name.text());
+
+                if symbol_id.is_some() || type_id.is_some() {
+                    self.import_reference(name, symbol_id, type_id);
+                    return Some((symbol_id, type_id));
+                }
+            }
+
+            self.db.error(
+                ErrorKind::UnresolvedImport(name.to_string()),
+                name.text_range(),
+            );
+            return None;
+        };
+
+        let Symbol::Module(module) = self.db.symbol(module_id) else {
+            unreachable!();
+        };
+
+        let scope = self.db.scope(module.scope_id);
+        let symbol_id = scope.symbol(name.text());
+        let type_id = scope.ty(name.text());
+
+        let exported_symbol_id =
+            symbol_id.filter(|symbol_id| module.exported_symbols.contains(symbol_id));
+        let exported_type_id = type_id.filter(|type_id| module.exported_types.contains(type_id));
+
+        if exported_symbol_id.is_some() || exported_type_id.is_some() {
+            self.import_reference(name, exported_symbol_id, exported_type_id);
+            return Some((exported_symbol_id, exported_type_id));
+        }
+
+        let error = if symbol_id.is_some() {
+            ErrorKind::PrivateSymbol(name.to_string())
+        } else if type_id.is_some() {
+            ErrorKind::PrivateType(name.to_string())
+        } else {
+            ErrorKind::UnknownModulePath(name.to_string())
+        };
+
+        self.db.error(error, name.text_range());
+        None
+    }
+
+    fn import_reference(
+        &mut self,
+        name: &SyntaxToken,
+        symbol_id: Option<SymbolId>,
+        type_id: Option<TypeId>,
+    ) {
+        if let Some(symbol_id) = symbol_id {
+            self.db
+                .insert_reference(name.clone(), Resolution::Symbol(symbol_id));
+        }
+
+        if let Some(type_id) = type_id {
+            self.db
+                .insert_reference(name.clone(), Resolution::Type(type_id));
+        }
+    }
+
+    /// The paths of every exported symbol with the given name, which could be imported into the current scope.
+    pub fn import_candidates(&self, name: &str) -> Vec<String> {
+        let mut paths = Vec::new();
+        let mut visited = HashSet::new();
+
+        for &scope_id in self.scope_stack.iter().rev() {
+            for (module_name, symbol_id) in self.db.scope(scope_id).named_symbols() {
+                self.collect_import_candidates(
+                    symbol_id,
+                    module_name,
+                    name,
+                    &mut paths,
+                    &mut visited,
+                );
+            }
+        }
+
+        paths
+    }
+
+    fn collect_import_candidates(
+        &self,
+        module_id: SymbolId,
+        path: &str,
+        name: &str,
+        paths: &mut Vec<String>,
+        visited: &mut HashSet<SymbolId>,
+    ) {
+        let Symbol::Module(module) = self.db.symbol(module_id) else {
+            return;
+        };
+
+        if !visited.insert(module_id) {
+            return;
+        }
+
+        for (item_name, symbol_id) in self.db.scope(module.scope_id).named_symbols() {
+            if !module.exported_symbols.contains(&symbol_id) {
+                continue;
+            }
+
+            let item_path = format!("{path}::{item_name}");
+
+            if let Symbol::Module(..) = self.db.symbol(symbol_id) {
+                self.collect_import_candidates(symbol_id, &item_path, name, paths, visited);
+            } else if item_name == name {
+                paths.push(item_path);
+            }
+        }
+    }
+}
@@ -1,5 +1,5 @@
 use indexmap::IndexSet;
-use rue_parser::{ModuleItem, Root};
+use rue_parser::{AstNode, ModuleItem, Root};
 
 use crate::{
     compiler::Compiler,
@@ -39,14 +39,17 @@ impl Compiler<'_> {
         exported_symbols.extend(declarations.exported_symbols.clone());
         exported_types.extend(declarations.exported_types.clone());
         self.scope_stack.push(*scope_id);
+        self.root = Some(root.syntax().clone());
+        self.record_visible_scopes(root.syntax(), root.syntax().text_range().start());
         self.compile_items(&root.items(), declarations);
+        self.root = None;
         self.scope_stack.pop().unwrap();
     }
 
     /// Define a module in the current scope.
     /// This creates a new scope for the module, and declares its items.
-    /// The exports are added during this phase too.
-    pub fn declare_module_item(&mut self, module_item: &ModuleItem) -> SymbolId {
+    /// The exports are added during this phase too, except for imports which are exported again.
+    pub fn declare_module_item(&mut self, module_item: &ModuleItem) -> (SymbolId, Declarations) {
         // Add the symbol to the stack early so you can track type references.
         let scope_id = self.db.alloc_scope(Scope::default());
         let symbol_id = self.db.alloc_symbol(Symbol::Module(Module {
@@ -65,24 +68,81 @@ impl Compiler<'_> {
         self.symbol_stack.push(symbol_id);
         self.scope_stack.push(scope_id);
 
-        let items = module_item.items();
-        let declarations = self.declare_items(&items);
-        self.compile_items(&items, declarations.clone());
+        self.record_visible_scopes(
+            module_item.syntax(),
+            module_item.syntax().text_range().start(),
+        );
+
+        let declarations = self.declare_names(&module_item.items());
 
         self.scope_stack.pop().unwrap();
         self.symbol_stack.pop().unwrap();
 
-        let Symbol::Module(Module {
-            exported_symbols,
-            exported_types,
-            ..
-        }) = self.db.symbol_mut(symbol_id)
-        else {
+        let Symbol::Module(module) = self.db.symbol_mut(symbol_id) else {
+            unreachable!();
+        };
+        module
+            .exported_types
+            .extend(declarations.exported_types.iter().copied());
+        module
+            .exported_symbols
+            .extend(declarations.exported_symbols.iter().copied());
+
+        (symbol_id, declarations)
+    }
+
+    /// Resolve the imports within a module, after all of the items around it have been declared.
+    pub fn declare_module_imports(
+        &mut self,
+        module_item: &ModuleItem,
+        symbol_id: SymbolId,
+        declarations: &mut Declarations,
+    ) {
+        self.enter_module(symbol_id);
+        let imports = self.declare_imports(&module_item.items(), declarations);
+        self.exit_module();
+
+        let Symbol::Module(module) = self.db.symbol_mut(symbol_id) else {
             unreachable!();
         };
-        exported_types.extend(declarations.exported_types);
-        exported_symbols.extend(declarations.exported_symbols);
+        module.exported_symbols.extend(imports.symbol_ids);
+        module.exported_types.extend(imports.type_ids);
+    }
 
-        symbol_id
+    /// Compile the signatures of the functions and constants within a module.
+    pub fn declare_module_signatures(
+        &mut self,
+        module_item: &ModuleItem,
+        symbol_id: SymbolId,
+        declarations: &Declarations,
+    ) {
+        self.enter_module(symbol_id);
+        self.declare_signatures(&module_item.items(), declarations);
+        self.exit_module();
+    }
+
+    /// Compile the items within a module.
+    pub fn compile_module_item(
+        &mut self,
+        module_item: &ModuleItem,
+        symbol_id: SymbolId,
+        declarations: Declarations,
+    ) {
+        self.enter_module(symbol_id);
+        self.compile_items(&module_item.items(), declarations);
+        self.exit_module();
+    }
+
+    fn enter_module(&mut self, symbol_id: SymbolId) {
+        let Symbol::Module(module) = self.db.symbol(symbol_id) else {
+            unreachable!();
+        };
+        self.scope_stack.push(module.scope_id);
+        self.symbol_stack.push(symbol_id);
+    }
+
+    fn exit_module(&mut self) {
+        self.symbol_stack.pop().unwrap();
+        self.scope_stack.pop().unwrap();
     }
 }
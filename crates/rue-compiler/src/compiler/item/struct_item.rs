@@ -19,7 +19,8 @@ impl Compiler<'_> {
     pub fn compile_struct_item(&mut self, struct_item: &StructItem, struct_type_id: TypeId) {
         self.type_definition_stack.push(struct_type_id);
 
-        let (fields, nil_terminated) = self.compile_struct_fields(struct_item.fields());
+        let (fields, nil_terminated) =
+            self.compile_struct_fields(struct_item.fields(), struct_type_id);
         let type_id = construct_items(self.ty, fields.values().copied(), nil_terminated);
 
         *self.ty.get_mut(struct_type_id) = Type::Struct(Struct {
@@ -37,6 +38,7 @@ impl Compiler<'_> {
     pub fn compile_struct_fields(
         &mut self,
         fields: Vec<StructField>,
+        owner_type_id: TypeId,
     ) -> (IndexMap<String, TypeId>, bool) {
         let mut named_fields = IndexMap::new();
         let mut nil_terminated = true;
@@ -61,8 +63,9 @@ impl Compiler<'_> {
             }
 
             if let Some(name) = field.name() {
-                named_fields.insert(name.to_string(), type_id);
-            };
+                let (index, _) = named_fields.insert_full(name.to_string(), type_id);
+                self.db.insert_field_token(owner_type_id, index, name);
+            }
         }
 
         (named_fields, nil_terminated)
@@ -1,3 +1,5 @@
+use std::collections::HashSet;
+
 use rue_parser::TypeAliasItem;
 use rue_typing::{Alias, Type, TypeId};
 
@@ -90,6 +92,43 @@ impl Compiler<'_> {
 
         *reference = type_id;
 
+        // An alias which resolves back to itself would never terminate, so break the cycle.
+        if self.is_alias_cycle(alias_type_id, type_id) {
+            if let Some(name) = type_alias.name() {
+                self.db.error(
+                    ErrorKind::RecursiveTypeAlias(name.to_string()),
+                    name.text_range(),
+                );
+            }
+
+            let unknown = self.ty.std().unknown;
+
+            let Type::Ref(reference) = self.ty.get_raw_mut(ref_type_id) else {
+                unreachable!();
+            };
+
+            *reference = unknown;
+        }
+
         self.type_definition_stack.pop().unwrap();
     }
+
+    /// Whether following aliases from the type leads back to the alias itself.
+    fn is_alias_cycle(&self, alias_type_id: TypeId, mut type_id: TypeId) -> bool {
+        let mut visited = HashSet::new();
+
+        while visited.insert(type_id) {
+            if type_id == alias_type_id {
+                return true;
+            }
+
+            type_id = match self.ty.get_raw(type_id) {
+                Type::Ref(type_id) => *type_id,
+                Type::Alias(alias) => alias.type_id,
+                _ => return false,
+            };
+        }
+
+        false
+    }
 }
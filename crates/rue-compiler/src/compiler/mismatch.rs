@@ -0,0 +1,129 @@
+use std::fmt::Write;
+
+use rue_typing::{Mismatch, MismatchSegment, Type, TypeId};
+
+use crate::Label;
+
+use super::Compiler;
+
+/// What the path to a mismatch is relative to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Context {
+    Value,
+    Parameters,
+    ReturnType,
+}
+
+impl Compiler<'_> {
+    /// Explains which part of a type didn't match, if it's more specific than the types themselves.
+    pub fn mismatch_note(&self, mismatch: &Mismatch) -> Option<String> {
+        let mut context = Context::Value;
+        let mut location = String::new();
+        let mut union_item = None;
+
+        for segment in mismatch.segments(self.ty) {
+            match segment {
+                MismatchSegment::Field(name) => {
+                    if !(context == Context::Parameters && location.is_empty()) {
+                        location.push('.');
+                    }
+                    location.push_str(&name);
+                }
+                MismatchSegment::First => location.push_str(".first"),
+                MismatchSegment::Rest => location.push_str(".rest"),
+                MismatchSegment::UnionItem(type_id) => {
+                    if union_item.is_none() {
+                        union_item = Some((subject(context, &location), location.len(), type_id));
+                    }
+                }
+                MismatchSegment::Parameters => {
+                    context = Context::Parameters;
+                    location.clear();
+                    union_item = None;
+                }
+                MismatchSegment::ReturnType => {
+                    context = Context::ReturnType;
+                    location.clear();
+                    union_item = None;
+                }
+            }
+        }
+
+        let found = self.type_name(mismatch.found);
+        let expected = self.type_name(mismatch.expected);
+
+        if context == Context::Value && location.is_empty() {
+            return union_item.map(|(_, _, type_id)| {
+                format!(
+                    "`{}` in the union isn't assignable to `{expected}`",
+                    self.type_name(type_id)
+                )
+            });
+        }
+
+        let mut note = String::new();
+
+        // The union item only needs to be mentioned if the mismatch is inside of it.
+        if let Some((union_subject, union_location, type_id)) = union_item {
+            if union_location < location.len() {
+                write!(
+                    note,
+                    "when {union_subject} is `{}`, ",
+                    self.type_name(type_id)
+                )
+                .unwrap();
+            }
+        }
+
+        write!(
+            note,
+            "{} is `{found}`, but `{expected}` was expected",
+            subject(context, &location)
+        )
+        .unwrap();
+
+        Some(note)
+    }
+
+    /// Points to where the expected type was declared, preferring the innermost mismatched type.
+    /// Types declared in other files, such as the standard library, aren't labeled.
+    pub fn declaration_label(&self, type_ids: &[TypeId]) -> Option<Label> {
+        let root = self.root.as_ref()?;
+
+        type_ids.iter().find_map(|&type_id| {
+            let token = self.db.type_token(self.original_type_id(type_id))?;
+
+            if token.parent_ancestors().last().as_ref() != Some(root) {
+                return None;
+            }
+
+            let range = token.text_range();
+
+            Some(Label::new(
+                range.start().into()..range.end().into(),
+                format!("`{}` is defined here", token.text()),
+            ))
+        })
+    }
+
+    fn original_type_id(&self, type_id: TypeId) -> TypeId {
+        match self.ty.get(type_id) {
+            Type::Alias(ty) => ty.original_type_id,
+            Type::Struct(ty) => ty.original_type_id,
+            Type::Enum(ty) => ty.original_type_id,
+            Type::Variant(ty) => ty.original_type_id,
+            _ => type_id,
+        }
+    }
+}
+
+fn subject(context: Context, location: &str) -> String {
+    match (context, location.is_empty()) {
+        (Context::Value, true) => "the value".to_string(),
+        (Context::Parameters, true) => "the parameters".to_string(),
+        (Context::ReturnType, true) => "the return type".to_string(),
+        (Context::Value, false) => format!("`{location}`"),
+        (Context::Parameters, false) => format!("parameter `{location}`"),
+        (Context::ReturnType, false) => format!("`{location}` of the return type"),
+    }
+}
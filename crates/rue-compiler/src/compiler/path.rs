@@ -2,12 +2,12 @@ use rue_typing::HashMap;
 
 use indexmap::IndexMap;
 use rowan::TextRange;
-use rue_parser::{AstNode, GenericArgs, PathItem};
+use rue_parser::{AstNode, GenericArgs, PathItem, SyntaxToken};
 use rue_typing::{Lazy, Type, TypeId};
 
-use crate::{symbol::Symbol, ErrorKind, SymbolId};
+use crate::{symbol::Symbol, DiagnosticData, ErrorKind, Resolution, SymbolId};
 
-use super::Compiler;
+use super::{similar_name, Compiler};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Path {
@@ -30,57 +30,40 @@ impl Compiler<'_> {
     ) -> Option<Path> {
         let name = item.name()?;
 
-        for &scope_id in self.scope_stack.iter().rev() {
-            let type_id = self.db.scope(scope_id).ty(name.text());
-            let symbol_id = self.db.scope(scope_id).symbol(name.text());
+        if let Some(path) = self.find_base_path(name.text(), path_kind, last) {
+            return self.resolved_path(item, &name, path, true);
+        }
 
-            if let (Some(type_id), Some(symbol_id)) = (type_id, symbol_id) {
-                if let Symbol::Module(..) = self.db.symbol(symbol_id) {
-                    if !last {
-                        return Some(Path::Symbol(symbol_id));
-                    }
-                }
+        match path_kind {
+            PathKind::Type => {
+                let suggestion = self.similar_type_name(name.text());
+                let diagnostic = self
+                    .db
+                    .error(ErrorKind::UnknownType(name.to_string()), name.text_range());
 
-                if let Type::Enum(..) = self.ty.get(type_id) {
-                    if !last {
-                        self.type_reference(type_id);
-                        return Some(Path::Type(type_id));
-                    }
+                if let Some(suggestion) = suggestion {
+                    diagnostic.with_suggestion(suggestion);
                 }
+            }
+            PathKind::Symbol => {
+                let candidates = self.import_candidates(name.text());
+                let kind = ErrorKind::UnknownSymbol(name.to_string());
 
-                match path_kind {
-                    PathKind::Type => {
-                        self.type_reference(type_id);
-                        return Some(Path::Type(self.handle_generics(
-                            type_id,
-                            item.generic_args(),
-                            item.syntax().text_range(),
-                        )?));
-                    }
-                    PathKind::Symbol => {
-                        return Some(Path::Symbol(symbol_id));
+                if candidates.is_empty() {
+                    let suggestion = self.similar_symbol_name(name.text());
+                    let diagnostic = self.db.error(kind, name.text_range());
+
+                    if let Some(suggestion) = suggestion {
+                        diagnostic.with_suggestion(suggestion);
                     }
+                } else {
+                    self.db
+                        .error(kind, name.text_range())
+                        .with_data(DiagnosticData::Imports(candidates));
                 }
-            } else if let Some(type_id) = type_id {
-                self.type_reference(type_id);
-                return Some(Path::Type(self.handle_generics(
-                    type_id,
-                    item.generic_args(),
-                    item.syntax().text_range(),
-                )?));
-            } else if let Some(symbol_id) = symbol_id {
-                return Some(Path::Symbol(symbol_id));
             }
         }
 
-        self.db.error(
-            match path_kind {
-                PathKind::Type => ErrorKind::UnknownType(name.to_string()),
-                PathKind::Symbol => ErrorKind::UnknownSymbol(name.to_string()),
-            },
-            name.text_range(),
-        );
-
         None
     }
 
@@ -104,15 +87,21 @@ impl Compiler<'_> {
                 };
 
                 let Some(variant_type) = enum_type.variants.get(name.text()).copied() else {
-                    self.db.error(
+                    let suggestion =
+                        similar_name(name.text(), enum_type.variants.keys().map(String::as_str));
+                    let diagnostic = self.db.error(
                         ErrorKind::UnknownEnumVariantPath(name.text().to_string()),
                         name.text_range(),
                     );
+
+                    if let Some(suggestion) = suggestion {
+                        diagnostic.with_suggestion(suggestion);
+                    }
                     return None;
                 };
 
-                self.type_reference(variant_type);
-                Some(Path::Type(variant_type))
+                // Variants can't have generic arguments of their own.
+                self.resolved_path(item, &name, Path::Type(variant_type), false)
             }
             Path::Symbol(module_id) => {
                 let Symbol::Module(module) = self.db.symbol(module_id) else {
@@ -137,60 +126,91 @@ impl Compiler<'_> {
                 let symbol_id =
                     symbol_id.filter(|symbol_id| module.exported_symbols.contains(symbol_id));
 
-                if let (Some(type_id), Some(symbol_id)) = (type_id, symbol_id) {
-                    if let Symbol::Module(..) = self.db.symbol(symbol_id) {
-                        if !last {
-                            return Some(Path::Symbol(symbol_id));
-                        }
-                    }
-
-                    if let Type::Enum(..) = self.ty.get(type_id) {
-                        if !last {
-                            self.type_reference(type_id);
-                            return Some(Path::Type(type_id));
-                        }
-                    }
+                if let Some(path) = self.choose_path(type_id, symbol_id, path_kind, last) {
+                    return self.resolved_path(item, &name, path, true);
+                }
 
-                    match path_kind {
-                        PathKind::Type => {
-                            self.type_reference(type_id);
-                            Some(Path::Type(self.handle_generics(
-                                type_id,
-                                item.generic_args(),
-                                item.syntax().text_range(),
-                            )?))
-                        }
-                        PathKind::Symbol => Some(Path::Symbol(symbol_id)),
-                    }
-                } else if let Some(type_id) = type_id {
-                    self.type_reference(type_id);
-                    Some(Path::Type(self.handle_generics(
-                        type_id,
-                        item.generic_args(),
-                        item.syntax().text_range(),
-                    )?))
-                } else if let Some(symbol_id) = symbol_id {
-                    Some(Path::Symbol(symbol_id))
-                } else if private_type {
-                    self.db.error(
-                        ErrorKind::PrivateType(name.text().to_string()),
-                        name.text_range(),
-                    );
-                    None
+                let error = if private_type {
+                    ErrorKind::PrivateType(name.text().to_string())
                 } else if private_symbol {
-                    self.db.error(
-                        ErrorKind::PrivateSymbol(name.text().to_string()),
-                        name.text_range(),
-                    );
-                    None
+                    ErrorKind::PrivateSymbol(name.text().to_string())
                 } else {
-                    self.db.error(
-                        ErrorKind::UnknownModulePath(name.text().to_string()),
-                        name.text_range(),
-                    );
-                    None
+                    ErrorKind::UnknownModulePath(name.text().to_string())
+                };
+
+                self.db.error(error, name.text_range());
+                None
+            }
+        }
+    }
+
+    /// Finds the innermost scope which defines the name.
+    fn find_base_path(&self, name: &str, path_kind: PathKind, last: bool) -> Option<Path> {
+        self.scope_stack.iter().rev().find_map(|&scope_id| {
+            let scope = self.db.scope(scope_id);
+            self.choose_path(scope.ty(name), scope.symbol(name), path_kind, last)
+        })
+    }
+
+    /// Chooses between a type and symbol with the same name. Modules and enums are preferred
+    /// in the middle of a path, since only they can contain other items.
+    fn choose_path(
+        &self,
+        type_id: Option<TypeId>,
+        symbol_id: Option<SymbolId>,
+        path_kind: PathKind,
+        last: bool,
+    ) -> Option<Path> {
+        match (type_id, symbol_id) {
+            (Some(type_id), Some(symbol_id)) => {
+                if !last && matches!(self.db.symbol(symbol_id), Symbol::Module(..)) {
+                    return Some(Path::Symbol(symbol_id));
+                }
+
+                if !last && matches!(self.ty.get(type_id), Type::Enum(..)) {
+                    return Some(Path::Type(type_id));
+                }
+
+                match path_kind {
+                    PathKind::Type => Some(Path::Type(type_id)),
+                    PathKind::Symbol => Some(Path::Symbol(symbol_id)),
                 }
             }
+            (Some(type_id), None) => Some(Path::Type(type_id)),
+            (None, Some(symbol_id)) => Some(Path::Symbol(symbol_id)),
+            (None, None) => None,
+        }
+    }
+
+    /// Records what the name refers to, and applies the generic arguments to types.
+    fn resolved_path(
+        &mut self,
+        item: &PathItem,
+        name: &SyntaxToken,
+        path: Path,
+        generics: bool,
+    ) -> Option<Path> {
+        match path {
+            Path::Symbol(symbol_id) => {
+                self.db
+                    .insert_reference(name.clone(), Resolution::Symbol(symbol_id));
+                Some(path)
+            }
+            Path::Type(type_id) => {
+                self.type_reference(type_id);
+                self.db
+                    .insert_reference(name.clone(), Resolution::Type(type_id));
+
+                if !generics {
+                    return Some(path);
+                }
+
+                Some(Path::Type(self.handle_generics(
+                    type_id,
+                    item.generic_args(),
+                    item.syntax().text_range(),
+                )?))
+            }
         }
     }
 
@@ -200,8 +220,15 @@ impl Compiler<'_> {
         generic_args: Option<GenericArgs>,
         text_range: TextRange,
     ) -> Option<TypeId> {
-        self.handle_generics_impl(type_id, generic_args, text_range)
-            .map(|type_id| self.ty.substitute(type_id, HashMap::new()))
+        let type_id = self.handle_generics_impl(type_id, generic_args, text_range)?;
+        let substituted = self.ty.substitute(type_id, HashMap::new());
+
+        // The substituted type is still an instance of the same alias.
+        if let Some(instance) = self.generic_instances.get(&type_id).cloned() {
+            self.generic_instances.insert(substituted, instance);
+        }
+
+        Some(substituted)
     }
 
     fn handle_generics_impl(
@@ -242,9 +269,15 @@ impl Compiler<'_> {
             }
 
             if self.type_definition_stack.is_empty() {
+                let alias_type_id = type_id;
+                let args = substitutions.values().copied().collect();
+
                 type_id = self
                     .ty
                     .substitute(type_id, substitutions.into_iter().collect());
+
+                self.generic_instances
+                    .insert(type_id, (alias_type_id, args));
             } else {
                 type_id = self.ty.alloc(Type::Lazy(Lazy {
                     type_id,
@@ -6,6 +6,7 @@ use rue_typing::TypeId;
 use crate::{
     compiler::{block::BlockTerminator, Compiler},
     scope::Scope,
+    trimmed_range,
     value::GuardPath,
     ErrorKind, HirId,
 };
@@ -30,6 +31,10 @@ impl Compiler<'_> {
             if_stmt.syntax().text_range(),
         );
 
+        if let Some(condition_expr) = if_stmt.condition() {
+            self.check_constant_condition(&condition, trimmed_range(condition_expr.syntax()));
+        }
+
         let then_block = if let Some(then_block) = if_stmt.then_block() {
             // We create a new scope for the then block.
             let scope_id = self.db.alloc_scope(Scope::default());
@@ -1,6 +1,6 @@
-use rue_parser::{AstNode, LetStmt};
+use rue_parser::{AstNode, LetStmt, SyntaxToken};
 
-use crate::{compiler::Compiler, scope::Scope, symbol::Symbol, ScopeId};
+use crate::{compiler::Compiler, scope::Scope, symbol::Symbol, ScopeId, WarningKind};
 
 impl Compiler<'_> {
     /// Compiles a let statement and returns its new scope id.
@@ -34,6 +34,8 @@ impl Compiler<'_> {
             value.type_id = expected_type;
         }
 
+        self.check_shadowed_parameter(&name);
+
         *self.db.symbol_mut(symbol_id) = Symbol::Let(value);
 
         // Every let binding is a new scope for now, to ensure references are resolved in the proper order.
@@ -48,4 +50,21 @@ impl Compiler<'_> {
 
         Some(scope_id)
     }
+
+    fn check_shadowed_parameter(&mut self, name: &SyntaxToken) {
+        let shadowed = self
+            .scope_stack
+            .iter()
+            .rev()
+            .find_map(|&scope_id| self.db.scope(scope_id).symbol(name.text()));
+
+        if let Some(symbol_id) = shadowed {
+            if let Symbol::Parameter(..) = self.db.symbol(symbol_id) {
+                self.db.warning(
+                    WarningKind::ShadowedParameter(name.to_string()),
+                    name.text_range(),
+                );
+            }
+        }
+    }
 }
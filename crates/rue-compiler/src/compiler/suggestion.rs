@@ -0,0 +1,69 @@
+use super::Compiler;
+
+impl Compiler<'_> {
+    /// The most similar name to a symbol which couldn't be resolved, from any visible scope.
+    pub fn similar_symbol_name(&self, name: &str) -> Option<String> {
+        let mut names = Vec::new();
+
+        for &scope_id in self.scope_stack.iter().rev() {
+            for (name, _) in self.db.scope(scope_id).named_symbols() {
+                names.push(name.to_string());
+            }
+        }
+
+        similar_name(name, names.iter().map(String::as_str))
+    }
+
+    /// The most similar name to a type which couldn't be resolved, from any visible scope.
+    pub fn similar_type_name(&self, name: &str) -> Option<String> {
+        let mut names = Vec::new();
+
+        for &scope_id in self.scope_stack.iter().rev() {
+            for (name, _) in self.db.scope(scope_id).named_types() {
+                names.push(name.to_string());
+            }
+        }
+
+        similar_name(name, names.iter().map(String::as_str))
+    }
+}
+
+/// Finds the candidate closest to the name by edit distance.
+/// Candidates which are too different to be a likely typo are ignored, including any which
+/// would replace every character of the name, such as `y` for `b`.
+/// If there's a tie, the earliest candidate is chosen.
+pub fn similar_name<'a>(
+    name: &str,
+    candidates: impl IntoIterator<Item = &'a str>,
+) -> Option<String> {
+    let length = name.chars().count();
+    let max_distance = (length / 3).max(1);
+
+    candidates
+        .into_iter()
+        .filter(|&candidate| candidate != name)
+        .map(|candidate| (edit_distance(name, candidate), candidate))
+        .filter(|&(distance, _)| distance <= max_distance && distance < length)
+        .min_by_key(|&(distance, _)| distance)
+        .map(|(_, candidate)| candidate.to_string())
+}
+
+/// The number of single character insertions, deletions or substitutions needed to turn one string into the other.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    let mut current = vec![0; b.len() + 1];
+
+    for (i, a) in a.chars().enumerate() {
+        current[0] = i + 1;
+
+        for (j, &b) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(a != b);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+
+        std::mem::swap(&mut previous, &mut current);
+    }
+
+    previous[b.len()]
+}
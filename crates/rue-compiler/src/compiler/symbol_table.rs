@@ -3,9 +3,14 @@ use rue_typing::HashSet;
 use indexmap::{IndexMap, IndexSet};
 use rue_typing::{Type, TypeId, TypeSystem};
 
-use crate::{dependency_graph::DependencyGraph, symbol::Symbol, Database, SymbolId, WarningKind};
+use rue_parser::SyntaxKind;
 
-#[derive(Debug, Default)]
+use crate::{
+    dependency_graph::DependencyGraph, removal_range, symbol::Symbol, Database, DiagnosticData,
+    SymbolId, WarningKind,
+};
+
+#[derive(Debug, Default, Clone)]
 pub struct SymbolTable {
     symbol_type_references: IndexMap<SymbolId, IndexSet<TypeId>>,
     type_type_references: IndexMap<TypeId, IndexSet<TypeId>>,
@@ -105,7 +110,7 @@ impl SymbolTable {
             if exempt_symbols.contains(symbol_id) {
                 continue;
             }
-            let token = db.symbol_token(*symbol_id).unwrap();
+            let token = db.symbol_token(*symbol_id).unwrap().clone();
             let kind = match db.symbol(*symbol_id).clone() {
                 Symbol::Unknown => unreachable!(),
                 // Symbol::Module(..) => WarningKind::UnusedModule(token.to_string()),
@@ -117,7 +122,31 @@ impl SymbolTable {
                 Symbol::Const(..) => WarningKind::UnusedConst(token.to_string()),
                 Symbol::InlineConst(..) => WarningKind::UnusedInlineConst(token.to_string()),
             };
-            db.warning(kind, token.text_range());
+
+            // Unused let bindings and functions can be removed entirely.
+            let removable = token
+                .parent()
+                .filter(|_| {
+                    matches!(
+                        kind,
+                        WarningKind::UnusedLet(..) | WarningKind::UnusedFunction(..)
+                    )
+                })
+                .filter(|parent| {
+                    matches!(
+                        parent.kind(),
+                        SyntaxKind::LetStmt | SyntaxKind::FunctionItem
+                    )
+                });
+
+            let diagnostic = db.warning(kind, token.text_range());
+
+            if let Some(parent) = removable {
+                let range = removal_range(&parent);
+                diagnostic.with_data(DiagnosticData::Remove(
+                    range.start().into()..range.end().into(),
+                ));
+            }
         }
 
         for type_id in &type_ids {
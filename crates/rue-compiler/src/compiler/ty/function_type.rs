@@ -19,9 +19,9 @@ impl Compiler<'_> {
                 .map(|token| token.to_string())
                 .unwrap_or(format!("#{i}"));
 
-            if !parameter_names.insert(name.to_string()) {
+            if !parameter_names.insert(name.clone()) {
                 self.db.error(
-                    ErrorKind::DuplicateSymbol(name.to_string()),
+                    ErrorKind::DuplicateSymbol(name.clone()),
                     param.name().unwrap().text_range(),
                 );
             }
@@ -1,7 +1,7 @@
 use id_arena::Arena;
 use indexmap::IndexMap;
-use rowan::TextRange;
-use rue_parser::SyntaxToken;
+use rowan::{TextRange, TextSize};
+use rue_parser::{SyntaxNode, SyntaxToken};
 
 mod comparison;
 mod ids;
@@ -12,7 +12,7 @@ use rue_typing::TypeId;
 
 use crate::{
     environment::Environment,
-    hir::{Hir, Op},
+    hir::{Hir, Op, VariantInitializer},
     lir::Lir,
     mir::Mir,
     scope::Scope,
@@ -20,7 +20,24 @@ use crate::{
     Diagnostic, DiagnosticKind, ErrorKind, WarningKind,
 };
 
-#[derive(Debug, Default)]
+/// What a name refers to after it has been resolved.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Resolution {
+    Symbol(SymbolId),
+    Type(TypeId),
+    /// A field of a struct or enum variant, by its index.
+    Field(TypeId, usize),
+}
+
+/// The scopes which are visible from a given offset onward, until the end of the enclosing node.
+#[derive(Debug, Clone)]
+pub struct VisibleScopes {
+    pub node: SyntaxNode,
+    pub offset: TextSize,
+    pub scope_ids: Vec<ScopeId>,
+}
+
+#[derive(Debug, Default, Clone)]
 pub struct Database {
     diagnostics: Vec<Diagnostic>,
     scopes: Arena<Scope>,
@@ -31,7 +48,12 @@ pub struct Database {
     environments: Arena<Environment>,
     symbol_tokens: IndexMap<SymbolId, SyntaxToken>,
     type_tokens: IndexMap<TypeId, SyntaxToken>,
+    field_tokens: IndexMap<(TypeId, usize), SyntaxToken>,
     scope_tokens: IndexMap<ScopeId, SyntaxToken>,
+    variant_initializers: IndexMap<HirId, VariantInitializer>,
+    references: Vec<(SyntaxToken, Resolution)>,
+    visible_scopes: Vec<VisibleScopes>,
+    expr_types: IndexMap<SyntaxNode, TypeId>,
 }
 
 impl Database {
@@ -107,10 +129,53 @@ impl Database {
         self.type_tokens.insert(type_id, token);
     }
 
+    pub(crate) fn insert_field_token(&mut self, type_id: TypeId, index: usize, token: SyntaxToken) {
+        self.field_tokens.insert((type_id, index), token);
+    }
+
     pub(crate) fn insert_scope_token(&mut self, scope_id: ScopeId, token: SyntaxToken) {
         self.scope_tokens.insert(scope_id, token);
     }
 
+    pub(crate) fn insert_variant_initializer(
+        &mut self,
+        hir_id: HirId,
+        variant_initializer: VariantInitializer,
+    ) {
+        self.variant_initializers
+            .insert(hir_id, variant_initializer);
+    }
+
+    pub(crate) fn variant_initializer(&self, hir_id: HirId) -> Option<&VariantInitializer> {
+        self.variant_initializers.get(&hir_id)
+    }
+
+    pub(crate) fn insert_reference(&mut self, token: SyntaxToken, resolution: Resolution) {
+        self.references.push((token, resolution));
+    }
+
+    /// Every name which has been resolved, along with what it refers to.
+    pub fn references(&self) -> &[(SyntaxToken, Resolution)] {
+        &self.references
+    }
+
+    pub(crate) fn insert_visible_scopes(&mut self, visible_scopes: VisibleScopes) {
+        self.visible_scopes.push(visible_scopes);
+    }
+
+    /// The scopes that were on the stack at various points while compiling blocks and lambdas.
+    pub fn visible_scopes(&self) -> &[VisibleScopes] {
+        &self.visible_scopes
+    }
+
+    pub(crate) fn insert_expr_type(&mut self, node: SyntaxNode, type_id: TypeId) {
+        self.expr_types.insert(node, type_id);
+    }
+
+    pub fn expr_type(&self, node: &SyntaxNode) -> Option<TypeId> {
+        self.expr_types.get(node).copied()
+    }
+
     pub fn symbol_token(&self, symbol_id: SymbolId) -> Option<&SyntaxToken> {
         self.symbol_tokens.get(&symbol_id)
     }
@@ -119,14 +184,31 @@ impl Database {
         self.type_tokens.get(&type_id)
     }
 
+    pub fn field_token(&self, type_id: TypeId, index: usize) -> Option<&SyntaxToken> {
+        self.field_tokens.get(&(type_id, index))
+    }
+
     pub fn scope_token(&self, scope_id: ScopeId) -> Option<&SyntaxToken> {
         self.scope_tokens.get(&scope_id)
     }
 
+    pub fn named_symbols(&self) -> Vec<SymbolId> {
+        self.symbol_tokens.keys().copied().collect()
+    }
+
     pub fn named_types(&self) -> Vec<TypeId> {
         self.type_tokens.keys().copied().collect()
     }
 
+    pub fn named_fields(&self) -> Vec<(TypeId, usize)> {
+        self.field_tokens.keys().copied().collect()
+    }
+
+    /// Every scope, along with its id.
+    pub fn scopes(&self) -> impl Iterator<Item = (ScopeId, &Scope)> {
+        self.scopes.iter().map(|(id, scope)| (ScopeId(id), scope))
+    }
+
     pub fn dbg_hir(&self, id: HirId) -> String {
         match self.hir(id) {
             Hir::Unknown => "<unknown hir>".to_string(),
@@ -180,6 +262,14 @@ impl Database {
                 self.dbg_hir(*then_block),
                 self.dbg_hir(*else_block)
             ),
+            Hir::Clvm(bytes, args) => format!(
+                "Clvm({}, [{}])",
+                hex::encode(bytes),
+                args.iter()
+                    .map(|arg| self.dbg_hir(*arg))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
         }
     }
 
@@ -220,17 +310,25 @@ impl Database {
         &self.diagnostics
     }
 
-    pub fn error(&mut self, info: ErrorKind, range: TextRange) {
+    pub(crate) fn diagnostics_mut(&mut self) -> &mut Vec<Diagnostic> {
+        &mut self.diagnostics
+    }
+
+    /// Reports an error, which can be given labels, a note, a suggestion or data before the next one.
+    pub fn error(&mut self, info: ErrorKind, range: TextRange) -> &mut Diagnostic {
         self.diagnostics.push(Diagnostic::new(
             DiagnosticKind::Error(info),
             range.start().into()..range.end().into(),
         ));
+        self.diagnostics.last_mut().unwrap()
     }
 
-    pub fn warning(&mut self, info: WarningKind, range: TextRange) {
+    /// Reports a warning, which can be given labels, a note, a suggestion or data before the next one.
+    pub fn warning(&mut self, info: WarningKind, range: TextRange) -> &mut Diagnostic {
         self.diagnostics.push(Diagnostic::new(
             DiagnosticKind::Warning(info),
             range.start().into()..range.end().into(),
         ));
+        self.diagnostics.last_mut().unwrap()
     }
 }
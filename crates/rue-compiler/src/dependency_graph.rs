@@ -13,6 +13,7 @@ pub struct DependencyGraph {
     environments: IndexMap<ScopeId, EnvironmentId>,
     parent_scopes: IndexMap<ScopeId, IndexSet<ScopeId>>,
     symbol_references: IndexMap<SymbolId, usize>,
+    captured_symbols: IndexSet<SymbolId>,
     references: IndexMap<SymbolId, IndexSet<SymbolId>>,
 }
 
@@ -28,6 +29,11 @@ impl DependencyGraph {
             .unwrap_or_default()
     }
 
+    /// Whether the symbol is referenced from a function other than the one which defines it.
+    pub fn is_captured(&self, symbol_id: SymbolId) -> bool {
+        self.captured_symbols.contains(&symbol_id)
+    }
+
     pub fn all_references(&self, symbol_id: SymbolId) -> IndexSet<SymbolId> {
         let mut visited = IndexSet::new();
         let mut stack = vec![symbol_id];
@@ -48,6 +54,18 @@ impl DependencyGraph {
         visited
     }
 
+    /// Whether the symbol references itself, either directly or through other symbols.
+    pub fn is_recursive(&self, symbol_id: SymbolId) -> bool {
+        let mut symbols = self.all_references(symbol_id);
+        symbols.insert(symbol_id);
+
+        symbols.into_iter().any(|reference| {
+            self.references
+                .get(&reference)
+                .is_some_and(|references| references.contains(&symbol_id))
+        })
+    }
+
     pub fn environment_id(&self, scope_id: ScopeId) -> EnvironmentId {
         self.environments[&scope_id]
     }
@@ -76,7 +94,7 @@ struct GraphBuilder<'a> {
     visited: IndexSet<(ScopeId, HirId)>,
 }
 
-impl<'a> GraphBuilder<'a> {
+impl GraphBuilder<'_> {
     fn walk_module(&mut self, module: &Module) {
         if self.graph.environments.contains_key(&module.scope_id) {
             log::debug!(
@@ -108,7 +126,13 @@ impl<'a> GraphBuilder<'a> {
                     .entry(function.scope_id)
                     .or_default();
 
+                // Track the references of the export, so that recursion can be detected.
+                let is_new = self.symbol_stack.insert(symbol_id);
                 self.walk_function(&function);
+
+                if is_new {
+                    self.symbol_stack.shift_remove(&symbol_id);
+                }
             }
             Symbol::Const(constant) | Symbol::InlineConst(constant) => {
                 self.walk_hir(scope_id, constant.hir_id);
@@ -183,6 +207,11 @@ impl<'a> GraphBuilder<'a> {
                 self.walk_hir(scope_id, start);
                 self.walk_hir(scope_id, end);
             }
+            Hir::Clvm(_bytes, args) => {
+                for arg in args {
+                    self.walk_hir(scope_id, arg);
+                }
+            }
             Hir::Definition(child_scope_id, hir_id) => {
                 self.walk_definition(scope_id, child_scope_id, hir_id);
             }
@@ -313,9 +342,13 @@ impl<'a> GraphBuilder<'a> {
                 .env_mut(self.graph.environments[&scope_id])
                 .define(symbol_id);
         } else if !is_local && capturable {
-            self.db
-                .env_mut(self.graph.environments[&scope_id])
-                .capture(symbol_id);
+            let env_id = self.graph.environments[&scope_id];
+            self.db.env_mut(env_id).capture(symbol_id);
+
+            // Bindings within the same function access the symbol through their parent environment instead.
+            if self.db.env(env_id).parent().is_none() {
+                self.graph.captured_symbols.insert(symbol_id);
+            }
 
             for parent_scope_id in self.graph.parent_scopes[&scope_id].clone() {
                 self.propagate_capture(parent_scope_id, symbol_id, visited_scopes);
@@ -384,6 +417,11 @@ impl<'a> GraphBuilder<'a> {
                 self.ref_hir(scope_id, start);
                 self.ref_hir(scope_id, end);
             }
+            Hir::Clvm(_bytes, args) => {
+                for arg in args {
+                    self.ref_hir(scope_id, arg);
+                }
+            }
             Hir::Definition(scope_id, hir_id) => {
                 self.ref_hir(scope_id, hir_id);
             }
@@ -6,11 +6,64 @@ use indoc::formatdoc;
 pub struct Diagnostic {
     kind: DiagnosticKind,
     span: Range<usize>,
+    labels: Vec<Label>,
+    suggestion: Option<String>,
+    note: Option<String>,
+    data: Option<DiagnosticData>,
+    denied: bool,
 }
 
 impl Diagnostic {
     pub fn new(kind: DiagnosticKind, span: Range<usize>) -> Self {
-        Self { kind, span }
+        Self {
+            kind,
+            span,
+            labels: Vec::new(),
+            suggestion: None,
+            note: None,
+            data: None,
+            denied: false,
+        }
+    }
+
+    pub fn with_label(&mut self, label: Label) -> &mut Self {
+        self.labels.push(label);
+        self
+    }
+
+    /// Secondary spans which point at other code that's relevant to the diagnostic.
+    pub fn labels(&self) -> &[Label] {
+        &self.labels
+    }
+
+    pub fn with_suggestion(&mut self, suggestion: impl Into<String>) -> &mut Self {
+        self.suggestion = Some(suggestion.into());
+        self
+    }
+
+    /// A similar name which was likely meant instead of the one at the primary span.
+    pub fn suggestion(&self) -> Option<&str> {
+        self.suggestion.as_deref()
+    }
+
+    pub fn with_note(&mut self, note: impl Into<String>) -> &mut Self {
+        self.note = Some(note.into());
+        self
+    }
+
+    /// A short explanation of the diagnostic, such as which part of a type didn't match.
+    pub fn note(&self) -> Option<&str> {
+        self.note.as_deref()
+    }
+
+    pub fn with_data(&mut self, data: DiagnosticData) -> &mut Self {
+        self.data = Some(data);
+        self
+    }
+
+    /// Structured information about the diagnostic, which tooling can use to suggest fixes.
+    pub fn data(&self) -> Option<&DiagnosticData> {
+        self.data.as_ref()
     }
 
     pub fn kind(&self) -> &DiagnosticKind {
@@ -21,21 +74,71 @@ impl Diagnostic {
         &self.span
     }
 
+    /// Treats a warning as an error, since its lint level is `deny`.
+    pub(crate) fn deny(&mut self) {
+        self.denied = true;
+    }
+
+    /// Whether this is a warning which is treated as an error.
+    pub fn is_denied(&self) -> bool {
+        self.denied
+    }
+
     pub fn is_error(&self) -> bool {
-        matches!(self.kind, DiagnosticKind::Error(_))
+        matches!(self.kind, DiagnosticKind::Error(_)) || self.denied
     }
 
     pub fn is_warning(&self) -> bool {
-        matches!(self.kind, DiagnosticKind::Warning(_))
+        matches!(self.kind, DiagnosticKind::Warning(_)) && !self.denied
     }
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Label {
+    pub span: Range<usize>,
+    pub message: String,
+}
+
+impl Label {
+    pub fn new(span: Range<usize>, message: impl Into<String>) -> Self {
+        Self {
+            span,
+            message: message.into(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum DiagnosticData {
+    /// The span of the item or statement which can be removed.
+    Remove(Range<usize>),
+    /// The span which can be replaced with simpler code.
+    Replace(Range<usize>, String),
+    /// The names and types of the fields missing from an initializer, and the offset to insert them at.
+    MissingFields {
+        offset: usize,
+        needs_comma: bool,
+        fields: Vec<(String, String)>,
+    },
+    /// The paths of exported symbols with the same name, which could be imported.
+    Imports(Vec<String>),
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum DiagnosticKind {
     Warning(WarningKind),
     Error(ErrorKind),
 }
 
+impl DiagnosticKind {
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::Warning(kind) => kind.code(),
+            Self::Error(kind) => kind.code(),
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum WarningKind {
     UnusedFunction(String),
@@ -50,6 +153,22 @@ pub enum WarningKind {
     UnusedStruct(String),
     UnusedTypeAlias(String),
     UnnecessaryTypeCheck(String, String),
+
+    // Conditions.
+    NegativeCoinAmount(String),
+    EmptyAnnouncementMessage,
+    DuplicateAssertMyAmount,
+    ConflictingAssertMyAmount(String, String),
+
+    // Attributes.
+    UnknownLint(String),
+
+    // Static analysis.
+    UnreachableCode,
+    ConstantCondition(bool),
+    ShadowedParameter(String),
+    SelfComparison(String),
+    DivisionByZero,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -116,6 +235,9 @@ pub enum ErrorKind {
     ExpectedGenericArgs,
     GenericArgsMismatch(usize, usize),
 
+    // Imports.
+    UnresolvedImport(String),
+
     // Type guards.
     ImpossibleTypeCheck(String, String),
     RecursiveTypeCheck(String, String),
@@ -128,11 +250,152 @@ pub enum ErrorKind {
     // Atoms.
     NonAtomEquality(String),
     IntegerTooLarge,
+    InvalidHexLiteral,
+
+    // Inline assembly.
+    InvalidClvmAssembly(String),
 
     // Recursive constants.
     RecursiveConstantReference,
     RecursiveInlineConstantReference,
     RecursiveInlineFunctionCall,
+
+    // Attributes.
+    UnknownAttribute(String),
+
+    // Entry point.
+    MissingMainFunction,
+    RecursiveMainFunction,
+
+    // Code generation.
+    InaccessibleSymbol(String),
+}
+
+impl WarningKind {
+    /// A stable identifier for the warning, which doesn't change between releases.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::UnusedFunction(..) => "W0001",
+            Self::UnusedInlineFunction(..) => "W0002",
+            Self::UnusedParameter(..) => "W0003",
+            Self::UnusedConst(..) => "W0004",
+            Self::UnusedInlineConst(..) => "W0005",
+            Self::UnusedLet(..) => "W0006",
+            Self::UnusedGenericType(..) => "W0007",
+            Self::UnusedEnum(..) => "W0008",
+            Self::UnusedEnumVariant(..) => "W0009",
+            Self::UnusedStruct(..) => "W0010",
+            Self::UnusedTypeAlias(..) => "W0011",
+            Self::UnnecessaryTypeCheck(..) => "W0012",
+            Self::NegativeCoinAmount(..) => "W0013",
+            Self::EmptyAnnouncementMessage => "W0014",
+            Self::DuplicateAssertMyAmount => "W0015",
+            Self::ConflictingAssertMyAmount(..) => "W0016",
+            Self::UnknownLint(..) => "W0017",
+            Self::UnreachableCode => "W0018",
+            Self::ConstantCondition(..) => "W0019",
+            Self::ShadowedParameter(..) => "W0020",
+            Self::SelfComparison(..) => "W0021",
+            Self::DivisionByZero => "W0022",
+        }
+    }
+
+    /// The name used to change the level of the warning, such as in `#[allow(unused_parameter)]`.
+    pub fn lint(&self) -> &'static str {
+        match self {
+            Self::UnusedFunction(..) => "unused_function",
+            Self::UnusedInlineFunction(..) => "unused_inline_function",
+            Self::UnusedParameter(..) => "unused_parameter",
+            Self::UnusedConst(..) => "unused_const",
+            Self::UnusedInlineConst(..) => "unused_inline_const",
+            Self::UnusedLet(..) => "unused_let",
+            Self::UnusedGenericType(..) => "unused_generic_type",
+            Self::UnusedEnum(..) => "unused_enum",
+            Self::UnusedEnumVariant(..) => "unused_enum_variant",
+            Self::UnusedStruct(..) => "unused_struct",
+            Self::UnusedTypeAlias(..) => "unused_type_alias",
+            Self::UnnecessaryTypeCheck(..) => "unnecessary_type_check",
+            Self::NegativeCoinAmount(..) => "negative_coin_amount",
+            Self::EmptyAnnouncementMessage => "empty_announcement_message",
+            Self::DuplicateAssertMyAmount => "duplicate_assert_my_amount",
+            Self::ConflictingAssertMyAmount(..) => "conflicting_assert_my_amount",
+            Self::UnknownLint(..) => "unknown_lint",
+            Self::UnreachableCode => "unreachable_code",
+            Self::ConstantCondition(..) => "constant_condition",
+            Self::ShadowedParameter(..) => "shadowed_parameter",
+            Self::SelfComparison(..) => "self_comparison",
+            Self::DivisionByZero => "division_by_zero",
+        }
+    }
+}
+
+impl ErrorKind {
+    /// A stable identifier for the error, which doesn't change between releases.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::DuplicateType(..) => "E0001",
+            Self::DuplicateSymbol(..) => "E0002",
+            Self::ModuleNameTakenByEnum(..) => "E0003",
+            Self::EnumNameTakenByModule(..) => "E0004",
+            Self::UnknownSymbol(..) => "E0005",
+            Self::UnknownType(..) => "E0006",
+            Self::InlineFunctionReference(..) => "E0007",
+            Self::ModuleReference(..) => "E0008",
+            Self::RecursiveTypeAlias(..) => "E0009",
+            Self::TypeMismatch(..) => "E0010",
+            Self::CastMismatch(..) => "E0011",
+            Self::CannotInferType => "E0012",
+            Self::UncallableType(..) => "E0013",
+            Self::ArgumentMismatch(..) => "E0014",
+            Self::ArgumentMismatchSpread(..) => "E0015",
+            Self::UninitializableType(..) => "E0016",
+            Self::InvalidEnumVariantInitializer(..) => "E0017",
+            Self::InvalidEnumVariantReference(..) => "E0018",
+            Self::DuplicateInitializerField(..) => "E0019",
+            Self::UnknownInitializerField(..) => "E0020",
+            Self::MissingInitializerFields(..) => "E0021",
+            Self::UnknownField(..) => "E0022",
+            Self::InvalidFieldAccess(..) => "E0023",
+            Self::InvalidIndexAccess(..) => "E0024",
+            Self::InvalidSpreadItem => "E0025",
+            Self::InvalidSpreadArgument => "E0026",
+            Self::InvalidSpreadParameter => "E0027",
+            Self::InvalidSpreadField => "E0028",
+            Self::UnsupportedFunctionSpread => "E0029",
+            Self::RequiredFunctionSpread => "E0030",
+            Self::DuplicateEnumVariant(..) => "E0031",
+            Self::DuplicateEnumDiscriminant(..) => "E0032",
+            Self::EnumDiscriminantTooLarge => "E0033",
+            Self::UnknownEnumVariantPath(..) => "E0034",
+            Self::UnknownModulePath(..) => "E0035",
+            Self::PrivateSymbol(..) => "E0036",
+            Self::PrivateType(..) => "E0037",
+            Self::InvalidTypePath(..) => "E0038",
+            Self::InvalidSymbolPath(..) => "E0039",
+            Self::ExpectedTypePath(..) => "E0040",
+            Self::ExpectedSymbolPath(..) => "E0041",
+            Self::UnexpectedGenericArgs => "E0042",
+            Self::ExpectedGenericArgs => "E0043",
+            Self::GenericArgsMismatch(..) => "E0044",
+            Self::UnresolvedImport(..) => "E0045",
+            Self::ImpossibleTypeCheck(..) => "E0046",
+            Self::RecursiveTypeCheck(..) => "E0047",
+            Self::ImplicitReturnInIf => "E0048",
+            Self::ExplicitReturnInExpr => "E0049",
+            Self::EmptyBlock => "E0050",
+            Self::NonAtomEquality(..) => "E0051",
+            Self::IntegerTooLarge => "E0052",
+            Self::InvalidClvmAssembly(..) => "E0053",
+            Self::RecursiveConstantReference => "E0054",
+            Self::RecursiveInlineConstantReference => "E0055",
+            Self::RecursiveInlineFunctionCall => "E0056",
+            Self::UnknownAttribute(..) => "E0057",
+            Self::InvalidHexLiteral => "E0058",
+            Self::MissingMainFunction => "E0059",
+            Self::RecursiveMainFunction => "E0060",
+            Self::InaccessibleSymbol(..) => "E0061",
+        }
+    }
 }
 
 impl fmt::Display for WarningKind {
@@ -152,6 +415,24 @@ impl fmt::Display for WarningKind {
             Self::UnnecessaryTypeCheck(from, to) => {
                 format!("Checking `{from}` against `{to}` has no effect")
             }
+
+            // Conditions.
+            Self::NegativeCoinAmount(amount) => format!("Cannot create a coin with negative amount `{amount}`"),
+            Self::EmptyAnnouncementMessage => "Announcement message is empty".to_string(),
+            Self::DuplicateAssertMyAmount => "Duplicate `AssertMyAmount` condition".to_string(),
+            Self::ConflictingAssertMyAmount(first, second) => format!(
+                "Conflicting `AssertMyAmount` conditions for amounts `{first}` and `{second}` will always fail"
+            ),
+
+            // Attributes.
+            Self::UnknownLint(name) => format!("Unknown lint `{name}`"),
+
+            // Static analysis.
+            Self::UnreachableCode => "Unreachable code".to_string(),
+            Self::ConstantCondition(value) => format!("Condition is always `{value}`"),
+            Self::ShadowedParameter(name) => format!("Let binding `{name}` shadows a parameter"),
+            Self::SelfComparison(name) => format!("Comparing `{name}` to itself"),
+            Self::DivisionByZero => "Division by zero will always fail".to_string(),
         };
         write!(f, "{}", message.trim())
     }
@@ -279,6 +560,9 @@ impl fmt::Display for ErrorKind {
                 format!("Expected {expected} generic argument{}, but found {found}", if *expected == 1 { "" } else { "s" })
             }
 
+            // Imports.
+            Self::UnresolvedImport(name) => format!("Cannot resolve import `{name}`"),
+
             // Type guards.
             Self::ImpossibleTypeCheck(from, to) => format!("Cannot check type `{from}` against `{to}`"),
             Self::RecursiveTypeCheck(from, to) => format!("Checking type `{from}` against `{to}` would result in infinite recursion at runtime"),
@@ -295,11 +579,28 @@ impl fmt::Display for ErrorKind {
             // Atoms.
             Self::NonAtomEquality(ty) => format!("Cannot check equality on non-atom type `{ty}`"),
             Self::IntegerTooLarge => "Integer literal is too large to allocate in CLVM".to_string(),
+            Self::InvalidHexLiteral => "Hex literal must have an even number of digits".to_string(),
+
+            // Inline assembly.
+            Self::InvalidClvmAssembly(error) => format!("Invalid CLVM assembly: {error}"),
 
             // Recursive constants.
             Self::RecursiveConstantReference => "Cannot recursively reference constant".to_string(),
             Self::RecursiveInlineConstantReference => "Cannot recursively reference inline constant".to_string(),
             Self::RecursiveInlineFunctionCall => "Cannot recursively call inline function".to_string(),
+
+            // Attributes.
+            Self::UnknownAttribute(name) => format!("Unknown attribute `{name}`, expected `allow`, `warn` or `deny`"),
+
+            // Entry point.
+            Self::MissingMainFunction => "A `main` function is required to compile the program".to_string(),
+            Self::RecursiveMainFunction => "The `main` function cannot reference itself, either directly or through other functions".to_string(),
+
+            // Code generation.
+            Self::InaccessibleSymbol(name) => formatdoc!("
+                Symbol `{name}` is not accessible from the environment it's referenced in. \
+                This is a bug in the compiler, and should be reported.
+            "),
         };
         write!(f, "{}", message.trim())
     }
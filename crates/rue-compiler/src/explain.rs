@@ -0,0 +1,27 @@
+macro_rules! explanations {
+    ($($code:literal),* $(,)?) => {
+        /// Every diagnostic code, including syntax errors from the parser.
+        pub const DIAGNOSTIC_CODES: &[&str] = &[$($code),*];
+
+        /// The long-form explanation of a diagnostic code, in Markdown.
+        /// Each explanation includes an example of erroneous code, followed by how to fix it.
+        pub fn explain(code: &str) -> Option<&'static str> {
+            match code {
+                $($code => Some(include_str!(concat!("../explanations/", $code, ".md"))),)*
+                _ => None,
+            }
+        }
+    };
+}
+
+explanations!(
+    "P0001", "P0002", "P0003", "P0004", "P0005", "E0001", "E0002", "E0003", "E0004", "E0005",
+    "E0006", "E0007", "E0008", "E0009", "E0010", "E0011", "E0012", "E0013", "E0014", "E0015",
+    "E0016", "E0017", "E0018", "E0019", "E0020", "E0021", "E0022", "E0023", "E0024", "E0025",
+    "E0026", "E0027", "E0028", "E0029", "E0030", "E0031", "E0032", "E0033", "E0034", "E0035",
+    "E0036", "E0037", "E0038", "E0039", "E0040", "E0041", "E0042", "E0043", "E0044", "E0045",
+    "E0046", "E0047", "E0048", "E0049", "E0050", "E0051", "E0052", "E0053", "E0054", "E0055",
+    "E0056", "E0057", "E0058", "E0059", "E0060", "E0061", "W0001", "W0002", "W0003", "W0004",
+    "W0005", "W0006", "W0007", "W0008", "W0009", "W0010", "W0011", "W0012", "W0013", "W0014",
+    "W0015", "W0016", "W0017", "W0018", "W0019", "W0020", "W0021", "W0022",
+);
@@ -1,4 +1,6 @@
+use indexmap::IndexMap;
 use rowan::TextRange;
+use rue_typing::TypeId;
 
 use crate::{
     database::{HirId, SymbolId},
@@ -18,6 +20,15 @@ pub enum Hir {
     Substr(HirId, HirId, HirId),
     Raise(Option<HirId>),
     If(HirId, HirId, HirId),
+    Clvm(Vec<u8>, Vec<HirId>),
+}
+
+/// The fields that were specified when initializing an enum variant.
+#[derive(Debug, Clone)]
+pub struct VariantInitializer {
+    pub type_id: TypeId,
+    pub fields: IndexMap<String, (HirId, TextRange)>,
+    pub text_range: TextRange,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -4,25 +4,40 @@ mod database;
 mod dependency_graph;
 mod environment;
 mod error;
+mod explain;
 mod hir;
+mod lint;
 mod lir;
 mod lowerer;
 mod mir;
 mod optimizer;
 mod scope;
 mod symbol;
+mod syntax;
 mod value;
 
 use clvmr::{Allocator, NodePtr};
 use compiler::{
-    build_graph, codegen, compile_modules, load_module, load_standard_library, setup_compiler,
-    try_export_main,
+    build_graph, check_conditions, codegen, compile_modules, load_module, load_standard_library,
+    restore_compiler, setup_compiler, try_export_main, CompilerSnapshot, SymbolTable,
 };
-use rue_parser::Root;
+use dependency_graph::DependencyGraph;
+use lint::apply_lint_levels;
+use rowan::TextRange;
+use rue_parser::{Root, SyntaxToken};
 
 pub use database::*;
 pub use error::*;
+pub use explain::*;
+pub use lint::*;
 use rue_typing::TypeSystem;
+pub use scope::Scope;
+pub use symbol::*;
+pub use syntax::*;
+pub use value::Value;
+
+/// The source code of the standard library, which is compiled alongside every program by default.
+pub const STANDARD_LIBRARY: &str = include_str!("../stdlib.rue");
 
 #[derive(Debug)]
 pub struct Output {
@@ -31,33 +46,109 @@ pub struct Output {
 }
 
 pub fn compile(allocator: &mut Allocator, root: &Root, should_codegen: bool) -> Output {
-    compile_raw(allocator, root, should_codegen, true)
+    compile_raw(
+        allocator,
+        root,
+        should_codegen,
+        true,
+        &LintConfig::default(),
+    )
 }
 
-pub fn compile_raw(
-    allocator: &mut Allocator,
-    root: &Root,
-    mut should_codegen: bool,
-    should_stdlib: bool,
-) -> Output {
+/// The result of analyzing a program without generating code.
+/// This is used by tooling such as the language server to query the compiler's results.
+#[derive(Debug)]
+pub struct Analysis {
+    pub db: Database,
+    pub ty: TypeSystem,
+    pub main_module_id: SymbolId,
+    pub stdlib_module_id: Option<SymbolId>,
+    pub main_symbol_id: Option<SymbolId>,
+    graph: DependencyGraph,
+}
+
+impl Analysis {
+    pub fn diagnostics(&self) -> &[Diagnostic] {
+        self.db.diagnostics()
+    }
+}
+
+/// The standard library after it has been compiled, which can be reused to analyze many programs
+/// without compiling it again each time.
+#[derive(Debug, Clone)]
+pub struct CompiledStandardLibrary {
+    db: Database,
+    ty: TypeSystem,
+    module_id: SymbolId,
+    snapshot: CompilerSnapshot,
+}
+
+pub fn compile_standard_library() -> CompiledStandardLibrary {
     let mut db = Database::new();
     let mut ty = TypeSystem::new();
     let mut ctx = setup_compiler(&mut db, &mut ty);
+    let module_id = load_standard_library(&mut ctx);
+    let snapshot = ctx.snapshot();
 
-    let stdlib = if should_stdlib {
-        Some(load_standard_library(&mut ctx))
-    } else {
-        None
-    };
+    CompiledStandardLibrary {
+        db,
+        ty,
+        module_id,
+        snapshot,
+    }
+}
+
+pub fn analyze(root: &Root, should_stdlib: bool, lints: &LintConfig) -> Analysis {
+    if should_stdlib {
+        return analyze_with_stdlib(root, &compile_standard_library(), lints);
+    }
 
+    let mut db = Database::new();
+    let mut ty = TypeSystem::new();
+    let mut ctx = setup_compiler(&mut db, &mut ty);
     let main_module_id = load_module(&mut ctx, root);
     let symbol_table = compile_modules(ctx);
 
+    finish_analysis(db, ty, &symbol_table, root, main_module_id, None, lints)
+}
+
+/// Analyzes a program against a copy of a standard library which has already been compiled.
+pub fn analyze_with_stdlib(
+    root: &Root,
+    stdlib: &CompiledStandardLibrary,
+    lints: &LintConfig,
+) -> Analysis {
+    let mut db = stdlib.db.clone();
+    let mut ty = stdlib.ty.clone();
+    let mut ctx = restore_compiler(&mut db, &mut ty, stdlib.snapshot.clone());
+    let main_module_id = load_module(&mut ctx, root);
+    let symbol_table = compile_modules(ctx);
+
+    finish_analysis(
+        db,
+        ty,
+        &symbol_table,
+        root,
+        main_module_id,
+        Some(stdlib.module_id),
+        lints,
+    )
+}
+
+fn finish_analysis(
+    mut db: Database,
+    ty: TypeSystem,
+    symbol_table: &SymbolTable,
+    root: &Root,
+    main_module_id: SymbolId,
+    stdlib: Option<SymbolId>,
+    lints: &LintConfig,
+) -> Analysis {
     let main = try_export_main(&mut db, main_module_id);
     let graph = build_graph(
         &mut db,
         &ty,
-        &symbol_table,
+        symbol_table,
         main_module_id,
         &if let Some(stdlib) = stdlib {
             [main_module_id, stdlib].to_vec()
@@ -66,19 +157,73 @@ pub fn compile_raw(
         },
     );
 
+    if let (Some(stdlib), Some(main)) = (stdlib, main) {
+        check_conditions(&mut db, &ty, stdlib, main);
+    }
+
+    apply_lint_levels(&mut db, root, lints);
+
+    Analysis {
+        db,
+        ty,
+        main_module_id,
+        stdlib_module_id: stdlib,
+        main_symbol_id: main,
+        graph,
+    }
+}
+
+pub fn compile_raw(
+    allocator: &mut Allocator,
+    root: &Root,
+    mut should_codegen: bool,
+    should_stdlib: bool,
+    lints: &LintConfig,
+) -> Output {
+    let Analysis {
+        mut db,
+        main_symbol_id,
+        graph,
+        ..
+    } = analyze(root, should_stdlib, lints);
+
+    // Only functions can be used as the entry point of the program.
+    let main_function_id =
+        main_symbol_id.filter(|&symbol_id| matches!(db.symbol(symbol_id), Symbol::Function(..)));
+
+    if should_codegen {
+        let range = main_symbol_id
+            .and_then(|symbol_id| db.symbol_token(symbol_id))
+            .map_or_else(TextRange::default, SyntaxToken::text_range);
+
+        match main_function_id {
+            None => {
+                db.error(ErrorKind::MissingMainFunction, range);
+            }
+            // The entry point isn't defined in its own environment, so it can't be lowered.
+            Some(main_function_id) if graph.is_recursive(main_function_id) => {
+                db.error(ErrorKind::RecursiveMainFunction, range);
+            }
+            Some(..) => {}
+        }
+    }
+
     should_codegen &= !db.diagnostics().iter().any(Diagnostic::is_error);
 
+    let mut node_ptr = match main_function_id {
+        Some(main_function_id) if should_codegen => {
+            codegen(allocator, &mut db, &graph, main_function_id)
+        }
+        _ => NodePtr::default(),
+    };
+
+    // Code generation can fail too, in which case the program is incomplete.
+    if db.diagnostics().iter().any(Diagnostic::is_error) {
+        node_ptr = NodePtr::default();
+    }
+
     Output {
         diagnostics: db.diagnostics().to_vec(),
-        node_ptr: if should_codegen {
-            codegen(
-                allocator,
-                &mut db,
-                &graph,
-                main.expect("missing main function"),
-            )
-        } else {
-            NodePtr::default()
-        },
+        node_ptr,
     }
 }
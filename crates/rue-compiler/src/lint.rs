@@ -0,0 +1,225 @@
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use indexmap::IndexMap;
+use rowan::{TextRange, TextSize};
+use rue_parser::{AstNode, Attribute, Item, Root};
+use thiserror::Error;
+
+use crate::{Database, DiagnosticKind, ErrorKind, WarningKind};
+
+/// The name of the project manifest, which is found in the directory of a source file or any of its ancestors.
+pub const MANIFEST_FILE_NAME: &str = "Rue.toml";
+
+/// Every lint which can be configured, along with `warnings`, which applies to all of them.
+pub const LINTS: &[&str] = &[
+    "warnings",
+    "unused_function",
+    "unused_inline_function",
+    "unused_parameter",
+    "unused_const",
+    "unused_inline_const",
+    "unused_let",
+    "unused_generic_type",
+    "unused_enum",
+    "unused_enum_variant",
+    "unused_struct",
+    "unused_type_alias",
+    "unnecessary_type_check",
+    "negative_coin_amount",
+    "empty_announcement_message",
+    "duplicate_assert_my_amount",
+    "conflicting_assert_my_amount",
+    "unknown_lint",
+    "unreachable_code",
+    "constant_condition",
+    "shadowed_parameter",
+    "self_comparison",
+    "division_by_zero",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LintLevel {
+    Allow,
+    Warn,
+    Deny,
+}
+
+impl LintLevel {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "allow" => Some(Self::Allow),
+            "warn" => Some(Self::Warn),
+            "deny" => Some(Self::Deny),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum ManifestError {
+    #[error("could not read {0}: {1}")]
+    Io(PathBuf, io::Error),
+
+    #[error("invalid manifest: {0}")]
+    Toml(#[from] toml::de::Error),
+
+    #[error("`lints` must be a table of lint names to levels")]
+    InvalidLints,
+
+    #[error("unknown lint `{0}`")]
+    UnknownLint(String),
+
+    #[error("invalid level for lint `{0}`, expected `allow`, `warn` or `deny`")]
+    InvalidLevel(String),
+}
+
+/// The lint levels which apply to the whole program, unless overridden by an attribute.
+#[derive(Debug, Default, Clone)]
+pub struct LintConfig {
+    levels: IndexMap<String, LintLevel>,
+    deny_warnings: bool,
+}
+
+impl LintConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn set_level(&mut self, lint: impl Into<String>, level: LintLevel) {
+        self.levels.insert(lint.into(), level);
+    }
+
+    /// Treats every warning which isn't allowed as an error, regardless of its configured level.
+    pub fn set_deny_warnings(&mut self, deny_warnings: bool) {
+        self.deny_warnings = deny_warnings;
+    }
+
+    /// Parses the `[lints]` table of a manifest, which maps lint names to levels.
+    pub fn from_manifest(source: &str) -> Result<Self, ManifestError> {
+        let manifest: toml::Table = source.parse()?;
+        let mut config = Self::new();
+
+        let Some(lints) = manifest.get("lints") else {
+            return Ok(config);
+        };
+
+        let lints = lints.as_table().ok_or(ManifestError::InvalidLints)?;
+
+        for (lint, level) in lints {
+            if !LINTS.contains(&lint.as_str()) {
+                return Err(ManifestError::UnknownLint(lint.clone()));
+            }
+
+            let level = level
+                .as_str()
+                .and_then(LintLevel::from_name)
+                .ok_or_else(|| ManifestError::InvalidLevel(lint.clone()))?;
+
+            config.set_level(lint.clone(), level);
+        }
+
+        Ok(config)
+    }
+
+    /// Loads the lint levels from the nearest manifest to the source file, if there is one.
+    pub fn load(file: &Path) -> Result<Self, ManifestError> {
+        let Some(path) = find_manifest(file) else {
+            return Ok(Self::new());
+        };
+
+        let source = fs::read_to_string(&path).map_err(|error| ManifestError::Io(path, error))?;
+        Self::from_manifest(&source)
+    }
+
+    fn level(&self, lint: &str) -> LintLevel {
+        self.levels
+            .get(lint)
+            .or_else(|| self.levels.get("warnings"))
+            .copied()
+            .unwrap_or(LintLevel::Warn)
+    }
+}
+
+/// Finds the manifest in the directory of the source file, or the closest ancestor directory.
+pub fn find_manifest(file: &Path) -> Option<PathBuf> {
+    let file = file.canonicalize().ok()?;
+
+    file.ancestors()
+        .skip(1)
+        .map(|directory| directory.join(MANIFEST_FILE_NAME))
+        .find(|path| path.is_file())
+}
+
+/// Checks the attributes in the program, then removes warnings which are allowed and denies those
+/// which are denied. Attributes on the innermost item take precedence, followed by the config.
+pub(crate) fn apply_lint_levels(db: &mut Database, root: &Root, config: &LintConfig) {
+    check_attributes(db, root);
+
+    let end = root.syntax().text_range().end();
+
+    db.diagnostics_mut().retain_mut(|diagnostic| {
+        let DiagnosticKind::Warning(kind) = diagnostic.kind() else {
+            return true;
+        };
+
+        let level = TextSize::try_from(diagnostic.span().start)
+            .ok()
+            .filter(|&offset| offset < end)
+            .and_then(|offset| attribute_level(root, offset, kind))
+            .unwrap_or_else(|| config.level(kind.lint()));
+
+        match level {
+            LintLevel::Allow => return false,
+            LintLevel::Warn if !config.deny_warnings => {}
+            LintLevel::Warn | LintLevel::Deny => diagnostic.deny(),
+        }
+
+        true
+    });
+}
+
+/// The level set by the closest attribute to the offset which mentions the lint, or `warnings`.
+fn attribute_level(root: &Root, offset: TextSize, kind: &WarningKind) -> Option<LintLevel> {
+    let element = root.syntax().covering_element(TextRange::empty(offset));
+
+    element.ancestors().filter_map(Item::cast).find_map(|item| {
+        // Later attributes on the same item take precedence over earlier ones.
+        item.attributes().into_iter().rev().find_map(|attribute| {
+            let level = LintLevel::from_name(attribute.name()?.text())?;
+
+            attribute
+                .args()
+                .iter()
+                .any(|lint| lint.text() == kind.lint() || lint.text() == "warnings")
+                .then_some(level)
+        })
+    })
+}
+
+fn check_attributes(db: &mut Database, root: &Root) {
+    for attribute in root.syntax().descendants().filter_map(Attribute::cast) {
+        let Some(name) = attribute.name() else {
+            continue;
+        };
+
+        if LintLevel::from_name(name.text()).is_none() {
+            db.error(
+                ErrorKind::UnknownAttribute(name.to_string()),
+                name.text_range(),
+            );
+            continue;
+        }
+
+        for lint in attribute.args() {
+            if !LINTS.contains(&lint.text()) {
+                db.warning(
+                    WarningKind::UnknownLint(lint.to_string()),
+                    lint.text_range(),
+                );
+            }
+        }
+    }
+}
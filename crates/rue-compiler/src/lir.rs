@@ -39,4 +39,5 @@ pub enum Lir {
     GtBytes(LirId, LirId),
     Rem(LirId, LirId),
     Ash(LirId, LirId),
+    Clvm(Vec<u8>, Vec<LirId>),
 }
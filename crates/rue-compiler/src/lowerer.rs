@@ -86,6 +86,13 @@ impl<'a> Lowerer<'a> {
                 self.db
                     .alloc_mir(Mir::If(condition, then_branch, else_branch))
             }
+            Hir::Clvm(bytes, args) => {
+                let args = args
+                    .into_iter()
+                    .map(|arg| self.lower_hir(env_id, arg))
+                    .collect();
+                self.db.alloc_mir(Mir::Clvm(bytes, args))
+            }
             Hir::Definition(scope_id, hir_id) => self.lower_definition(env_id, scope_id, hir_id),
         }
     }
@@ -100,7 +107,7 @@ impl<'a> Lowerer<'a> {
         match self.db.symbol(symbol_id).clone() {
             Symbol::Function(function) => self.lower_closure(symbol_id, &function),
             Symbol::InlineConst(constant) => self.lower_hir(env_id, constant.hir_id),
-            Symbol::Let(binding) if self.graph.symbol_references(symbol_id) == 1 => {
+            Symbol::Let(binding) if self.is_inlined(symbol_id) => {
                 self.lower_hir(env_id, binding.hir_id)
             }
             Symbol::Let(..) | Symbol::Const(..) | Symbol::Parameter(..) => {
@@ -125,10 +132,7 @@ impl<'a> Lowerer<'a> {
 
         let mut captures = Vec::new();
 
-        for symbol_id in self.db.env(function_env_id).definitions() {
-            captures.push(self.lower_predefined_reference(symbol_id));
-        }
-
+        // Definitions are curried into the body by the function itself, so only captures are passed.
         for symbol_id in self.db.env(function_env_id).captures() {
             captures.push(self.lower_predefined_reference(symbol_id));
         }
@@ -136,20 +140,33 @@ impl<'a> Lowerer<'a> {
         self.db.alloc_mir(Mir::Closure(body, captures))
     }
 
+    /// Let bindings which are only referenced once are inlined, unless they're captured by a closure,
+    /// since the closure needs to be able to access the value from its environment.
+    fn is_inlined(&self, symbol_id: SymbolId) -> bool {
+        self.graph.symbol_references(symbol_id) == 1 && !self.graph.is_captured(symbol_id)
+    }
+
     fn lower_definition(
         &mut self,
         parent_env_id: EnvironmentId,
         child_scope_id: ScopeId,
         hir_id: HirId,
     ) -> MirId {
-        let child_env_id = self.graph.environment_id(child_scope_id);
+        // The definition is evaluated in the environment it's lowered in, which differs from the
+        // one it was defined in if it's part of an inline function.
+        let mut child_env = self
+            .db
+            .env(self.graph.environment_id(child_scope_id))
+            .clone();
+        *child_env.parent_mut() = Some(parent_env_id);
+        let child_env_id = self.db.alloc_env(child_env);
 
         for symbol_id in self.db.env_mut(child_env_id).definitions() {
             let Symbol::Let(..) = self.db.symbol(symbol_id) else {
                 continue;
             };
 
-            if self.graph.symbol_references(symbol_id) == 1 {
+            if self.is_inlined(symbol_id) {
                 self.db.env_mut(child_env_id).remove_definition(symbol_id);
             }
         }
@@ -225,7 +242,7 @@ impl<'a> Lowerer<'a> {
         match self.db.symbol(symbol_id).clone() {
             Symbol::Function(function) => self.lower_function(&function),
             Symbol::Const(constant) => self.lower_hir(env_id, constant.hir_id),
-            Symbol::Let(binding) if self.graph.symbol_references(symbol_id) > 1 => {
+            Symbol::Let(binding) if !self.is_inlined(symbol_id) => {
                 self.lower_hir(env_id, binding.hir_id)
             }
             Symbol::Unknown
@@ -18,4 +18,5 @@ pub enum Mir {
     Curry(MirId, Vec<MirId>),
     Closure(MirId, Vec<MirId>),
     Quote(MirId),
+    Clvm(Vec<u8>, Vec<MirId>),
 }
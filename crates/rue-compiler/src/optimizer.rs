@@ -1,6 +1,8 @@
 use num_bigint::BigInt;
 use num_traits::One;
+use rowan::TextRange;
 use rue_clvm::{first_path, rest_path};
+use rue_parser::SyntaxToken;
 use rue_typing::bigint_to_bytes;
 
 use crate::{
@@ -8,7 +10,7 @@ use crate::{
     hir::{BinOp, Op},
     lir::Lir,
     mir::Mir,
-    EnvironmentId, MirId,
+    EnvironmentId, ErrorKind, MirId,
 };
 
 #[derive(Debug)]
@@ -80,6 +82,7 @@ impl<'a> Optimizer<'a> {
             Mir::Curry(body, args) => self.opt_curry(env_id, body, args),
             Mir::Run(body, args) => self.opt_run(env_id, body, args),
             Mir::Quote(value) => self.opt_quote(env_id, value),
+            Mir::Clvm(bytes, args) => self.opt_clvm(env_id, bytes, args),
             Mir::Environment(env_id, mir_id) => self.opt_mir(env_id, mir_id),
         }
     }
@@ -108,6 +111,15 @@ impl<'a> Optimizer<'a> {
         self.db.alloc_lir(Lir::Run(body, Some(args)))
     }
 
+    fn opt_clvm(&mut self, env_id: EnvironmentId, bytes: Vec<u8>, args: Vec<MirId>) -> LirId {
+        // The assembly itself is emitted verbatim, only the operands are optimized.
+        let args = args
+            .into_iter()
+            .map(|arg| self.opt_mir(env_id, arg))
+            .collect();
+        self.db.alloc_lir(Lir::Clvm(bytes, args))
+    }
+
     fn opt_quote(&mut self, env_id: EnvironmentId, mir_id: MirId) -> LirId {
         let value = self.opt_mir(env_id, mir_id);
         self.db.alloc_lir(Lir::Quote(value))
@@ -130,15 +142,15 @@ impl<'a> Optimizer<'a> {
             env_id = parent_env_id;
         }
 
-        let index = environment
-            .iter()
-            .position(|&id| id == symbol_id)
-            .unwrap_or_else(|| {
-                panic!(
-                    "Symbol `{}` not found in environment.",
-                    self.db.dbg_symbol(symbol_id)
-                );
-            });
+        let Some(index) = environment.iter().position(|&id| id == symbol_id) else {
+            let range = self
+                .db
+                .symbol_token(symbol_id)
+                .map_or_else(TextRange::default, SyntaxToken::text_range);
+            let name = self.db.dbg_symbol(symbol_id);
+            self.db.error(ErrorKind::InaccessibleSymbol(name), range);
+            return self.db.alloc_lir(Lir::Atom(Vec::new()));
+        };
 
         let mut path = BigInt::one();
 
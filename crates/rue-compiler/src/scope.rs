@@ -3,12 +3,14 @@ use rue_typing::TypeId;
 
 use crate::SymbolId;
 
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone)]
 pub struct Scope {
     named_symbols: IndexMap<String, SymbolId>,
     symbol_names: IndexMap<SymbolId, String>,
     named_types: IndexMap<String, TypeId>,
     type_names: IndexMap<TypeId, String>,
+    imported_symbols: IndexMap<String, SymbolId>,
+    imported_types: IndexMap<String, TypeId>,
 }
 
 impl Scope {
@@ -18,7 +20,10 @@ impl Scope {
     }
 
     pub fn symbol(&self, name: &str) -> Option<SymbolId> {
-        self.named_symbols.get(name).copied()
+        self.named_symbols
+            .get(name)
+            .or_else(|| self.imported_symbols.get(name))
+            .copied()
     }
 
     pub fn define_type(&mut self, name: String, type_id: TypeId) {
@@ -27,15 +32,44 @@ impl Scope {
     }
 
     pub fn ty(&self, name: &str) -> Option<TypeId> {
-        self.named_types.get(name).copied()
+        self.named_types
+            .get(name)
+            .or_else(|| self.imported_types.get(name))
+            .copied()
+    }
+
+    /// Imported symbols can be referenced by name, but aren't local to the scope.
+    pub fn import_symbol(&mut self, name: String, symbol_id: SymbolId) {
+        self.imported_symbols.insert(name, symbol_id);
+    }
+
+    /// Imported types can be referenced by name, but aren't local to the scope.
+    pub fn import_type(&mut self, name: String, type_id: TypeId) {
+        self.imported_types.insert(name, type_id);
     }
 
     pub fn type_name(&self, type_id: TypeId) -> Option<&str> {
-        self.type_names.get(&type_id).map(String::as_str)
+        self.type_names
+            .get(&type_id)
+            .map(String::as_str)
+            .or_else(|| {
+                self.imported_types
+                    .iter()
+                    .find(|(_, &id)| id == type_id)
+                    .map(|(name, _)| name.as_str())
+            })
     }
 
     pub fn symbol_name(&self, symbol_id: SymbolId) -> Option<&str> {
-        self.symbol_names.get(&symbol_id).map(String::as_str)
+        self.symbol_names
+            .get(&symbol_id)
+            .map(String::as_str)
+            .or_else(|| {
+                self.imported_symbols
+                    .iter()
+                    .find(|(_, &id)| id == symbol_id)
+                    .map(|(name, _)| name.as_str())
+            })
     }
 
     pub fn is_local(&self, symbol_id: SymbolId) -> bool {
@@ -49,4 +83,26 @@ impl Scope {
     pub fn local_types(&self) -> Vec<TypeId> {
         self.type_names.keys().copied().collect()
     }
+
+    pub fn imported_types(&self) -> Vec<TypeId> {
+        self.imported_types.values().copied().collect()
+    }
+
+    /// Every symbol that can be referenced by name from this scope, including imports.
+    pub fn named_symbols(&self) -> Vec<(&str, SymbolId)> {
+        self.named_symbols
+            .iter()
+            .chain(&self.imported_symbols)
+            .map(|(name, &symbol_id)| (name.as_str(), symbol_id))
+            .collect()
+    }
+
+    /// Every type that can be referenced by name from this scope, including imports.
+    pub fn named_types(&self) -> Vec<(&str, TypeId)> {
+        self.named_types
+            .iter()
+            .chain(&self.imported_types)
+            .map(|(name, &type_id)| (name.as_str(), type_id))
+            .collect()
+    }
 }
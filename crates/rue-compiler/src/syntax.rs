@@ -0,0 +1,126 @@
+use rowan::{NodeOrToken, TextRange, TextSize};
+use rue_parser::{SyntaxKind, SyntaxNode, SyntaxToken};
+
+/// Whitespace and comments, which the parser attaches to the end of the preceding node.
+pub fn is_trivia(kind: SyntaxKind) -> bool {
+    matches!(
+        kind,
+        SyntaxKind::Whitespace | SyntaxKind::LineComment | SyntaxKind::BlockComment
+    )
+}
+
+/// The range of a node, excluding any whitespace or comments attached to either end.
+pub fn trimmed_range(node: &SyntaxNode) -> TextRange {
+    let Some((first, last)) = trimmed_tokens(node) else {
+        return node.text_range();
+    };
+
+    TextRange::new(first.text_range().start(), last.text_range().end())
+}
+
+/// The range to delete when removing a node. If nothing else is on the same lines as the node,
+/// the lines are removed entirely. Comments after the node are always kept.
+pub fn removal_range(node: &SyntaxNode) -> TextRange {
+    let Some((first, last)) = trimmed_tokens(node) else {
+        return node.text_range();
+    };
+
+    let (end, line_end) = trailing_whitespace(&last);
+
+    let (Some(start), Some(line_end)) = (line_start(&first), line_end) else {
+        return TextRange::new(first.text_range().start(), end);
+    };
+
+    // If the node is surrounded by blank lines, one of them is removed too.
+    if blank_line_before(&first) {
+        if let Some(end) = blank_line_after(&last) {
+            return TextRange::new(start, end);
+        }
+    }
+
+    TextRange::new(start, line_end)
+}
+
+fn trimmed_tokens(node: &SyntaxNode) -> Option<(SyntaxToken, SyntaxToken)> {
+    let mut tokens = node
+        .descendants_with_tokens()
+        .filter_map(NodeOrToken::into_token)
+        .filter(|token| !is_trivia(token.kind()));
+
+    let first = tokens.next()?;
+    let last = tokens.last().unwrap_or_else(|| first.clone());
+
+    Some((first, last))
+}
+
+/// The start of the line, if only indentation comes before the token on it.
+fn line_start(token: &SyntaxToken) -> Option<TextSize> {
+    let Some(previous) = token.prev_token() else {
+        return Some(token.text_range().start());
+    };
+
+    match previous.kind() {
+        // Line comments include the newline that ends them.
+        SyntaxKind::LineComment if previous.text().ends_with('\n') => {
+            Some(token.text_range().start())
+        }
+        SyntaxKind::Whitespace => match previous.text().rfind('\n') {
+            Some(index) => {
+                Some(previous.text_range().start() + TextSize::of(&previous.text()[..=index]))
+            }
+            None if previous.prev_token().is_none() => Some(previous.text_range().start()),
+            None => None,
+        },
+        _ => None,
+    }
+}
+
+/// The end of the whitespace after the token on the same line, and the end of the line
+/// including the newline, if nothing else comes after the token on it.
+fn trailing_whitespace(token: &SyntaxToken) -> (TextSize, Option<TextSize>) {
+    let end = token.text_range().end();
+
+    let Some(next) = token.next_token() else {
+        return (end, Some(end));
+    };
+
+    if next.kind() != SyntaxKind::Whitespace {
+        return (end, None);
+    }
+
+    let start = next.text_range().start();
+    let text = next.text();
+
+    match text.find('\n') {
+        Some(index) => (
+            start + TextSize::of(&text[..index]),
+            Some(start + TextSize::of(&text[..=index])),
+        ),
+        None if next.next_token().is_none() => {
+            (next.text_range().end(), Some(next.text_range().end()))
+        }
+        None => (next.text_range().end(), None),
+    }
+}
+
+fn blank_line_before(token: &SyntaxToken) -> bool {
+    let Some(previous) = token.prev_token() else {
+        return true;
+    };
+
+    previous.kind() == SyntaxKind::Whitespace
+        && (previous.text().matches('\n').count() > 1 || previous.prev_token().is_none())
+}
+
+/// The end of the blank line after the token's line, including its newline.
+fn blank_line_after(token: &SyntaxToken) -> Option<TextSize> {
+    let next = token
+        .next_token()
+        .filter(|next| next.kind() == SyntaxKind::Whitespace)?;
+
+    let text = next.text();
+    let first = text.find('\n')?;
+    let second = first + 1 + text[first + 1..].find('\n')?;
+
+    Some(next.text_range().start() + TextSize::of(&text[..=second]))
+}
@@ -0,0 +1,196 @@
+/// An intermediate representation of the formatted output, which decides where lines can break.
+#[derive(Debug, Clone)]
+pub enum Doc {
+    Text(String),
+    /// A space, or a newline if the enclosing group is broken.
+    Line,
+    /// Nothing, or a newline if the enclosing group is broken.
+    SoftLine,
+    /// Always a newline, which breaks every enclosing group.
+    HardLine,
+    /// Text which is deferred until the end of the current line, such as a trailing comment.
+    LineSuffix(String),
+    /// Breaks every enclosing group, without printing anything.
+    BreakParent,
+    Indent(Box<Doc>),
+    /// Printed on a single line if it fits, otherwise every line in it is broken.
+    Group {
+        contents: Box<Doc>,
+        broken: bool,
+    },
+    /// Chooses the first document if the enclosing group is broken, and the second otherwise.
+    IfBreak(Box<Doc>, Box<Doc>),
+    Concat(Vec<Doc>),
+}
+
+impl Doc {
+    pub fn nil() -> Self {
+        Self::Concat(Vec::new())
+    }
+
+    pub fn text(text: impl Into<String>) -> Self {
+        Self::Text(text.into())
+    }
+
+    pub fn indent(doc: Doc) -> Self {
+        Self::Indent(Box::new(doc))
+    }
+
+    pub fn group(doc: Doc) -> Self {
+        let broken = doc.has_forced_break();
+
+        Self::Group {
+            contents: Box::new(doc),
+            broken,
+        }
+    }
+
+    pub fn if_break(broken: Doc, flat: Doc) -> Self {
+        Self::IfBreak(Box::new(broken), Box::new(flat))
+    }
+
+    fn has_forced_break(&self) -> bool {
+        match self {
+            Self::HardLine | Self::BreakParent => true,
+            Self::Group { broken, .. } => *broken,
+            Self::Indent(doc) => doc.has_forced_break(),
+            Self::Concat(docs) => docs.iter().any(Self::has_forced_break),
+            Self::Text(..)
+            | Self::Line
+            | Self::SoftLine
+            | Self::LineSuffix(..)
+            | Self::IfBreak(..) => false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Mode {
+    Flat,
+    Break,
+}
+
+const INDENT: usize = 4;
+
+/// Prints the document, breaking groups which don't fit within the width.
+pub fn print(doc: &Doc, width: usize) -> String {
+    let mut output = String::new();
+    let mut suffixes: Vec<&str> = Vec::new();
+    let mut column = 0;
+    let mut stack = vec![(0, Mode::Break, doc)];
+
+    while let Some((indent, mode, doc)) = stack.pop() {
+        match doc {
+            Doc::Text(text) => {
+                output.push_str(text);
+                column += text.chars().count();
+            }
+            Doc::Line | Doc::SoftLine if mode == Mode::Flat => {
+                if matches!(doc, Doc::Line) {
+                    output.push(' ');
+                    column += 1;
+                }
+            }
+            Doc::Line | Doc::SoftLine | Doc::HardLine => {
+                for suffix in suffixes.drain(..) {
+                    output.push_str(suffix);
+                }
+                newline(&mut output, indent);
+                column = indent;
+            }
+            Doc::LineSuffix(text) => suffixes.push(text),
+            Doc::BreakParent => {}
+            Doc::Indent(doc) => stack.push((indent + INDENT, mode, doc)),
+            Doc::Group { contents, broken } => {
+                let mode = if mode == Mode::Flat
+                    || (!broken && fits(contents, &stack, width.saturating_sub(column)))
+                {
+                    Mode::Flat
+                } else {
+                    Mode::Break
+                };
+                stack.push((indent, mode, contents));
+            }
+            Doc::IfBreak(broken, flat) => {
+                stack.push((
+                    indent,
+                    mode,
+                    if mode == Mode::Break { broken } else { flat },
+                ));
+            }
+            Doc::Concat(docs) => {
+                for doc in docs.iter().rev() {
+                    stack.push((indent, mode, doc));
+                }
+            }
+        }
+    }
+
+    for suffix in suffixes {
+        output.push_str(suffix);
+    }
+
+    let trimmed = output.trim_end().len();
+    output.truncate(trimmed);
+
+    if !output.is_empty() {
+        output.push('\n');
+    }
+
+    output
+}
+
+fn newline(output: &mut String, indent: usize) {
+    let trimmed = output.trim_end_matches(' ').len();
+    output.truncate(trimmed);
+    output.push('\n');
+    output.extend(std::iter::repeat_n(' ', indent));
+}
+
+/// Whether the group fits on the rest of the line when printed flat,
+/// including whatever follows it up until the next possible line break.
+fn fits(group: &Doc, rest: &[(usize, Mode, &Doc)], mut remaining: usize) -> bool {
+    let mut stack = vec![(Mode::Flat, group)];
+    let mut rest = rest.iter().rev();
+
+    loop {
+        let (mode, doc) = match stack.pop() {
+            Some(item) => item,
+            None => match rest.next() {
+                Some(&(_, mode, doc)) => (mode, doc),
+                None => return true,
+            },
+        };
+
+        match doc {
+            Doc::Text(text) => {
+                let Some(left) = remaining.checked_sub(text.chars().count()) else {
+                    return false;
+                };
+                remaining = left;
+            }
+            Doc::Line | Doc::SoftLine if mode == Mode::Flat => {
+                if matches!(doc, Doc::Line) {
+                    let Some(left) = remaining.checked_sub(1) else {
+                        return false;
+                    };
+                    remaining = left;
+                }
+            }
+            Doc::Line | Doc::SoftLine | Doc::HardLine => return true,
+            Doc::LineSuffix(..) | Doc::BreakParent => {}
+            Doc::Indent(doc) => stack.push((mode, doc)),
+            Doc::Group { contents, broken } => {
+                stack.push((if *broken { Mode::Break } else { mode }, contents));
+            }
+            Doc::IfBreak(broken, flat) => {
+                stack.push((mode, if mode == Mode::Break { broken } else { flat }));
+            }
+            Doc::Concat(docs) => {
+                for doc in docs.iter().rev() {
+                    stack.push((mode, doc));
+                }
+            }
+        }
+    }
+}
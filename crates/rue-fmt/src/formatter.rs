@@ -0,0 +1,539 @@
+use rowan::NodeOrToken;
+use rue_parser::{SyntaxElement, SyntaxKind, SyntaxNode, SyntaxToken};
+
+use crate::{
+    doc::Doc,
+    trivia::{Comment, Trivia},
+};
+
+/// How the elements between a pair of delimiters are laid out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ListStyle {
+    /// Items or statements, each on their own line.
+    Statements,
+    /// Comma separated, each on their own line with a trailing comma.
+    Vertical,
+    /// Comma separated with padding inside of the delimiters, such as `{ a, b }`.
+    Spaced,
+    /// Comma separated without padding, such as `(a, b)`.
+    Tight,
+    /// Comma separated and never broken, such as `<A, B>`.
+    Compact,
+}
+
+fn list_style(parent: SyntaxKind, open: SyntaxKind) -> Option<(ListStyle, SyntaxKind)> {
+    use SyntaxKind as S;
+
+    let style = match (parent, open) {
+        (S::ModuleItem, S::OpenBrace) => ListStyle::Statements,
+        (S::StructItem | S::EnumItem, S::OpenBrace) => ListStyle::Vertical,
+        (S::EnumVariantFields | S::InitializerExpr, S::OpenBrace) => ListStyle::Spaced,
+        (S::ImportGroup, S::OpenBrace)
+        | (
+            S::FunctionItem
+            | S::LambdaExpr
+            | S::FunctionType
+            | S::FunctionCallExpr
+            | S::ClvmExpr
+            | S::PairExpr
+            | S::PairType,
+            S::OpenParen,
+        )
+        | (S::ListExpr, S::OpenBracket) => ListStyle::Tight,
+        (S::GenericArgs | S::GenericParams, S::LessThan) | (S::Attribute, S::OpenParen) => {
+            ListStyle::Compact
+        }
+        _ => return None,
+    };
+
+    let close = match open {
+        S::OpenBrace => S::CloseBrace,
+        S::OpenParen => S::CloseParen,
+        S::OpenBracket => S::CloseBracket,
+        _ => S::GreaterThan,
+    };
+
+    Some((style, close))
+}
+
+pub struct Formatter {
+    trivia: Trivia,
+}
+
+impl Formatter {
+    pub fn new(root: &SyntaxNode) -> Self {
+        Self {
+            trivia: Trivia::new(root),
+        }
+    }
+
+    pub fn root(&self, root: &SyntaxNode) -> Doc {
+        let items: Vec<SyntaxElement> = root.children().map(NodeOrToken::Node).collect();
+
+        let mut docs = Vec::new();
+
+        for (index, item) in items.iter().enumerate() {
+            if index > 0 {
+                docs.push(Doc::HardLine);
+                if self.blank_line_before(item) {
+                    docs.push(Doc::HardLine);
+                }
+            }
+            docs.push(self.element(item));
+        }
+
+        for (index, comment) in self.trivia.end.iter().enumerate() {
+            if index > 0 || !items.is_empty() {
+                docs.push(Doc::HardLine);
+                if comment.newlines_before >= 2 {
+                    docs.push(Doc::HardLine);
+                }
+            }
+            docs.push(Doc::text(&comment.text));
+        }
+
+        Doc::Concat(docs)
+    }
+
+    fn element(&self, element: &SyntaxElement) -> Doc {
+        match element {
+            NodeOrToken::Node(node) if node.kind() == SyntaxKind::Block => self.block(node),
+            // Every branch of an if is broken onto multiple lines if any of them are.
+            NodeOrToken::Node(node)
+                if matches!(node.kind(), SyntaxKind::IfExpr | SyntaxKind::IfStmt) =>
+            {
+                Doc::group(self.sequence(node))
+            }
+            NodeOrToken::Node(node) => self.sequence(node),
+            NodeOrToken::Token(token) => self.token(token),
+        }
+    }
+
+    /// Formats the children in order, with spacing between tokens and lists laid out by style.
+    fn sequence(&self, node: &SyntaxNode) -> Doc {
+        let elements = significant_children(node);
+
+        let mut docs = Vec::new();
+        let mut previous: Option<SyntaxToken> = None;
+        let mut index = 0;
+
+        while index < elements.len() {
+            let element = &elements[index];
+
+            if let Some(first) = first_token(element) {
+                if previous
+                    .as_ref()
+                    .is_some_and(|previous| needs_space(previous, &first))
+                {
+                    docs.push(Doc::text(" "));
+                }
+            }
+
+            if let NodeOrToken::Token(open) = element {
+                if let Some((style, close_kind)) = list_style(node.kind(), open.kind()) {
+                    let close = elements[index + 1..].iter().position(|element| {
+                        matches!(element, NodeOrToken::Token(token) if token.kind() == close_kind)
+                    });
+
+                    if let Some(close) = close.map(|close| close + index + 1) {
+                        let NodeOrToken::Token(close_token) = &elements[close] else {
+                            unreachable!();
+                        };
+
+                        docs.push(self.list(style, open, &elements[index + 1..close], close_token));
+                        previous = Some(close_token.clone());
+                        index = close + 1;
+                        continue;
+                    }
+                }
+            }
+
+            docs.push(self.element(element));
+            index += 1;
+
+            // Attributes are each on their own line, before the item they apply to.
+            if element.kind() == SyntaxKind::Attribute {
+                docs.push(Doc::HardLine);
+                previous = None;
+                continue;
+            }
+
+            previous = last_token(element).or(previous);
+        }
+
+        Doc::Concat(docs)
+    }
+
+    fn block(&self, node: &SyntaxNode) -> Doc {
+        let elements = significant_children(node);
+
+        let (Some(NodeOrToken::Token(open)), Some(NodeOrToken::Token(close))) =
+            (elements.first(), elements.last())
+        else {
+            return self.sequence(node);
+        };
+
+        let statements = &elements[1..elements.len() - 1];
+
+        // Blocks containing only an expression can stay on one line, except for function bodies.
+        let is_expr = statements.len() == 1
+            && matches!(&statements[0], NodeOrToken::Node(node) if !is_statement(node.kind()));
+
+        let parent = node.parent().map(|parent| parent.kind());
+        let is_function_body = parent == Some(SyntaxKind::FunctionItem);
+        let is_branch = matches!(parent, Some(SyntaxKind::IfExpr | SyntaxKind::IfStmt));
+
+        let has_comments = node.descendants_with_tokens().any(|element| {
+            matches!(
+                element.kind(),
+                SyntaxKind::LineComment | SyntaxKind::BlockComment
+            )
+        });
+
+        if is_expr && !is_function_body && !has_comments {
+            let doc = Doc::Concat(vec![
+                self.token(open),
+                Doc::indent(Doc::Concat(vec![Doc::Line, self.element(&statements[0])])),
+                Doc::Line,
+                self.token(close),
+            ]);
+
+            // Branches share the group of the if, so that they break together.
+            return if is_branch { doc } else { Doc::group(doc) };
+        }
+
+        self.list(ListStyle::Statements, open, statements, close)
+    }
+
+    fn list(
+        &self,
+        style: ListStyle,
+        open: &SyntaxToken,
+        elements: &[SyntaxElement],
+        close: &SyntaxToken,
+    ) -> Doc {
+        let items = split_items(style, elements);
+        let dangling = self.dangling(close);
+
+        if items.is_empty() && dangling.is_empty() {
+            return Doc::Concat(vec![self.token(open), self.token(close)]);
+        }
+
+        // The line inside of the delimiters, and the line between items.
+        let (padding, separator) = match style {
+            ListStyle::Statements | ListStyle::Vertical => (Doc::HardLine, Doc::HardLine),
+            ListStyle::Spaced => (Doc::Line, Doc::Line),
+            ListStyle::Tight => (Doc::SoftLine, Doc::Line),
+            ListStyle::Compact => (Doc::nil(), Doc::text(" ")),
+        };
+
+        let mut inner = Vec::new();
+
+        // Padded lists which were written across multiple lines are kept that way.
+        if style == ListStyle::Spaced
+            && items
+                .first()
+                .and_then(|(item, _)| item.first())
+                .is_some_and(|element| self.starts_line(element))
+        {
+            inner.push(Doc::BreakParent);
+        }
+
+        for (index, (item, comma)) in items.iter().enumerate() {
+            let is_last = index == items.len() - 1;
+
+            inner.push(if index == 0 {
+                padding.clone()
+            } else {
+                separator.clone()
+            });
+
+            // Blank lines between items are preserved, unless the list fits on one line.
+            if index > 0 && style != ListStyle::Compact && self.blank_line_before(&item[0]) {
+                inner.push(Doc::if_break(Doc::HardLine, Doc::nil()));
+            }
+
+            inner.push(self.sequence_of(item));
+
+            if style == ListStyle::Statements {
+                continue;
+            }
+
+            match comma {
+                Some(comma) if !is_last || style == ListStyle::Vertical => {
+                    inner.push(self.token(comma));
+                }
+                Some(comma) => {
+                    inner.push(self.leading(comma));
+                    inner.push(trailing_comma(style));
+                    inner.push(self.trailing(comma));
+                }
+                None if style == ListStyle::Vertical => inner.push(Doc::text(",")),
+                None => inner.push(trailing_comma(style)),
+            }
+        }
+
+        for comment in dangling {
+            inner.push(Doc::HardLine);
+            inner.push(Doc::text(&comment.text));
+        }
+
+        // Adjacent angle brackets would be lexed as a shift operator.
+        let last_token = items
+            .last()
+            .and_then(|(item, _)| item.last())
+            .and_then(last_token);
+
+        if close.kind() == SyntaxKind::GreaterThan
+            && last_token.is_some_and(|token| token.kind() == SyntaxKind::GreaterThan)
+        {
+            inner.push(if style == ListStyle::Compact {
+                Doc::text(" ")
+            } else {
+                Doc::if_break(Doc::nil(), Doc::text(" "))
+            });
+        }
+
+        Doc::group(Doc::Concat(vec![
+            self.token(open),
+            Doc::indent(Doc::Concat(inner)),
+            padding,
+            self.trailing_token(close),
+        ]))
+    }
+
+    /// Formats the elements of a single list item, which are usually a single node.
+    fn sequence_of(&self, elements: &[SyntaxElement]) -> Doc {
+        let mut docs = Vec::new();
+        let mut previous: Option<SyntaxToken> = None;
+
+        for element in elements {
+            if let (Some(previous), Some(first)) = (&previous, first_token(element)) {
+                if needs_space(previous, &first) {
+                    docs.push(Doc::text(" "));
+                }
+            }
+            docs.push(self.element(element));
+            previous = last_token(element).or(previous);
+        }
+
+        Doc::Concat(docs)
+    }
+
+    /// The token along with its comments.
+    fn token(&self, token: &SyntaxToken) -> Doc {
+        Doc::Concat(vec![self.leading(token), self.trailing_token(token)])
+    }
+
+    /// The token and its trailing comments, without the comments before it.
+    fn trailing_token(&self, token: &SyntaxToken) -> Doc {
+        Doc::Concat(vec![Doc::text(token.text()), self.trailing(token)])
+    }
+
+    fn leading(&self, token: &SyntaxToken) -> Doc {
+        let Some(trivia) = self.trivia.get(token) else {
+            return Doc::nil();
+        };
+
+        let mut docs = Vec::new();
+
+        for (index, comment) in trivia.leading.iter().enumerate() {
+            if index > 0 && comment.newlines_before >= 2 {
+                docs.push(Doc::HardLine);
+            }
+
+            docs.push(Doc::text(&comment.text));
+
+            if comment.is_line || comment.newline_after {
+                docs.push(Doc::HardLine);
+            } else {
+                docs.push(Doc::text(" "));
+            }
+        }
+
+        if !trivia.leading.is_empty() && trivia.newlines_before >= 2 {
+            docs.push(Doc::HardLine);
+        }
+
+        Doc::Concat(docs)
+    }
+
+    fn trailing(&self, token: &SyntaxToken) -> Doc {
+        let Some(trivia) = self.trivia.get(token) else {
+            return Doc::nil();
+        };
+
+        let mut docs = Vec::new();
+
+        for comment in &trivia.trailing {
+            if comment.is_line {
+                docs.push(Doc::LineSuffix(format!(" {}", comment.text)));
+                docs.push(Doc::BreakParent);
+            } else {
+                docs.push(Doc::text(format!(" {}", comment.text)));
+            }
+        }
+
+        Doc::Concat(docs)
+    }
+
+    /// Comments before a closing delimiter, which belong inside of the list.
+    fn dangling(&self, close: &SyntaxToken) -> &[Comment] {
+        self.trivia
+            .get(close)
+            .map_or(&[], |trivia| trivia.leading.as_slice())
+    }
+
+    fn starts_line(&self, element: &SyntaxElement) -> bool {
+        first_token(element)
+            .and_then(|token| self.trivia.get(&token))
+            .is_some_and(|trivia| {
+                trivia
+                    .leading
+                    .first()
+                    .map_or(trivia.newlines_before, |comment| comment.newlines_before)
+                    > 0
+            })
+    }
+
+    fn blank_line_before(&self, element: &SyntaxElement) -> bool {
+        first_token(element).is_some_and(|token| self.trivia.blank_line_before(&token))
+    }
+}
+
+/// Lists which are broken onto multiple lines have a trailing comma, and those that aren't don't.
+fn trailing_comma(style: ListStyle) -> Doc {
+    if style == ListStyle::Compact {
+        Doc::nil()
+    } else {
+        Doc::if_break(Doc::text(","), Doc::nil())
+    }
+}
+
+/// Splits the elements of a list into items, along with the comma after each one.
+fn split_items(
+    style: ListStyle,
+    elements: &[SyntaxElement],
+) -> Vec<(Vec<SyntaxElement>, Option<SyntaxToken>)> {
+    if style == ListStyle::Statements {
+        return elements
+            .iter()
+            .map(|element| (vec![element.clone()], None))
+            .collect();
+    }
+
+    let mut items = Vec::new();
+    let mut current = Vec::new();
+
+    for element in elements {
+        match element {
+            NodeOrToken::Token(token) if token.kind() == SyntaxKind::Comma => {
+                items.push((std::mem::take(&mut current), Some(token.clone())));
+            }
+            _ => current.push(element.clone()),
+        }
+    }
+
+    if !current.is_empty() {
+        items.push((current, None));
+    }
+
+    items
+}
+
+fn is_statement(kind: SyntaxKind) -> bool {
+    matches!(
+        kind,
+        SyntaxKind::LetStmt
+            | SyntaxKind::IfStmt
+            | SyntaxKind::ReturnStmt
+            | SyntaxKind::RaiseStmt
+            | SyntaxKind::AssertStmt
+            | SyntaxKind::AssumeStmt
+            | SyntaxKind::ModuleItem
+            | SyntaxKind::FunctionItem
+            | SyntaxKind::TypeAliasItem
+            | SyntaxKind::StructItem
+            | SyntaxKind::EnumItem
+            | SyntaxKind::ConstItem
+            | SyntaxKind::ImportItem
+    )
+}
+
+fn is_trivia(kind: SyntaxKind) -> bool {
+    matches!(
+        kind,
+        SyntaxKind::Whitespace | SyntaxKind::LineComment | SyntaxKind::BlockComment
+    )
+}
+
+fn significant_children(node: &SyntaxNode) -> Vec<SyntaxElement> {
+    node.children_with_tokens()
+        .filter(|element| !is_trivia(element.kind()))
+        .collect()
+}
+
+fn first_token(element: &SyntaxElement) -> Option<SyntaxToken> {
+    match element {
+        NodeOrToken::Node(node) => node
+            .descendants_with_tokens()
+            .filter_map(NodeOrToken::into_token)
+            .find(|token| !is_trivia(token.kind())),
+        NodeOrToken::Token(token) => Some(token.clone()),
+    }
+}
+
+fn last_token(element: &SyntaxElement) -> Option<SyntaxToken> {
+    match element {
+        NodeOrToken::Node(node) => node
+            .descendants_with_tokens()
+            .filter_map(NodeOrToken::into_token)
+            .filter(|token| !is_trivia(token.kind()))
+            .last(),
+        NodeOrToken::Token(token) => Some(token.clone()),
+    }
+}
+
+/// Whether there should be a space between two adjacent tokens.
+fn needs_space(previous: &SyntaxToken, next: &SyntaxToken) -> bool {
+    use SyntaxKind as S;
+
+    let parent = |token: &SyntaxToken| token.parent().map(|parent| parent.kind());
+    let is_operator = |token: &SyntaxToken| parent(token) == Some(S::BinaryExpr);
+
+    match previous.kind() {
+        S::OpenParen | S::OpenBracket | S::Dot | S::PathSeparator | S::Spread | S::Pound => {
+            return false;
+        }
+        S::LessThan if !is_operator(previous) => return false,
+        S::Not | S::Minus | S::Plus | S::BitwiseNot if parent(previous) == Some(S::PrefixExpr) => {
+            return false;
+        }
+        _ => {}
+    }
+
+    match next.kind() {
+        S::CloseParen
+        | S::CloseBracket
+        | S::Comma
+        | S::Semicolon
+        | S::Dot
+        | S::PathSeparator
+        | S::Colon => false,
+        // Adjacent angle brackets would be lexed as a shift operator.
+        S::GreaterThan if !is_operator(next) => previous.kind() == S::GreaterThan,
+        S::LessThan if !is_operator(next) => false,
+        S::OpenParen => !matches!(
+            parent(next),
+            Some(
+                S::FunctionItem
+                    | S::LambdaExpr
+                    | S::FunctionType
+                    | S::FunctionCallExpr
+                    | S::ClvmExpr
+                    | S::Attribute
+            )
+        ),
+        _ => true,
+    }
+}
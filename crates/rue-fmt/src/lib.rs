@@ -0,0 +1,271 @@
+mod doc;
+mod formatter;
+mod trivia;
+
+use formatter::Formatter;
+use rue_parser::{parse, AstNode, ParserError};
+
+/// The maximum width of a line, before lists are broken onto multiple lines.
+pub const LINE_WIDTH: usize = 100;
+
+/// Formats the source code, preserving comments.
+/// Source code with syntax errors can't be formatted, so the errors are returned instead.
+pub fn format(source: &str) -> Result<String, Vec<ParserError>> {
+    let (root, errors) = parse(source);
+
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+
+    let doc = Formatter::new(root.syntax()).root(root.syntax());
+
+    Ok(doc::print(&doc, LINE_WIDTH))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, path::Path};
+
+    use indoc::indoc;
+    use rue_parser::SyntaxKind;
+
+    use super::*;
+
+    fn check(source: &str, expected: &str) {
+        let formatted = format(source).unwrap();
+        assert_eq!(formatted, expected);
+        assert_eq!(format(&formatted).unwrap(), formatted, "not idempotent");
+    }
+
+    #[test]
+    fn test_items() {
+        check(
+            indoc! {"
+                import std::{ list::concat,list::map};
+                export   fun main ( a:Int , b : Int ,) ->Int{a+b}
+                type Pair<T> =(T,T);
+                const  X :Int=42;
+                mod inner { export inline fun f<T>(...items:List<T>)->List<T>{items} }
+            "},
+            indoc! {"
+                import std::{list::concat, list::map};
+                export fun main(a: Int, b: Int) -> Int {
+                    a + b
+                }
+                type Pair<T> = (T, T);
+                const X: Int = 42;
+                mod inner {
+                    export inline fun f<T>(...items: List<T>) -> List<T> {
+                        items
+                    }
+                }
+            "},
+        );
+    }
+
+    #[test]
+    fn test_struct_and_enum() {
+        check(
+            indoc! {"
+                struct Point { x: Int, y: Int }
+                enum Value { A = 1 { num: Int, }, B = 2, C }
+                struct Empty {}
+            "},
+            indoc! {"
+                struct Point {
+                    x: Int,
+                    y: Int,
+                }
+                enum Value {
+                    A = 1 { num: Int },
+                    B = 2,
+                    C,
+                }
+                struct Empty {}
+            "},
+        );
+    }
+
+    #[test]
+    fn test_blocks() {
+        check(
+            indoc! {"
+                fun main() -> Int {
+                    let a=if true {1} else {2};
+                    let f = fun(x) => {x};
+                    if a > 1 { return a; }
+                    assert a == 1;
+                    f(-a)
+                }
+            "},
+            indoc! {"
+                fun main() -> Int {
+                    let a = if true { 1 } else { 2 };
+                    let f = fun(x) => { x };
+                    if a > 1 {
+                        return a;
+                    }
+                    assert a == 1;
+                    f(-a)
+                }
+            "},
+        );
+    }
+
+    #[test]
+    fn test_long_lists() {
+        check(
+            indoc! {"
+                fun main() -> Int {
+                    let value = function_with_a_long_name(first_argument, second_argument, third_argument, 42, 43, 44);
+                    let point = Point { x: some_long_expression_value, y: another_much_longer_expression_value, z: 10000 };
+                    [value, point.x]
+                }
+            "},
+            indoc! {"
+                fun main() -> Int {
+                    let value = function_with_a_long_name(
+                        first_argument,
+                        second_argument,
+                        third_argument,
+                        42,
+                        43,
+                        44,
+                    );
+                    let point = Point {
+                        x: some_long_expression_value,
+                        y: another_much_longer_expression_value,
+                        z: 10000,
+                    };
+                    [value, point.x]
+                }
+            "},
+        );
+    }
+
+    #[test]
+    fn test_comments() {
+        check(
+            indoc! {"
+                // Header comment.
+
+                fun main() -> Int { // Trailing.
+                    // Leading.
+                    let a = 1; /* block */
+
+
+                    let b = foo(a, // Argument.
+                        2);
+                    b
+                    // Dangling.
+                }
+                // End of file.
+            "},
+            indoc! {"
+                // Header comment.
+
+                fun main() -> Int { // Trailing.
+                    // Leading.
+                    let a = 1; /* block */
+
+                    let b = foo(
+                        a, // Argument.
+                        2,
+                    );
+                    b
+                    // Dangling.
+                }
+                // End of file.
+            "},
+        );
+    }
+
+    #[test]
+    fn test_attributes() {
+        check(
+            indoc! {"
+                # [ allow ( unused_parameter ,unused_let ) ] #[deny(warnings)] export fun main(a: Int) -> Int { 0 }
+                mod inner {
+                    // Comments stay before the attribute.
+                    #[allow(unused_function)]
+                    fun f() -> Int { 1 }
+                }
+            "},
+            indoc! {"
+                #[allow(unused_parameter, unused_let)]
+                #[deny(warnings)]
+                export fun main(a: Int) -> Int {
+                    0
+                }
+                mod inner {
+                    // Comments stay before the attribute.
+                    #[allow(unused_function)]
+                    fun f() -> Int {
+                        1
+                    }
+                }
+            "},
+        );
+    }
+
+    #[test]
+    fn test_syntax_errors() {
+        assert!(format("fun main( -> Int {}").is_err());
+    }
+
+    /// Every program in the repository should keep the same tokens, and be stable once formatted.
+    #[test]
+    fn test_preserves_tokens() {
+        let manifest_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+        let mut paths = vec![manifest_dir.join("../rue-compiler/stdlib.rue")];
+
+        for dir in ["../../tests", "../../examples"] {
+            collect_files(&manifest_dir.join(dir), &mut paths);
+        }
+
+        for path in paths {
+            let source = fs::read_to_string(&path).unwrap();
+
+            let Ok(formatted) = format(&source) else {
+                continue;
+            };
+
+            assert_eq!(
+                tokens(&source),
+                tokens(&formatted),
+                "tokens changed in {}",
+                path.display()
+            );
+            assert_eq!(
+                format(&formatted).unwrap(),
+                formatted,
+                "not idempotent in {}",
+                path.display()
+            );
+        }
+    }
+
+    fn collect_files(dir: &Path, paths: &mut Vec<std::path::PathBuf>) {
+        for entry in fs::read_dir(dir).unwrap() {
+            let path = entry.unwrap().path();
+
+            if path.is_dir() {
+                collect_files(&path, paths);
+            } else if path.extension().is_some_and(|extension| extension == "rue") {
+                paths.push(path);
+            }
+        }
+    }
+
+    /// The tokens and comments, ignoring commas since trailing commas are added or removed.
+    fn tokens(source: &str) -> Vec<String> {
+        parse(source)
+            .0
+            .syntax()
+            .descendants_with_tokens()
+            .filter_map(rowan::NodeOrToken::into_token)
+            .filter(|token| !matches!(token.kind(), SyntaxKind::Whitespace | SyntaxKind::Comma))
+            .map(|token| token.text().trim_end().to_string())
+            .collect()
+    }
+}
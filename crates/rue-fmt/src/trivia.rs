@@ -0,0 +1,112 @@
+use std::collections::HashMap;
+
+use rowan::TextSize;
+use rue_parser::{SyntaxKind, SyntaxNode, SyntaxToken};
+
+/// A comment from the original source, which must be preserved.
+#[derive(Debug, Clone)]
+pub struct Comment {
+    pub text: String,
+    pub is_line: bool,
+    /// The number of line breaks between the previous token or comment and this one.
+    pub newlines_before: usize,
+    /// Whether the next token or comment starts on a new line.
+    pub newline_after: bool,
+}
+
+/// The comments and line breaks surrounding a significant token.
+#[derive(Debug, Default, Clone)]
+pub struct TokenTrivia {
+    /// Comments on their own lines, or otherwise before the token.
+    pub leading: Vec<Comment>,
+    /// Comments after the token, on the same line.
+    pub trailing: Vec<Comment>,
+    /// The number of line breaks between the last leading comment (or previous token) and this token.
+    pub newlines_before: usize,
+}
+
+/// Comments and line breaks can appear anywhere in the syntax tree, so they are attached to
+/// the nearest significant token instead. This way, every token carries its own comments.
+#[derive(Debug, Default, Clone)]
+pub struct Trivia {
+    tokens: HashMap<TextSize, TokenTrivia>,
+    /// Comments after the last token in the file.
+    pub end: Vec<Comment>,
+}
+
+impl Trivia {
+    pub fn new(root: &SyntaxNode) -> Self {
+        let mut trivia = Self::default();
+        let mut previous: Option<TextSize> = None;
+        let mut pending: Vec<Comment> = Vec::new();
+        let mut newlines = 0;
+
+        for token in root
+            .descendants_with_tokens()
+            .filter_map(rowan::NodeOrToken::into_token)
+        {
+            match token.kind() {
+                SyntaxKind::Whitespace => {
+                    let count = token.text().matches('\n').count();
+                    newlines += count;
+
+                    if count > 0 {
+                        if let Some(comment) = pending.last_mut() {
+                            comment.newline_after = true;
+                        }
+                    }
+                }
+                SyntaxKind::LineComment | SyntaxKind::BlockComment => {
+                    // Line comments include the line break which ends them.
+                    let is_line = token.kind() == SyntaxKind::LineComment;
+                    let ends_line = is_line && token.text().ends_with('\n');
+
+                    let comment = Comment {
+                        text: token.text().trim_end().to_string(),
+                        is_line,
+                        newlines_before: newlines,
+                        newline_after: ends_line,
+                    };
+
+                    match previous {
+                        Some(previous) if newlines == 0 && pending.is_empty() => {
+                            trivia.entry(previous).trailing.push(comment);
+                        }
+                        _ => pending.push(comment),
+                    }
+
+                    newlines = usize::from(ends_line);
+                }
+                _ => {
+                    let entry = trivia.entry(token.text_range().start());
+                    entry.leading = std::mem::take(&mut pending);
+                    entry.newlines_before = newlines;
+                    newlines = 0;
+                    previous = Some(token.text_range().start());
+                }
+            }
+        }
+
+        trivia.end = pending;
+        trivia
+    }
+
+    fn entry(&mut self, offset: TextSize) -> &mut TokenTrivia {
+        self.tokens.entry(offset).or_default()
+    }
+
+    pub fn get(&self, token: &SyntaxToken) -> Option<&TokenTrivia> {
+        self.tokens.get(&token.text_range().start())
+    }
+
+    /// Whether there is an empty line before the token, or before the comments leading up to it.
+    pub fn blank_line_before(&self, token: &SyntaxToken) -> bool {
+        self.get(token).is_some_and(|trivia| {
+            trivia
+                .leading
+                .first()
+                .map_or(trivia.newlines_before, |comment| comment.newlines_before)
+                >= 2
+        })
+    }
+}
@@ -106,6 +106,7 @@ impl<'a> Lexer<'a> {
             '^' => TokenKind::BitwiseXor,
             '~' => TokenKind::BitwiseNot,
             '?' => TokenKind::Question,
+            '#' => TokenKind::Pound,
             '.' => match self.peek() {
                 '.' if self.peek_nth(1) == '.' => {
                     self.bump();
@@ -145,6 +146,7 @@ impl<'a> Lexer<'a> {
                     "raise" => TokenKind::Raise,
                     "assert" => TokenKind::Assert,
                     "assume" => TokenKind::Assume,
+                    "clvm" => TokenKind::Clvm,
                     "nil" => TokenKind::Nil,
                     "true" => TokenKind::True,
                     "false" => TokenKind::False,
@@ -217,20 +219,16 @@ impl<'a> Lexer<'a> {
         let mut depth = 1;
         let is_terminated = loop {
             match self.bump() {
-                '*' => {
-                    if self.peek() == '/' {
-                        self.bump();
-                        depth -= 1;
-                        if depth == 0 {
-                            break true;
-                        }
+                '*' if self.peek() == '/' => {
+                    self.bump();
+                    depth -= 1;
+                    if depth == 0 {
+                        break true;
                     }
                 }
-                '/' => {
-                    if self.peek() == '*' {
-                        self.bump();
-                        depth += 1;
-                    }
+                '/' if self.peek() == '*' => {
+                    self.bump();
+                    depth += 1;
                 }
                 '\0' => break false,
                 _ => {}
@@ -258,7 +256,7 @@ impl<'a> Lexer<'a> {
     }
 }
 
-impl<'a> Iterator for Lexer<'a> {
+impl Iterator for Lexer<'_> {
     type Item = Token;
 
     fn next(&mut self) -> Option<Self::Item> {
@@ -350,6 +348,7 @@ mod tests {
         check("raise", &[TokenKind::Raise]);
         check("assert", &[TokenKind::Assert]);
         check("assume", &[TokenKind::Assume]);
+        check("clvm", &[TokenKind::Clvm]);
         check("nil", &[TokenKind::Nil]);
         check("true", &[TokenKind::True]);
         check("false", &[TokenKind::False]);
@@ -379,6 +378,7 @@ mod tests {
         check("=>", &[TokenKind::FatArrow]);
         check("...", &[TokenKind::Spread]);
         check("?", &[TokenKind::Question]);
+        check("#", &[TokenKind::Pound]);
     }
 
     #[test]
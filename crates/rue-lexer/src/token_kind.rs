@@ -26,6 +26,7 @@ pub enum TokenKind {
     Raise,
     Assert,
     Assume,
+    Clvm,
     Nil,
     True,
     False,
@@ -40,6 +41,7 @@ pub enum TokenKind {
     FatArrow,
     Spread,
     Question,
+    Pound,
     Plus,
     Minus,
     Star,
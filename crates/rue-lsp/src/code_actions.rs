@@ -0,0 +1,290 @@
+use std::collections::HashMap;
+use std::ops::Range;
+
+use rowan::TextRange;
+use rue_compiler::{trimmed_range, DiagnosticData, DiagnosticKind, ErrorKind, WarningKind};
+use rue_parser::{AstNode, Item};
+use tower_lsp::lsp_types::{
+    CodeAction, CodeActionKind, CodeActionOrCommand, TextEdit, Url, WorkspaceEdit,
+};
+
+use crate::{compiler_diagnostic, document::Document, text_range};
+
+/// Quick fixes for the diagnostics which overlap the range.
+pub fn code_actions(document: &Document, uri: &Url, range: TextRange) -> Vec<CodeActionOrCommand> {
+    let mut actions = Vec::new();
+
+    for error in document.analysis.diagnostics() {
+        let span = text_range(error.span());
+
+        if span.intersect(range).is_none() {
+            continue;
+        }
+
+        let mut fixes = match (error.kind(), error.data()) {
+            (
+                DiagnosticKind::Warning(
+                    WarningKind::UnusedLet(name) | WarningKind::UnusedFunction(name),
+                ),
+                data,
+            ) => {
+                let mut fixes = Vec::new();
+
+                if let Some(DiagnosticData::Remove(removed)) = data {
+                    fixes.push((format!("Remove `{name}`"), edit(removed, "")));
+                }
+
+                fixes.push((
+                    format!("Prefix `{name}` with an underscore"),
+                    edit(&(span.start().into()..span.start().into()), "_"),
+                ));
+
+                fixes
+            }
+            (
+                DiagnosticKind::Warning(WarningKind::UnnecessaryTypeCheck(..)),
+                Some(DiagnosticData::Remove(removed)),
+            ) => vec![(
+                "Remove the unnecessary type check".to_string(),
+                edit(removed, ""),
+            )],
+            (
+                DiagnosticKind::Warning(WarningKind::UnnecessaryTypeCheck(..)),
+                Some(DiagnosticData::Replace(replaced, text)),
+            ) => vec![(
+                format!("Replace the unnecessary type check with `{text}`"),
+                edit(replaced, text),
+            )],
+            (
+                DiagnosticKind::Error(ErrorKind::MissingInitializerFields(..)),
+                Some(DiagnosticData::MissingFields {
+                    offset,
+                    needs_comma,
+                    fields,
+                }),
+            ) => {
+                let fields: Vec<String> = fields
+                    .iter()
+                    .map(|(name, ty)| format!("{name}: {}", placeholder(ty)))
+                    .collect();
+
+                let separator = if *needs_comma { ", " } else { " " };
+
+                vec![(
+                    "Add the missing fields".to_string(),
+                    edit(
+                        &(*offset..*offset),
+                        &format!("{separator}{}", fields.join(", ")),
+                    ),
+                )]
+            }
+            (
+                DiagnosticKind::Error(ErrorKind::UnknownSymbol(..)),
+                Some(DiagnosticData::Imports(paths)),
+            ) => paths
+                .iter()
+                .map(|path| (format!("Import `{path}`"), import_edit(document, path)))
+                .collect(),
+            _ => Vec::new(),
+        };
+
+        if let Some(suggestion) = error.suggestion() {
+            fixes.insert(
+                0,
+                (
+                    format!("Change to `{suggestion}`"),
+                    edit(error.span(), suggestion),
+                ),
+            );
+        }
+
+        for (title, edit) in fixes {
+            actions.push(CodeActionOrCommand::CodeAction(CodeAction {
+                title,
+                kind: Some(CodeActionKind::QUICKFIX),
+                diagnostics: Some(vec![compiler_diagnostic(document, error)]),
+                edit: Some(WorkspaceEdit {
+                    changes: Some(HashMap::from([(
+                        uri.clone(),
+                        vec![TextEdit {
+                            range: document.range(text_range(&edit.0)),
+                            new_text: edit.1,
+                        }],
+                    )])),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            }));
+        }
+    }
+
+    actions
+}
+
+fn edit(span: &Range<usize>, text: &str) -> (Range<usize>, String) {
+    (span.clone(), text.to_string())
+}
+
+/// Imports are added before any existing imports, or otherwise before the first item.
+fn import_edit(document: &Document, path: &str) -> (Range<usize>, String) {
+    let items = document.root.items();
+
+    let import = items
+        .iter()
+        .find(|item| matches!(item, Item::ImportItem(..)));
+
+    let (item, text) = match import {
+        Some(item) => (Some(item), format!("import {path};\n")),
+        None => (items.first(), format!("import {path};\n\n")),
+    };
+
+    let offset = item.map_or(0, |item| trimmed_range(item.syntax()).start().into());
+
+    (offset..offset, text)
+}
+
+/// A value of the given type which can be used until the field is filled in.
+fn placeholder(ty: &str) -> &'static str {
+    match ty {
+        "Int" => "0",
+        "Bool" => "false",
+        "Bytes" => "\"\"",
+        _ => "nil",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use indoc::indoc;
+    use rowan::TextSize;
+
+    use crate::document::{apply_edits, test_document};
+
+    use super::*;
+
+    /// Applies the quick fix with the given title, from the actions for the whole document.
+    fn fix(source: &str, title: &str) -> String {
+        let document = test_document(source);
+        let uri = Url::parse("file:///main.rue").unwrap();
+        let range = TextRange::up_to(TextSize::of(source));
+
+        let actions = code_actions(&document, &uri, range);
+
+        let action = actions
+            .iter()
+            .find_map(|action| match action {
+                CodeActionOrCommand::CodeAction(action) if action.title == title => Some(action),
+                _ => None,
+            })
+            .unwrap_or_else(|| panic!("missing code action `{title}`"));
+
+        let changes = action.edit.as_ref().unwrap().changes.as_ref().unwrap();
+        apply_edits(&document, &changes[&uri])
+    }
+
+    #[test]
+    fn test_remove_unused_let() {
+        let source = indoc! {"
+            fun main() -> Int {
+                // Keep me.
+                let value = 42; // Unused.
+                let other = 34;
+                other
+            }
+        "};
+
+        assert_eq!(
+            fix(source, "Remove `value`"),
+            indoc! {"
+                fun main() -> Int {
+                    // Keep me.
+                    // Unused.
+                    let other = 34;
+                    other
+                }
+            "}
+        );
+    }
+
+    #[test]
+    fn test_prefix_unused_let() {
+        let source = indoc! {"
+            fun main() -> Int {
+                let value = 42;
+                0
+            }
+        "};
+
+        assert_eq!(
+            fix(source, "Prefix `value` with an underscore"),
+            indoc! {"
+                fun main() -> Int {
+                    let _value = 42;
+                    0
+                }
+            "}
+        );
+    }
+
+    #[test]
+    fn test_add_missing_fields() {
+        let source = indoc! {"
+            struct Point {
+                x: Int,
+                y: Int,
+                label: Bytes,
+            }
+
+            fun main() -> Point {
+                Point { x: 1 }
+            }
+        "};
+
+        assert_eq!(
+            fix(source, "Add the missing fields"),
+            indoc! {"
+                struct Point {
+                    x: Int,
+                    y: Int,
+                    label: Bytes,
+                }
+
+                fun main() -> Point {
+                    Point { x: 1, y: 0, label: \"\" }
+                }
+            "}
+        );
+    }
+
+    #[test]
+    fn test_import_unknown_symbol() {
+        let source = indoc! {"
+            fun main() -> Int {
+                double(21)
+            }
+
+            mod math {
+                export fun double(value: Int) -> Int {
+                    value * 2
+                }
+            }
+        "};
+
+        assert_eq!(
+            fix(source, "Import `math::double`"),
+            indoc! {"
+                import math::double;
+
+                fun main() -> Int {
+                    double(21)
+                }
+
+                mod math {
+                    export fun double(value: Int) -> Int {
+                        value * 2
+                    }
+                }
+            "}
+        );
+    }
+}
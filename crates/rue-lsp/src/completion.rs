@@ -0,0 +1,276 @@
+use rue_compiler::{Resolution, Symbol, SymbolId};
+use rue_parser::{AstNode, SyntaxKind, SyntaxToken};
+use rue_typing::{Type, TypeId};
+use tower_lsp::lsp_types::{CompletionItem, CompletionItemKind, CompletionResponse, Position};
+
+use crate::document::Document;
+
+const KEYWORDS: &[&str] = &[
+    "mod", "fun", "inline", "import", "export", "type", "struct", "enum", "let", "const", "if",
+    "else", "return", "raise", "assert", "assume", "clvm", "nil", "true", "false", "as", "is",
+];
+
+pub fn completion(document: &Document, position: Position) -> Option<CompletionResponse> {
+    let offset = document.offset(position)?;
+
+    let mut token = document
+        .root
+        .syntax()
+        .token_at_offset(offset)
+        .left_biased()?;
+
+    // The name that is currently being typed is replaced by the completion.
+    if token.kind() == SyntaxKind::Ident {
+        if let Some(prev) = token.prev_token() {
+            token = prev;
+        }
+    }
+
+    let items = match token.kind() {
+        SyntaxKind::Dot => field_completions(document, &token)?,
+        SyntaxKind::PathSeparator => path_completions(document, &token)?,
+        _ => scope_completions(document, offset),
+    };
+
+    Some(CompletionResponse::Array(items))
+}
+
+/// The fields of the receiver's type, which can be accessed after a `.`.
+fn field_completions(document: &Document, dot: &SyntaxToken) -> Option<Vec<CompletionItem>> {
+    let field_access = dot.parent()?;
+    let receiver = field_access.children().next()?;
+    let type_id = document.analysis.db.expr_type(&receiver)?;
+    let ty = &document.analysis.ty;
+
+    let fields: Vec<(String, TypeId)> = match ty.get(type_id) {
+        Type::Struct(..) | Type::Variant(..) => document.fields(type_id),
+        Type::Pair(first, rest) => vec![("first".to_string(), *first), ("rest".to_string(), *rest)],
+        Type::Bytes | Type::Bytes32 => vec![("length".to_string(), ty.std().int)],
+        _ => Vec::new(),
+    };
+
+    Some(
+        fields
+            .into_iter()
+            .map(|(name, type_id)| {
+                item(name, CompletionItemKind::FIELD, document.type_name(type_id))
+            })
+            .collect(),
+    )
+}
+
+/// The variants of an enum, or the exported members of a module, after a `::`.
+fn path_completions(document: &Document, separator: &SyntaxToken) -> Option<Vec<CompletionItem>> {
+    let mut current = separator.prev_token();
+
+    while let Some(token) = &current {
+        if token.kind() == SyntaxKind::Ident {
+            break;
+        }
+        current = token.prev_token();
+    }
+
+    let name = current?;
+    let db = &document.analysis.db;
+
+    match document.resolve(&name)? {
+        Resolution::Type(type_id) => {
+            let Type::Enum(enum_type) = document.analysis.ty.get(type_id) else {
+                return None;
+            };
+
+            Some(
+                enum_type
+                    .variants
+                    .iter()
+                    .map(|(name, &variant_type_id)| {
+                        item(
+                            name.clone(),
+                            CompletionItemKind::ENUM_MEMBER,
+                            document.describe(Resolution::Type(variant_type_id), name),
+                        )
+                    })
+                    .collect(),
+            )
+        }
+        Resolution::Field(..) => None,
+        Resolution::Symbol(symbol_id) => {
+            let Symbol::Module(module) = db.symbol(symbol_id) else {
+                return None;
+            };
+            let scope = db.scope(module.scope_id);
+
+            let mut items = Vec::new();
+
+            for (name, symbol_id) in scope.named_symbols() {
+                if module.exported_symbols.contains(&symbol_id) {
+                    items.push(symbol_item(document, name, symbol_id));
+                }
+            }
+
+            for (name, type_id) in scope.named_types() {
+                if module.exported_types.contains(&type_id) {
+                    items.push(type_item(document, name, type_id));
+                }
+            }
+
+            Some(items)
+        }
+    }
+}
+
+/// Every symbol and type which is visible at the offset, along with the keywords.
+fn scope_completions(document: &Document, offset: rowan::TextSize) -> Vec<CompletionItem> {
+    let db = &document.analysis.db;
+
+    let mut items = Vec::new();
+    let mut names = Vec::new();
+
+    // Inner scopes shadow outer scopes, so they are visited first.
+    for &scope_id in document.visible_scopes(offset).iter().rev() {
+        let scope = db.scope(scope_id);
+
+        for (name, symbol_id) in scope.named_symbols() {
+            if names.contains(&(name, false)) {
+                continue;
+            }
+            names.push((name, false));
+            items.push(symbol_item(document, name, symbol_id));
+        }
+
+        for (name, type_id) in scope.named_types() {
+            if names.contains(&(name, true)) {
+                continue;
+            }
+            names.push((name, true));
+            items.push(type_item(document, name, type_id));
+        }
+    }
+
+    for keyword in KEYWORDS {
+        items.push(CompletionItem {
+            label: (*keyword).to_string(),
+            kind: Some(CompletionItemKind::KEYWORD),
+            ..Default::default()
+        });
+    }
+
+    items
+}
+
+fn symbol_item(document: &Document, name: &str, symbol_id: SymbolId) -> CompletionItem {
+    let kind = match document.analysis.db.symbol(symbol_id) {
+        Symbol::Unknown => CompletionItemKind::TEXT,
+        Symbol::Module(..) => CompletionItemKind::MODULE,
+        Symbol::Function(..) | Symbol::InlineFunction(..) => CompletionItemKind::FUNCTION,
+        Symbol::Parameter(..) | Symbol::Let(..) => CompletionItemKind::VARIABLE,
+        Symbol::Const(..) | Symbol::InlineConst(..) => CompletionItemKind::CONSTANT,
+    };
+
+    item(
+        name.to_string(),
+        kind,
+        document.describe(Resolution::Symbol(symbol_id), name),
+    )
+}
+
+fn type_item(document: &Document, name: &str, type_id: TypeId) -> CompletionItem {
+    let kind = match document.analysis.ty.get_raw(type_id) {
+        Type::Struct(..) => CompletionItemKind::STRUCT,
+        Type::Enum(..) => CompletionItemKind::ENUM,
+        Type::Variant(..) => CompletionItemKind::ENUM_MEMBER,
+        Type::Generic => CompletionItemKind::TYPE_PARAMETER,
+        _ => CompletionItemKind::CLASS,
+    };
+
+    item(
+        name.to_string(),
+        kind,
+        document.describe(Resolution::Type(type_id), name),
+    )
+}
+
+fn item(label: String, kind: CompletionItemKind, detail: String) -> CompletionItem {
+    CompletionItem {
+        label,
+        kind: Some(kind),
+        detail: Some(detail),
+        ..Default::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use indoc::indoc;
+
+    use crate::document::{position_of, test_document};
+
+    use super::*;
+
+    /// The labels of the completions at the end of the first occurrence of the text.
+    fn labels(source: &str, needle: &str) -> Vec<String> {
+        let document = test_document(source);
+        let position = position_of(&document, needle, needle.len());
+
+        let Some(CompletionResponse::Array(items)) = completion(&document, position) else {
+            panic!("expected a list of completions");
+        };
+
+        items.into_iter().map(|item| item.label).collect()
+    }
+
+    #[test]
+    fn test_field_completions() {
+        let source = indoc! {"
+            struct Point {
+                x: Int,
+                y: Int,
+            }
+
+            fun main(point: Point) -> Int {
+                point.
+            }
+        "};
+
+        assert_eq!(labels(source, "point."), ["x", "y"]);
+    }
+
+    #[test]
+    fn test_enum_variant_completions() {
+        let source = indoc! {"
+            enum Color {
+                Red,
+                Green,
+            }
+
+            fun main() -> Color {
+                Color::
+            }
+        "};
+
+        let labels = labels(source, "Color::");
+        assert_eq!(labels.len(), 2);
+        assert!(labels.contains(&"Red".to_string()));
+        assert!(labels.contains(&"Green".to_string()));
+    }
+
+    #[test]
+    fn test_scope_completions() {
+        let source = indoc! {"
+            fun main(amount: Int) -> Int {
+                let doubled = amount * 2;
+                valu
+                let later = 1;
+                later
+            }
+        "};
+
+        let labels = labels(source, "valu");
+
+        assert!(labels.contains(&"doubled".to_string()));
+        assert!(labels.contains(&"amount".to_string()));
+        assert!(labels.contains(&"main".to_string()));
+        assert!(labels.contains(&"let".to_string()));
+        assert!(!labels.contains(&"later".to_string()));
+    }
+}
@@ -0,0 +1,486 @@
+use std::{env, fs, sync::OnceLock};
+
+use rowan::{TextRange, TextSize, TokenAtOffset};
+use rue_compiler::{
+    analyze_with_stdlib, Analysis, CompiledStandardLibrary, LintConfig, Resolution, ScopeId,
+    Symbol, STANDARD_LIBRARY,
+};
+use rue_parser::{AstNode, ParserError, Root, SyntaxKind, SyntaxToken};
+use rue_typing::{HashMap, Type, TypeId};
+use tower_lsp::lsp_types::{Location, Position, Range, Url};
+
+use crate::line_index::LineIndex;
+
+/// A parsed and analyzed document, which can be queried by the language features.
+pub struct Document {
+    pub text: String,
+    pub root: Root,
+    pub parser_errors: Vec<ParserError>,
+    pub analysis: Analysis,
+    pub line_index: LineIndex,
+}
+
+/// The stringified signature of a function type.
+#[derive(Debug, Clone)]
+pub struct Signature {
+    pub parameters: Vec<(String, String)>,
+    pub return_type: String,
+    /// The last parameter is a spread parameter if the parameters aren't nil terminated.
+    pub spread: bool,
+}
+
+impl Document {
+    /// Analyzes a syntax tree which has already been parsed from the text.
+    pub fn new(
+        text: String,
+        root: Root,
+        parser_errors: Vec<ParserError>,
+        stdlib: &CompiledStandardLibrary,
+        lints: &LintConfig,
+    ) -> Self {
+        let analysis = analyze_with_stdlib(&root, stdlib, lints);
+        let line_index = LineIndex::new(&text);
+
+        Self {
+            text,
+            root,
+            parser_errors,
+            analysis,
+            line_index,
+        }
+    }
+
+    pub fn offset(&self, position: Position) -> Option<TextSize> {
+        TextSize::try_from(self.line_index.offset(&self.text, position)?).ok()
+    }
+
+    pub fn range(&self, text_range: TextRange) -> Range {
+        self.line_index.range(&self.text, text_range)
+    }
+
+    /// The identifier at the given position, preferring the one to the left of the cursor.
+    pub fn ident_at(&self, position: Position) -> Option<SyntaxToken> {
+        let offset = self.offset(position)?;
+
+        let is_ident = |token: &SyntaxToken| token.kind() == SyntaxKind::Ident;
+
+        match self.root.syntax().token_at_offset(offset) {
+            TokenAtOffset::None => None,
+            TokenAtOffset::Single(token) => Some(token).filter(is_ident),
+            TokenAtOffset::Between(left, right) => {
+                Some(left).filter(is_ident).or(Some(right).filter(is_ident))
+            }
+        }
+    }
+
+    /// Whether the token is part of this document, rather than the standard library.
+    pub fn is_local(&self, token: &SyntaxToken) -> bool {
+        token
+            .parent_ancestors()
+            .last()
+            .is_some_and(|root| &root == self.root.syntax())
+    }
+
+    /// Finds what the token refers to, whether it's a reference or the definition itself.
+    pub fn resolve(&self, token: &SyntaxToken) -> Option<Resolution> {
+        let db = &self.analysis.db;
+
+        let same_token =
+            |other: &SyntaxToken| other.text_range() == token.text_range() && self.is_local(other);
+
+        if let Some((_, resolution)) = db.references().iter().find(|(other, _)| same_token(other)) {
+            return Some(*resolution);
+        }
+
+        if let Some(symbol_id) = db
+            .named_symbols()
+            .into_iter()
+            .find(|&symbol_id| db.symbol_token(symbol_id).is_some_and(same_token))
+        {
+            return Some(Resolution::Symbol(symbol_id));
+        }
+
+        if let Some(type_id) = db
+            .named_types()
+            .into_iter()
+            .find(|&type_id| db.type_token(type_id).is_some_and(same_token))
+        {
+            return Some(Resolution::Type(type_id));
+        }
+
+        db.named_fields()
+            .into_iter()
+            .find(|&(type_id, index)| db.field_token(type_id, index).is_some_and(same_token))
+            .map(|(type_id, index)| Resolution::Field(type_id, index))
+    }
+
+    /// The scopes which are visible at the offset, from the outermost to the innermost.
+    pub fn visible_scopes(&self, offset: TextSize) -> &[ScopeId] {
+        self.analysis
+            .db
+            .visible_scopes()
+            .iter()
+            .filter(|visible| {
+                visible.offset <= offset
+                    && visible.node.text_range().contains_inclusive(offset)
+                    && visible
+                        .node
+                        .ancestors()
+                        .last()
+                        .is_some_and(|root| &root == self.root.syntax())
+            })
+            .max_by_key(|visible| visible.offset)
+            .map(|visible| visible.scope_ids.as_slice())
+            .unwrap_or_default()
+    }
+
+    /// The token where the item was originally defined.
+    pub fn definition(&self, resolution: Resolution) -> Option<SyntaxToken> {
+        match resolution {
+            Resolution::Symbol(symbol_id) => self.analysis.db.symbol_token(symbol_id).cloned(),
+            Resolution::Type(type_id) => self.analysis.db.type_token(type_id).cloned(),
+            Resolution::Field(type_id, index) => {
+                self.analysis.db.field_token(type_id, index).cloned()
+            }
+        }
+    }
+
+    /// Every reference to the item, in the order they appear.
+    /// This includes references from the standard library, but not the definition itself.
+    pub fn references_to(&self, resolution: Resolution) -> Vec<SyntaxToken> {
+        let mut tokens: Vec<SyntaxToken> = Vec::new();
+
+        for (token, other) in self.analysis.db.references() {
+            if *other != resolution || tokens.contains(token) {
+                continue;
+            }
+            tokens.push(token.clone());
+        }
+
+        tokens.sort_by_key(|token| (!self.is_local(token), token.text_range().start()));
+        tokens
+    }
+
+    /// The location of a token, which is either in this document or the standard library.
+    pub fn location(&self, uri: &Url, token: &SyntaxToken) -> Option<Location> {
+        if self.is_local(token) {
+            return Some(Location {
+                uri: uri.clone(),
+                range: self.range(token.text_range()),
+            });
+        }
+
+        let stdlib = stdlib()?;
+
+        Some(Location {
+            uri: stdlib.uri.clone(),
+            range: stdlib
+                .line_index
+                .range(STANDARD_LIBRARY, token.text_range()),
+        })
+    }
+
+    /// Stringifies a type, using the names of any types that have been defined.
+    pub fn type_name(&self, type_id: TypeId) -> String {
+        let db = &self.analysis.db;
+
+        let names: HashMap<TypeId, String> = db
+            .named_types()
+            .into_iter()
+            .filter_map(|type_id| Some((type_id, db.type_token(type_id)?.to_string())))
+            .collect();
+
+        self.analysis.ty.stringify_named(type_id, names)
+    }
+
+    /// The parameter names and types of a function type, along with its return type.
+    pub fn signature(&self, type_id: TypeId) -> Option<Signature> {
+        let ty = &self.analysis.ty;
+
+        let Type::Callable(callable) = ty.get(type_id) else {
+            return None;
+        };
+
+        let types = self.items(
+            callable.parameters,
+            callable.parameter_names.len(),
+            callable.nil_terminated,
+        );
+
+        let parameters = callable
+            .parameter_names
+            .iter()
+            .zip(types)
+            .map(|(name, type_id)| (name.clone(), self.type_name(type_id)))
+            .collect();
+
+        Some(Signature {
+            parameters,
+            return_type: self.type_name(callable.return_type),
+            spread: !callable.nil_terminated,
+        })
+    }
+
+    /// Splits a list of types into its items, where the last item is the rest of the list if it isn't nil terminated.
+    pub fn items(&self, type_id: TypeId, len: usize, nil_terminated: bool) -> Vec<TypeId> {
+        let ty = &self.analysis.ty;
+
+        let mut items = Vec::new();
+        let mut current = type_id;
+
+        for index in 0..len {
+            if index + 1 == len && !nil_terminated {
+                items.push(current);
+            } else if let Some((first, rest)) = ty.get_pair(current) {
+                items.push(first);
+                current = rest;
+            } else {
+                items.push(ty.std().unknown);
+            }
+        }
+
+        items
+    }
+
+    /// Formats the signature of a function, such as `fun name(a: Int) -> Int`.
+    pub fn format_signature(&self, name: &str, type_id: TypeId) -> Option<String> {
+        let Signature {
+            parameters,
+            return_type,
+            spread,
+        } = self.signature(type_id)?;
+        let len = parameters.len();
+
+        let parameters: Vec<String> = parameters
+            .into_iter()
+            .enumerate()
+            .map(|(index, (name, ty))| {
+                let spread = if spread && index + 1 == len {
+                    "..."
+                } else {
+                    ""
+                };
+                format!("{spread}{name}: {ty}")
+            })
+            .collect();
+
+        Some(format!(
+            "fun {name}({}) -> {return_type}",
+            parameters.join(", ")
+        ))
+    }
+
+    /// A short description of the symbol or type, as it would be declared.
+    pub fn describe(&self, resolution: Resolution, name: &str) -> String {
+        let db = &self.analysis.db;
+
+        match resolution {
+            Resolution::Symbol(symbol_id) => match db.symbol(symbol_id) {
+                Symbol::Unknown => name.to_string(),
+                Symbol::Module(..) => format!("mod {name}"),
+                Symbol::Function(function) => self
+                    .format_signature(name, function.type_id)
+                    .unwrap_or_else(|| format!("fun {name}")),
+                Symbol::InlineFunction(function) => {
+                    self.format_signature(name, function.type_id).map_or_else(
+                        || format!("inline fun {name}"),
+                        |sig| format!("inline {sig}"),
+                    )
+                }
+                Symbol::Parameter(type_id) => format!("{name}: {}", self.type_name(*type_id)),
+                Symbol::Let(value) => format!("let {name}: {}", self.type_name(value.type_id)),
+                Symbol::Const(value) => format!("const {name}: {}", self.type_name(value.type_id)),
+                Symbol::InlineConst(value) => {
+                    format!("inline const {name}: {}", self.type_name(value.type_id))
+                }
+            },
+            Resolution::Type(type_id) => match self.analysis.ty.get_raw(type_id) {
+                Type::Struct(..) => format!("struct {name}"),
+                Type::Enum(..) => format!("enum {name}"),
+                Type::Variant(variant) => {
+                    format!("{}::{name}", self.type_name(variant.original_enum_type_id))
+                }
+                Type::Alias(alias) => {
+                    format!("type {name} = {}", self.type_name(alias.type_id))
+                }
+                Type::Generic => name.to_string(),
+                _ => format!("type {name} = {}", self.type_name(type_id)),
+            },
+            Resolution::Field(type_id, index) => match self.field_type(type_id, index) {
+                Some(field_type_id) => format!("{name}: {}", self.type_name(field_type_id)),
+                None => name.to_string(),
+            },
+        }
+    }
+
+    /// The names and types of the fields of a struct or enum variant.
+    pub fn fields(&self, type_id: TypeId) -> Vec<(String, TypeId)> {
+        let (names, type_id, nil_terminated): (Vec<String>, TypeId, bool) =
+            match self.analysis.ty.get(type_id) {
+                Type::Struct(ty) => (
+                    ty.field_names.iter().cloned().collect(),
+                    ty.type_id,
+                    ty.nil_terminated,
+                ),
+                Type::Variant(ty) => {
+                    // Variants of enums with fields are prefixed by their discriminant.
+                    let type_id = self
+                        .analysis
+                        .ty
+                        .get_pair(ty.type_id)
+                        .map_or(ty.type_id, |(_, rest)| rest);
+
+                    (
+                        ty.field_names.iter().flatten().cloned().collect(),
+                        type_id,
+                        ty.nil_terminated,
+                    )
+                }
+                _ => return Vec::new(),
+            };
+
+        let types = self.items(type_id, names.len(), nil_terminated);
+        names.into_iter().zip(types).collect()
+    }
+
+    fn field_type(&self, type_id: TypeId, index: usize) -> Option<TypeId> {
+        self.fields(type_id)
+            .get(index)
+            .map(|&(_, field_type_id)| field_type_id)
+    }
+}
+
+struct StandardLibrary {
+    uri: Url,
+    line_index: LineIndex,
+}
+
+/// The standard library is written to a temporary file, so that editors can navigate to it.
+fn stdlib() -> Option<&'static StandardLibrary> {
+    static STDLIB: OnceLock<Option<StandardLibrary>> = OnceLock::new();
+
+    STDLIB
+        .get_or_init(|| {
+            let path = env::temp_dir().join("rue").join("stdlib.rue");
+            fs::create_dir_all(path.parent()?).ok()?;
+            fs::write(&path, STANDARD_LIBRARY).ok()?;
+
+            Some(StandardLibrary {
+                uri: Url::from_file_path(path).ok()?,
+                line_index: LineIndex::new(STANDARD_LIBRARY),
+            })
+        })
+        .as_ref()
+}
+
+/// Finds the comments directly above the item which defines the token.
+pub fn doc_comment(token: &SyntaxToken) -> Option<String> {
+    let node = token.parent_ancestors().find(|node| {
+        matches!(
+            node.kind(),
+            SyntaxKind::FunctionItem
+                | SyntaxKind::ConstItem
+                | SyntaxKind::StructItem
+                | SyntaxKind::StructField
+                | SyntaxKind::EnumItem
+                | SyntaxKind::EnumVariant
+                | SyntaxKind::TypeAliasItem
+                | SyntaxKind::ModuleItem
+                | SyntaxKind::LetStmt
+        )
+    })?;
+
+    let mut lines = Vec::new();
+    let mut current = node.first_token()?.prev_token();
+
+    // Trivia is attached to the end of the previous node, so the comments are found by walking backwards.
+    while let Some(token) = current {
+        match token.kind() {
+            SyntaxKind::LineComment if starts_line(&token) => {
+                lines.push(comment_text(token.text()));
+            }
+            SyntaxKind::Whitespace if !token.text().contains('\n') => {}
+            _ => break,
+        }
+        current = token.prev_token();
+    }
+
+    if lines.is_empty() {
+        return None;
+    }
+
+    lines.reverse();
+    Some(lines.join("\n"))
+}
+
+/// Whether the comment is on its own line, rather than after some code.
+fn starts_line(comment: &SyntaxToken) -> bool {
+    let mut current = comment.prev_token();
+
+    while let Some(token) = current {
+        match token.kind() {
+            SyntaxKind::Whitespace if !token.text().contains('\n') => {}
+            SyntaxKind::Whitespace | SyntaxKind::LineComment => return true,
+            _ => return false,
+        }
+        current = token.prev_token();
+    }
+
+    true
+}
+
+fn comment_text(comment: &str) -> String {
+    let comment = comment.trim_start_matches('/');
+    comment
+        .strip_prefix(' ')
+        .unwrap_or(comment)
+        .trim_end()
+        .to_string()
+}
+
+/// Analyzes the source text against the standard library, with the default lints.
+#[cfg(test)]
+pub fn test_document(text: &str) -> Document {
+    let stdlib = rue_compiler::compile_standard_library();
+    let (root, parser_errors) = rue_parser::parse(text);
+    Document::new(
+        text.to_string(),
+        root,
+        parser_errors,
+        &stdlib,
+        &LintConfig::default(),
+    )
+}
+
+/// Applies the edits to the document's text, from the last to the first.
+#[cfg(test)]
+pub fn apply_edits(document: &Document, edits: &[tower_lsp::lsp_types::TextEdit]) -> String {
+    let mut edits: Vec<_> = edits
+        .iter()
+        .map(|edit| {
+            let start = usize::from(document.offset(edit.range.start).unwrap());
+            let end = usize::from(document.offset(edit.range.end).unwrap());
+            (start..end, edit.new_text.as_str())
+        })
+        .collect();
+
+    edits.sort_by_key(|(range, _)| std::cmp::Reverse(range.start));
+
+    let mut text = document.text.clone();
+
+    for (range, new_text) in edits {
+        text.replace_range(range, new_text);
+    }
+
+    text
+}
+
+/// The position of the first occurrence of the text, plus some number of characters into it.
+#[cfg(test)]
+pub fn position_of(document: &Document, needle: &str, offset: usize) -> Position {
+    let start = document
+        .text
+        .find(needle)
+        .expect("missing text in document");
+    document.line_index.position(&document.text, start + offset)
+}
@@ -0,0 +1,19 @@
+use rue_fmt::format;
+use tower_lsp::lsp_types::TextEdit;
+
+use crate::line_index::LineIndex;
+
+/// Replaces the whole document with its formatted text.
+/// Documents with syntax errors are left as they are.
+pub fn formatting(text: &str) -> Option<Vec<TextEdit>> {
+    let formatted = format(text).ok()?;
+
+    if formatted == text {
+        return Some(Vec::new());
+    }
+
+    let range =
+        LineIndex::new(text).range(text, rowan::TextRange::up_to(text.len().try_into().ok()?));
+
+    Some(vec![TextEdit::new(range, formatted)])
+}
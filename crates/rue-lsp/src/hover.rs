@@ -0,0 +1,107 @@
+use tower_lsp::lsp_types::{Hover, HoverContents, MarkupContent, MarkupKind, Position};
+
+use crate::document::{doc_comment, Document};
+
+pub fn hover(document: &Document, position: Position) -> Option<Hover> {
+    let token = document.ident_at(position)?;
+    let resolution = document.resolve(&token)?;
+
+    let mut sections = vec![format!(
+        "```rue\n{}\n```",
+        document.describe(resolution, token.text())
+    )];
+
+    if let Some(comment) = document
+        .definition(resolution)
+        .and_then(|definition| doc_comment(&definition))
+    {
+        sections.push(comment);
+    }
+
+    Some(Hover {
+        contents: HoverContents::Markup(MarkupContent {
+            kind: MarkupKind::Markdown,
+            value: sections.join("\n\n---\n\n"),
+        }),
+        range: Some(document.range(token.text_range())),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use indoc::indoc;
+    use tower_lsp::lsp_types::Range;
+
+    use crate::document::{position_of, test_document};
+
+    use super::*;
+
+    /// The markdown shown when hovering at the offset into the first occurrence of the text.
+    fn hover_text(source: &str, needle: &str, offset: usize) -> Option<String> {
+        let document = test_document(source);
+        let position = position_of(&document, needle, offset);
+
+        let HoverContents::Markup(markup) = hover(&document, position)?.contents else {
+            panic!("expected markdown hover contents");
+        };
+
+        Some(markup.value)
+    }
+
+    #[test]
+    fn test_hover_function() {
+        let source = indoc! {"
+            // Doubles the amount.
+            fun double(amount: Int) -> Int {
+                amount * 2
+            }
+
+            fun main() -> Int {
+                double(21)
+            }
+        "};
+
+        assert_eq!(
+            hover_text(source, "double(21)", 0).unwrap(),
+            "```rue\nfun double(amount: Int) -> Int\n```\n\n---\n\nDoubles the amount."
+        );
+
+        let document = test_document(source);
+        let position = position_of(&document, "double(21)", 3);
+        assert_eq!(
+            hover(&document, position).unwrap().range,
+            Some(Range::new(Position::new(6, 4), Position::new(6, 10)))
+        );
+    }
+
+    #[test]
+    fn test_hover_binding() {
+        let source = indoc! {"
+            fun main(amount: Int) -> Int {
+                let doubled = amount * 2;
+                doubled
+            }
+        "};
+
+        assert_eq!(
+            hover_text(source, "amount * 2", 0).unwrap(),
+            "```rue\namount: Int\n```"
+        );
+        assert_eq!(
+            hover_text(source, "doubled\n}", 0).unwrap(),
+            "```rue\nlet doubled: Int\n```"
+        );
+    }
+
+    #[test]
+    fn test_hover_nothing() {
+        let source = indoc! {"
+            fun main(amount: Int) -> Int {
+                amount * unknown
+            }
+        "};
+
+        assert_eq!(hover_text(source, "* unknown", 1), None);
+        assert_eq!(hover_text(source, "unknown", 0), None);
+    }
+}
@@ -0,0 +1,233 @@
+use std::collections::HashMap;
+
+use rowan::TextRange;
+use rue_compiler::{trimmed_range, Symbol, SymbolId};
+use rue_parser::{AstNode, Expr, FunctionCallExpr, LambdaParam, LetStmt, SyntaxToken};
+use rue_typing::TypeId;
+use tower_lsp::lsp_types::{InlayHint, InlayHintKind, InlayHintLabel};
+
+use crate::document::Document;
+
+/// Shows the inferred types of untyped bindings, and the names of parameters at call sites.
+pub fn inlay_hints(document: &Document, range: TextRange) -> Vec<InlayHint> {
+    let db = &document.analysis.db;
+
+    // Definitions are looked up by their position, since they are all in this document.
+    let symbols: HashMap<TextRange, SymbolId> = db
+        .named_symbols()
+        .into_iter()
+        .filter_map(|symbol_id| {
+            let token = db.symbol_token(symbol_id)?;
+            document
+                .is_local(token)
+                .then(|| (token.text_range(), symbol_id))
+        })
+        .collect();
+
+    let symbol_type = |name: &SyntaxToken| match db.symbol(*symbols.get(&name.text_range())?) {
+        Symbol::Let(value) => Some(value.type_id),
+        Symbol::Parameter(type_id) => Some(*type_id),
+        _ => None,
+    };
+
+    let mut hints = Vec::new();
+
+    for node in document.root.syntax().descendants() {
+        // Trailing trivia would otherwise include the statement before the start of the range.
+        if range.intersect(trimmed_range(&node)).is_none() {
+            continue;
+        }
+
+        if let Some(let_stmt) = LetStmt::cast(node.clone()) {
+            if let_stmt.ty().is_some() {
+                continue;
+            }
+            let Some(name) = let_stmt.name() else {
+                continue;
+            };
+            if let Some(type_id) = symbol_type(&name) {
+                hints.push(type_hint(document, &name, type_id));
+            }
+        } else if let Some(param) = LambdaParam::cast(node.clone()) {
+            if param.ty().is_some() {
+                continue;
+            }
+            let Some(name) = param.name() else {
+                continue;
+            };
+            if let Some(type_id) = symbol_type(&name) {
+                hints.push(type_hint(document, &name, type_id));
+            }
+        } else if let Some(call) = FunctionCallExpr::cast(node) {
+            hints.extend(parameter_hints(document, &call));
+        }
+    }
+
+    hints
+}
+
+fn type_hint(document: &Document, name: &SyntaxToken, type_id: TypeId) -> InlayHint {
+    InlayHint {
+        position: document.range(name.text_range()).end,
+        label: InlayHintLabel::String(format!(": {}", document.type_name(type_id))),
+        kind: Some(InlayHintKind::TYPE),
+        text_edits: None,
+        tooltip: None,
+        padding_left: None,
+        padding_right: None,
+        data: None,
+    }
+}
+
+fn parameter_hints(document: &Document, call: &FunctionCallExpr) -> Vec<InlayHint> {
+    let Some(type_id) = call
+        .callee()
+        .and_then(|callee| document.analysis.db.expr_type(callee.syntax()))
+    else {
+        return Vec::new();
+    };
+
+    let Some(signature) = document.signature(type_id) else {
+        return Vec::new();
+    };
+
+    let len = signature.parameters.len();
+    let mut hints = Vec::new();
+
+    for (index, arg) in call.args().into_iter().enumerate() {
+        // The spread parameter is only labeled at the first of its arguments.
+        let Some((name, _)) = signature.parameters.get(index) else {
+            break;
+        };
+
+        let Some(expr) = arg.expr() else {
+            continue;
+        };
+
+        // The hint would be redundant if the argument is a variable of the same name.
+        if let Expr::PathExpr(path) = &expr {
+            if path.syntax().text().to_string().trim() == name {
+                continue;
+            }
+        }
+
+        let spread = if signature.spread && index + 1 == len {
+            "..."
+        } else {
+            ""
+        };
+
+        // Leading trivia can be attached to the start of the expression.
+        let start = trimmed_range(expr.syntax());
+
+        hints.push(InlayHint {
+            position: document.range(start).start,
+            label: InlayHintLabel::String(format!("{spread}{name}:")),
+            kind: Some(InlayHintKind::PARAMETER),
+            text_edits: None,
+            tooltip: None,
+            padding_left: None,
+            padding_right: Some(true),
+            data: None,
+        });
+    }
+
+    hints
+}
+
+#[cfg(test)]
+mod tests {
+    use indoc::indoc;
+    use rowan::TextSize;
+    use tower_lsp::lsp_types::Position;
+
+    use crate::document::test_document;
+
+    use super::*;
+
+    /// The position and label of each hint in the document, or only the given lines of it.
+    fn hints(source: &str, lines: Option<(u32, u32)>) -> Vec<(Position, String)> {
+        let document = test_document(source);
+
+        let range = match lines {
+            Some((start, end)) => TextRange::new(
+                document.offset(Position::new(start, 0)).unwrap(),
+                document.offset(Position::new(end, 0)).unwrap(),
+            ),
+            None => TextRange::up_to(TextSize::of(source)),
+        };
+
+        inlay_hints(&document, range)
+            .into_iter()
+            .map(|hint| {
+                let InlayHintLabel::String(label) = hint.label else {
+                    panic!("expected a string label");
+                };
+                (hint.position, label)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_type_hints() {
+        let source = indoc! {"
+            fun main(amount: Int) -> List<Int> {
+                let inferred = amount * 2;
+                let annotated: Int = inferred;
+                map([annotated], fun(value) => value * 2)
+            }
+        "};
+
+        assert_eq!(
+            hints(source, None),
+            [
+                (Position::new(1, 16), ": Int".to_string()),
+                (Position::new(3, 8), "list:".to_string()),
+                (Position::new(3, 21), "fn:".to_string()),
+                (Position::new(3, 30), ": Int".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parameter_hints() {
+        let source = indoc! {"
+            fun add(left: Int, right: Int) -> Int {
+                left + right
+            }
+
+            fun sum(...values: List<Int>) -> Int {
+                0
+            }
+
+            fun main(right: Int) -> Int {
+                add(1, right) + sum(1, 2, 3)
+            }
+        "};
+
+        // The argument with the same name as its parameter isn't labeled.
+        assert_eq!(
+            hints(source, None),
+            [
+                (Position::new(9, 8), "left:".to_string()),
+                (Position::new(9, 24), "...values:".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_hints_in_range() {
+        let source = indoc! {"
+            fun main() -> Int {
+                let first = 1;
+                let second = 2;
+                first + second
+            }
+        "};
+
+        assert_eq!(
+            hints(source, Some((2, 3))),
+            [(Position::new(2, 14), ": Int".to_string())]
+        );
+    }
+}
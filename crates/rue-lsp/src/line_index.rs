@@ -0,0 +1,63 @@
+use rowan::TextRange;
+use tower_lsp::lsp_types::{Position, Range};
+
+/// Converts between byte offsets in the source and LSP positions, which are in UTF-16 code units.
+#[derive(Debug, Clone)]
+pub struct LineIndex {
+    line_starts: Vec<usize>,
+}
+
+impl LineIndex {
+    pub fn new(text: &str) -> Self {
+        let mut line_starts = vec![0];
+
+        for (index, character) in text.char_indices() {
+            if character == '\n' {
+                line_starts.push(index + 1);
+            }
+        }
+
+        Self { line_starts }
+    }
+
+    pub fn position(&self, text: &str, offset: usize) -> Position {
+        let offset = offset.min(text.len());
+        let line = self.line_starts.partition_point(|&start| start <= offset) - 1;
+        let start = self.line_starts[line];
+
+        let character = text[start..offset].encode_utf16().count();
+
+        Position {
+            line: line.try_into().unwrap(),
+            character: character.try_into().unwrap(),
+        }
+    }
+
+    pub fn offset(&self, text: &str, position: Position) -> Option<usize> {
+        let line = usize::try_from(position.line).ok()?;
+        let start = *self.line_starts.get(line)?;
+        let end = self
+            .line_starts
+            .get(line + 1)
+            .copied()
+            .unwrap_or(text.len());
+
+        let mut remaining = usize::try_from(position.character).ok()?;
+
+        for (index, character) in text[start..end].char_indices() {
+            if remaining == 0 || character == '\n' {
+                return Some(start + index);
+            }
+            remaining = remaining.saturating_sub(character.len_utf16());
+        }
+
+        Some(end)
+    }
+
+    pub fn range(&self, text: &str, range: TextRange) -> Range {
+        Range {
+            start: self.position(text, range.start().into()),
+            end: self.position(text, range.end().into()),
+        }
+    }
+}
@@ -1,27 +1,112 @@
-use clvmr::Allocator;
-use rue_compiler::{compile_raw, DiagnosticKind};
-use rue_parser::{line_col, parse, LineCol};
+use std::time::Duration;
+
+use rue_compiler::{DiagnosticKind, MANIFEST_FILE_NAME};
 use tower_lsp::jsonrpc::Result;
 use tower_lsp::lsp_types::{
-    Diagnostic, DiagnosticSeverity, DidChangeTextDocumentParams, DidOpenTextDocumentParams,
-    InitializeParams, InitializeResult, InitializedParams, MessageType, Position, Range,
-    ServerCapabilities, TextDocumentSyncCapability, TextDocumentSyncKind, Url,
+    CodeActionParams, CodeActionProviderCapability, CodeActionResponse, CompletionOptions,
+    CompletionParams, CompletionResponse, Diagnostic, DiagnosticSeverity,
+    DidChangeTextDocumentParams, DidChangeWatchedFilesParams,
+    DidChangeWatchedFilesRegistrationOptions, DidCloseTextDocumentParams,
+    DidOpenTextDocumentParams, DocumentFormattingParams, DocumentHighlight,
+    DocumentHighlightParams, DocumentSymbolParams, DocumentSymbolResponse, FileSystemWatcher,
+    GlobPattern, GotoDefinitionParams, GotoDefinitionResponse, Hover, HoverParams,
+    HoverProviderCapability, InitializeParams, InitializeResult, InitializedParams, InlayHint,
+    InlayHintParams, Location, MessageType, NumberOrString, OneOf, PrepareRenameResponse, Range,
+    ReferenceParams, Registration, RenameOptions, RenameParams, SemanticTokensFullOptions,
+    SemanticTokensOptions, SemanticTokensParams, SemanticTokensRangeParams,
+    SemanticTokensRangeResult, SemanticTokensResult, SemanticTokensServerCapabilities,
+    ServerCapabilities, SignatureHelp, SignatureHelpOptions, SignatureHelpParams,
+    SymbolInformation, TextDocumentPositionParams, TextDocumentSyncCapability,
+    TextDocumentSyncKind, TextEdit, Url, WorkDoneProgressOptions, WorkspaceEdit,
+    WorkspaceSymbolParams,
 };
 use tower_lsp::{Client, LanguageServer, LspService, Server};
 
+use document::Document;
+use workspace::WorkspaceHandle;
+
+mod code_actions;
+mod completion;
+mod document;
+mod formatting;
+mod hover;
+mod inlay_hints;
+mod line_index;
+mod navigation;
+mod rename;
+mod semantic_tokens;
+mod signature_help;
+mod symbols;
+mod workspace;
+
+/// How long to wait after the last change before publishing diagnostics.
+const DEBOUNCE: Duration = Duration::from_millis(200);
+
 #[derive(Debug)]
 struct Backend {
     client: Client,
+    workspace: WorkspaceHandle,
 }
 
 #[tower_lsp::async_trait]
 impl LanguageServer for Backend {
-    async fn initialize(&self, _: InitializeParams) -> Result<InitializeResult> {
+    async fn initialize(&self, params: InitializeParams) -> Result<InitializeResult> {
+        #[allow(deprecated)]
+        let folders = match params.workspace_folders {
+            Some(folders) => folders.into_iter().map(|folder| folder.uri).collect(),
+            None => params.root_uri.into_iter().collect::<Vec<Url>>(),
+        };
+
+        let folders = folders
+            .into_iter()
+            .filter_map(|uri| uri.to_file_path().ok())
+            .collect();
+
+        self.workspace
+            .run(move |workspace| workspace.set_folders(folders))
+            .await;
+
         Ok(InitializeResult {
             capabilities: ServerCapabilities {
                 text_document_sync: Some(TextDocumentSyncCapability::Kind(
-                    TextDocumentSyncKind::FULL,
+                    TextDocumentSyncKind::INCREMENTAL,
                 )),
+                hover_provider: Some(HoverProviderCapability::Simple(true)),
+                definition_provider: Some(OneOf::Left(true)),
+                references_provider: Some(OneOf::Left(true)),
+                document_highlight_provider: Some(OneOf::Left(true)),
+                rename_provider: Some(OneOf::Right(RenameOptions {
+                    prepare_provider: Some(true),
+                    work_done_progress_options: WorkDoneProgressOptions::default(),
+                })),
+                semantic_tokens_provider: Some(
+                    SemanticTokensServerCapabilities::SemanticTokensOptions(
+                        SemanticTokensOptions {
+                            legend: semantic_tokens::legend(),
+                            range: Some(true),
+                            full: Some(SemanticTokensFullOptions::Bool(true)),
+                            ..Default::default()
+                        },
+                    ),
+                ),
+                inlay_hint_provider: Some(OneOf::Left(true)),
+                code_action_provider: Some(CodeActionProviderCapability::Simple(true)),
+                signature_help_provider: Some(SignatureHelpOptions {
+                    trigger_characters: Some(vec![
+                        "(".to_string(),
+                        ",".to_string(),
+                        "{".to_string(),
+                    ]),
+                    retrigger_characters: None,
+                    work_done_progress_options: WorkDoneProgressOptions::default(),
+                }),
+                document_symbol_provider: Some(OneOf::Left(true)),
+                document_formatting_provider: Some(OneOf::Left(true)),
+                workspace_symbol_provider: Some(OneOf::Left(true)),
+                completion_provider: Some(CompletionOptions {
+                    trigger_characters: Some(vec![".".to_string(), ":".to_string()]),
+                    ..Default::default()
+                }),
                 ..Default::default()
             },
             ..Default::default()
@@ -29,27 +114,273 @@ impl LanguageServer for Backend {
     }
 
     async fn initialized(&self, _: InitializedParams) {
+        // Manifests configure the lints, so documents are checked again when one changes on disk.
+        let watchers = DidChangeWatchedFilesRegistrationOptions {
+            watchers: vec![FileSystemWatcher {
+                glob_pattern: GlobPattern::String(format!("**/{MANIFEST_FILE_NAME}")),
+                kind: None,
+            }],
+        };
+
+        let registration = Registration {
+            id: "manifest-watcher".to_string(),
+            method: "workspace/didChangeWatchedFiles".to_string(),
+            register_options: serde_json::to_value(watchers).ok(),
+        };
+
+        if self
+            .client
+            .register_capability(vec![registration])
+            .await
+            .is_err()
+        {
+            self.client
+                .log_message(
+                    MessageType::WARNING,
+                    "Changes to manifests won't be detected until the documents are reopened.",
+                )
+                .await;
+        }
+
         self.client
             .log_message(MessageType::INFO, "Rue language server initialized.")
             .await;
     }
 
     async fn did_open(&self, params: DidOpenTextDocumentParams) {
-        self.on_change(
-            params.text_document.uri,
-            params.text_document.text,
-            params.text_document.version,
-        )
-        .await;
+        let document = params.text_document;
+        let uri = document.uri.clone();
+        let version = document.version;
+
+        self.workspace
+            .run(move |workspace| workspace.open(document.uri, document.text, document.version))
+            .await;
+
+        publish_diagnostics(&self.client, &self.workspace, uri, version).await;
     }
 
     async fn did_change(&self, params: DidChangeTextDocumentParams) {
-        self.on_change(
-            params.text_document.uri,
-            params.content_changes[0].text.clone(),
-            params.text_document.version,
-        )
-        .await;
+        let uri = params.text_document.uri;
+        let version = params.text_document.version;
+
+        let changed_uri = uri.clone();
+        self.workspace
+            .run(move |workspace| workspace.change(&changed_uri, params.content_changes, version))
+            .await;
+
+        // Wait for typing to pause, so that each keystroke doesn't trigger another analysis.
+        let client = self.client.clone();
+        let workspace = self.workspace.clone();
+
+        tokio::spawn(async move {
+            tokio::time::sleep(DEBOUNCE).await;
+            publish_diagnostics(&client, &workspace, uri, version).await;
+        });
+    }
+
+    async fn did_close(&self, params: DidCloseTextDocumentParams) {
+        let uri = params.text_document.uri;
+        self.workspace
+            .run(move |workspace| workspace.close(&uri))
+            .await;
+    }
+
+    async fn did_change_watched_files(&self, params: DidChangeWatchedFilesParams) {
+        for change in params.changes {
+            let Ok(path) = change.uri.to_file_path() else {
+                continue;
+            };
+
+            let changed = self
+                .workspace
+                .run(move |workspace| workspace.change_manifest(&path))
+                .await
+                .unwrap_or_default();
+
+            for (uri, version) in changed {
+                publish_diagnostics(&self.client, &self.workspace, uri, version).await;
+            }
+        }
+    }
+
+    async fn hover(&self, params: HoverParams) -> Result<Option<Hover>> {
+        let position = params.text_document_position_params;
+        Ok(self
+            .with_document(position.text_document.uri, move |document| {
+                hover::hover(document, position.position)
+            })
+            .await)
+    }
+
+    async fn goto_definition(
+        &self,
+        params: GotoDefinitionParams,
+    ) -> Result<Option<GotoDefinitionResponse>> {
+        let position = params.text_document_position_params;
+        let uri = position.text_document.uri;
+        Ok(self
+            .with_document(uri.clone(), move |document| {
+                navigation::definition(document, &uri, position.position)
+            })
+            .await)
+    }
+
+    async fn references(&self, params: ReferenceParams) -> Result<Option<Vec<Location>>> {
+        let position = params.text_document_position;
+        let uri = position.text_document.uri;
+        Ok(self
+            .with_document(uri.clone(), move |document| {
+                navigation::references(
+                    document,
+                    &uri,
+                    position.position,
+                    params.context.include_declaration,
+                )
+            })
+            .await)
+    }
+
+    async fn document_highlight(
+        &self,
+        params: DocumentHighlightParams,
+    ) -> Result<Option<Vec<DocumentHighlight>>> {
+        let position = params.text_document_position_params;
+        Ok(self
+            .with_document(position.text_document.uri, move |document| {
+                navigation::highlights(document, position.position)
+            })
+            .await)
+    }
+
+    async fn completion(&self, params: CompletionParams) -> Result<Option<CompletionResponse>> {
+        let position = params.text_document_position;
+        Ok(self
+            .with_document(position.text_document.uri, move |document| {
+                completion::completion(document, position.position)
+            })
+            .await)
+    }
+
+    async fn prepare_rename(
+        &self,
+        params: TextDocumentPositionParams,
+    ) -> Result<Option<PrepareRenameResponse>> {
+        self.with_document(params.text_document.uri, move |document| {
+            Some(rename::prepare_rename(document, params.position))
+        })
+        .await
+        .unwrap_or(Ok(None))
+    }
+
+    async fn rename(&self, params: RenameParams) -> Result<Option<WorkspaceEdit>> {
+        let position = params.text_document_position;
+        let uri = position.text_document.uri;
+        self.with_document(uri.clone(), move |document| {
+            Some(rename::rename(
+                document,
+                &uri,
+                position.position,
+                &params.new_name,
+            ))
+        })
+        .await
+        .unwrap_or(Ok(None))
+    }
+
+    async fn semantic_tokens_full(
+        &self,
+        params: SemanticTokensParams,
+    ) -> Result<Option<SemanticTokensResult>> {
+        Ok(self
+            .with_document(params.text_document.uri, |document| {
+                Some(SemanticTokensResult::Tokens(
+                    semantic_tokens::semantic_tokens(document, None),
+                ))
+            })
+            .await)
+    }
+
+    async fn semantic_tokens_range(
+        &self,
+        params: SemanticTokensRangeParams,
+    ) -> Result<Option<SemanticTokensRangeResult>> {
+        Ok(self
+            .with_document(params.text_document.uri, move |document| {
+                let range = rowan::TextRange::new(
+                    document.offset(params.range.start)?,
+                    document.offset(params.range.end)?,
+                );
+                Some(SemanticTokensRangeResult::Tokens(
+                    semantic_tokens::semantic_tokens(document, Some(range)),
+                ))
+            })
+            .await)
+    }
+
+    async fn inlay_hint(&self, params: InlayHintParams) -> Result<Option<Vec<InlayHint>>> {
+        Ok(self
+            .with_document(params.text_document.uri, move |document| {
+                let range = rowan::TextRange::new(
+                    document.offset(params.range.start)?,
+                    document.offset(params.range.end)?,
+                );
+                Some(inlay_hints::inlay_hints(document, range))
+            })
+            .await)
+    }
+
+    async fn code_action(&self, params: CodeActionParams) -> Result<Option<CodeActionResponse>> {
+        let uri = params.text_document.uri;
+        Ok(self
+            .with_document(uri.clone(), move |document| {
+                let range = rowan::TextRange::new(
+                    document.offset(params.range.start)?,
+                    document.offset(params.range.end)?,
+                );
+                Some(code_actions::code_actions(document, &uri, range))
+            })
+            .await)
+    }
+
+    async fn signature_help(&self, params: SignatureHelpParams) -> Result<Option<SignatureHelp>> {
+        let position = params.text_document_position_params;
+        Ok(self
+            .with_document(position.text_document.uri, move |document| {
+                signature_help::signature_help(document, position.position)
+            })
+            .await)
+    }
+
+    async fn document_symbol(
+        &self,
+        params: DocumentSymbolParams,
+    ) -> Result<Option<DocumentSymbolResponse>> {
+        let uri = params.text_document.uri;
+        Ok(self
+            .workspace
+            .run(move |workspace| workspace.document_symbols(&uri))
+            .await
+            .flatten()
+            .map(DocumentSymbolResponse::Nested))
+    }
+
+    async fn formatting(&self, params: DocumentFormattingParams) -> Result<Option<Vec<TextEdit>>> {
+        let uri = params.text_document.uri;
+        Ok(self
+            .workspace
+            .run(move |workspace| workspace.text(&uri).and_then(formatting::formatting))
+            .await
+            .flatten())
+    }
+
+    async fn symbol(
+        &self,
+        params: WorkspaceSymbolParams,
+    ) -> Result<Option<Vec<SymbolInformation>>> {
+        Ok(self
+            .workspace
+            .run(move |workspace| workspace.symbols(&params.query))
+            .await)
     }
 
     async fn shutdown(&self) -> Result<()> {
@@ -57,68 +388,103 @@ impl LanguageServer for Backend {
     }
 }
 
-/// This is a hack to get around a Rust compiler error.
-#[allow(clippy::needless_pass_by_value)]
-fn analyze_owned(root: rue_parser::Root) -> Vec<rue_compiler::Diagnostic> {
-    compile_raw(&mut Allocator::new(), &root, false, true).diagnostics
+impl Backend {
+    /// Queries the analyzed document on the analysis thread.
+    async fn with_document<T>(
+        &self,
+        uri: Url,
+        f: impl FnOnce(&Document) -> Option<T> + Send + 'static,
+    ) -> Option<T>
+    where
+        T: Send + 'static,
+    {
+        self.workspace
+            .run(move |workspace| workspace.document(&uri).and_then(f))
+            .await
+            .flatten()
+    }
+}
+
+/// Publishes the diagnostics for a document, unless it has changed since the given version.
+async fn publish_diagnostics(client: &Client, workspace: &WorkspaceHandle, uri: Url, version: i32) {
+    let document_uri = uri.clone();
+    let diagnostics = workspace
+        .run(move |workspace| {
+            if workspace.version(&document_uri) != Some(version) {
+                return None;
+            }
+            workspace.document(&document_uri).map(diagnostics)
+        })
+        .await
+        .flatten();
+
+    if let Some(diagnostics) = diagnostics {
+        client
+            .publish_diagnostics(uri, diagnostics, Some(version))
+            .await;
+    }
 }
 
-impl Backend {
-    async fn on_change(&self, uri: Url, text: String, _version: i32) {
-        let (root, errors) = parse(&text);
-        let compiler_errors = analyze_owned(root);
+fn diagnostics(document: &Document) -> Vec<Diagnostic> {
+    let mut diagnostics: Vec<Diagnostic> = document
+        .parser_errors
+        .iter()
+        .map(|error| {
+            diagnostic(
+                document.range(text_range(error.span())),
+                format!("{}", error.kind()),
+                error.kind().code(),
+                DiagnosticSeverity::ERROR,
+            )
+        })
+        .collect();
 
-        let mut diagnostics: Vec<Diagnostic> = errors
-            .into_iter()
-            .map(|error| {
-                let start = line_col(&text, error.span().start);
-                let end = line_col(&text, error.span().end);
-
-                diagnostic(
-                    start,
-                    end,
-                    format!("{}", error.kind()),
-                    DiagnosticSeverity::ERROR,
-                )
-            })
-            .collect();
+    for error in document.analysis.diagnostics() {
+        diagnostics.push(compiler_diagnostic(document, error));
+    }
 
-        for error in compiler_errors {
-            let start = line_col(&text, error.span().start);
-            let end = line_col(&text, error.span().end);
+    diagnostics
+}
 
-            let (message, severity) = match error.kind() {
-                DiagnosticKind::Error(kind) => (format!("{kind}"), DiagnosticSeverity::ERROR),
-                DiagnosticKind::Warning(kind) => (format!("{kind}"), DiagnosticSeverity::WARNING),
-            };
+fn compiler_diagnostic(document: &Document, error: &rue_compiler::Diagnostic) -> Diagnostic {
+    let mut message = match error.kind() {
+        DiagnosticKind::Error(kind) => format!("{kind}"),
+        DiagnosticKind::Warning(kind) => format!("{kind}"),
+    };
 
-            diagnostics.push(diagnostic(start, end, message, severity));
-        }
+    let severity = if error.is_error() {
+        DiagnosticSeverity::ERROR
+    } else {
+        DiagnosticSeverity::WARNING
+    };
 
-        self.client
-            .publish_diagnostics(uri, diagnostics, None)
-            .await;
+    if let Some(note) = error.note() {
+        message.push('\n');
+        message.push_str(note);
     }
+
+    diagnostic(
+        document.range(text_range(error.span())),
+        message,
+        error.kind().code(),
+        severity,
+    )
+}
+
+fn text_range(span: &std::ops::Range<usize>) -> rowan::TextRange {
+    rowan::TextRange::new(span.start.try_into().unwrap(), span.end.try_into().unwrap())
 }
 
 fn diagnostic(
-    start: LineCol,
-    end: LineCol,
+    range: Range,
     message: String,
+    code: &str,
     severity: DiagnosticSeverity,
 ) -> Diagnostic {
     Diagnostic {
-        range: Range {
-            start: Position {
-                line: start.line.try_into().unwrap(),
-                character: start.col.try_into().unwrap(),
-            },
-            end: Position {
-                line: end.line.try_into().unwrap(),
-                character: end.col.try_into().unwrap(),
-            },
-        },
+        range,
         message,
+        code: Some(NumberOrString::String(code.to_string())),
         severity: Some(severity),
         ..Diagnostic::default()
     }
@@ -129,6 +495,9 @@ async fn main() {
     let stdin = tokio::io::stdin();
     let stdout = tokio::io::stdout();
 
-    let (service, socket) = LspService::new(|client| Backend { client });
+    let (service, socket) = LspService::new(|client| Backend {
+        client,
+        workspace: WorkspaceHandle::spawn(),
+    });
     Server::new(stdin, stdout, socket).serve(service).await;
 }
@@ -0,0 +1,185 @@
+use tower_lsp::lsp_types::{
+    DocumentHighlight, DocumentHighlightKind, GotoDefinitionResponse, Location, Position, Url,
+};
+
+use crate::document::Document;
+
+pub fn definition(
+    document: &Document,
+    uri: &Url,
+    position: Position,
+) -> Option<GotoDefinitionResponse> {
+    let token = document.ident_at(position)?;
+    let resolution = document.resolve(&token)?;
+    let definition = document.definition(resolution)?;
+    document
+        .location(uri, &definition)
+        .map(GotoDefinitionResponse::Scalar)
+}
+
+pub fn references(
+    document: &Document,
+    uri: &Url,
+    position: Position,
+    include_declaration: bool,
+) -> Option<Vec<Location>> {
+    let token = document.ident_at(position)?;
+    let resolution = document.resolve(&token)?;
+
+    let mut locations = Vec::new();
+
+    if include_declaration {
+        if let Some(definition) = document.definition(resolution) {
+            locations.extend(document.location(uri, &definition));
+        }
+    }
+
+    for reference in document.references_to(resolution) {
+        locations.extend(document.location(uri, &reference));
+    }
+
+    Some(locations)
+}
+
+/// Highlights the definition and references of the item under the cursor, within this document.
+pub fn highlights(document: &Document, position: Position) -> Option<Vec<DocumentHighlight>> {
+    let token = document.ident_at(position)?;
+    let resolution = document.resolve(&token)?;
+
+    let mut highlights = Vec::new();
+
+    if let Some(definition) = document
+        .definition(resolution)
+        .filter(|definition| document.is_local(definition))
+    {
+        highlights.push(DocumentHighlight {
+            range: document.range(definition.text_range()),
+            kind: Some(DocumentHighlightKind::WRITE),
+        });
+    }
+
+    for reference in document.references_to(resolution) {
+        if !document.is_local(&reference) {
+            continue;
+        }
+
+        highlights.push(DocumentHighlight {
+            range: document.range(reference.text_range()),
+            kind: Some(DocumentHighlightKind::READ),
+        });
+    }
+
+    Some(highlights)
+}
+
+#[cfg(test)]
+mod tests {
+    use indoc::indoc;
+    use tower_lsp::lsp_types::Range;
+
+    use crate::document::{position_of, test_document};
+
+    use super::*;
+
+    fn uri() -> Url {
+        Url::parse("file:///main.rue").unwrap()
+    }
+
+    fn range(line: u32, start: u32, end: u32) -> Range {
+        Range::new(Position::new(line, start), Position::new(line, end))
+    }
+
+    const SOURCE: &str = indoc! {"
+        fun main() -> Int {
+            let value = 42;
+            let other = value + 1;
+            value * other
+        }
+    "};
+
+    #[test]
+    fn test_definition() {
+        let document = test_document(SOURCE);
+        let position = position_of(&document, "value * other", 0);
+
+        assert_eq!(
+            definition(&document, &uri(), position),
+            Some(GotoDefinitionResponse::Scalar(Location::new(
+                uri(),
+                range(1, 8, 13)
+            )))
+        );
+    }
+
+    #[test]
+    fn test_references() {
+        let document = test_document(SOURCE);
+        let position = position_of(&document, "value", 0);
+
+        let ranges = |include_declaration| {
+            references(&document, &uri(), position, include_declaration)
+                .unwrap()
+                .into_iter()
+                .map(|location| location.range)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(ranges(false), [range(2, 16, 21), range(3, 4, 9)]);
+        assert_eq!(
+            ranges(true),
+            [range(1, 8, 13), range(2, 16, 21), range(3, 4, 9)]
+        );
+    }
+
+    #[test]
+    fn test_highlights() {
+        let document = test_document(SOURCE);
+        let position = position_of(&document, "other", 0);
+
+        assert_eq!(
+            highlights(&document, position).unwrap(),
+            [
+                DocumentHighlight {
+                    range: range(2, 8, 13),
+                    kind: Some(DocumentHighlightKind::WRITE),
+                },
+                DocumentHighlight {
+                    range: range(3, 12, 17),
+                    kind: Some(DocumentHighlightKind::READ),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_standard_library() {
+        let source = indoc! {"
+            fun main() -> Int {
+                sha256(\"hello\") as Int
+            }
+        "};
+
+        let document = test_document(source);
+        let position = position_of(&document, "sha256", 0);
+
+        // Only the reference in this document is highlighted, not the definition in the standard library.
+        assert_eq!(
+            highlights(&document, position).unwrap(),
+            [DocumentHighlight {
+                range: range(1, 4, 10),
+                kind: Some(DocumentHighlightKind::READ),
+            }]
+        );
+        assert_eq!(definition(&document, &uri(), position), None);
+    }
+
+    #[test]
+    fn test_whitespace() {
+        let document = test_document(SOURCE);
+        let position = position_of(&document, "* other", 1);
+
+        assert_eq!(definition(&document, &uri(), position), None);
+        assert_eq!(references(&document, &uri(), position, true), None);
+        assert_eq!(highlights(&document, position), None);
+    }
+}
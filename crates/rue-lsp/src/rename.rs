@@ -0,0 +1,254 @@
+use std::collections::HashMap;
+
+use rue_compiler::Resolution;
+use rue_lexer::{Lexer, TokenKind};
+use rue_parser::SyntaxToken;
+use rue_typing::Type;
+use tower_lsp::jsonrpc::{Error, Result};
+use tower_lsp::lsp_types::{Position, PrepareRenameResponse, TextEdit, Url, WorkspaceEdit};
+
+use crate::document::Document;
+
+pub fn prepare_rename(
+    document: &Document,
+    position: Position,
+) -> Result<Option<PrepareRenameResponse>> {
+    let Some(token) = document.ident_at(position) else {
+        return Ok(None);
+    };
+    let Some(resolution) = document.resolve(&token) else {
+        return Ok(None);
+    };
+
+    renamable_definition(document, resolution)?;
+
+    Ok(Some(PrepareRenameResponse::Range(
+        document.range(token.text_range()),
+    )))
+}
+
+pub fn rename(
+    document: &Document,
+    uri: &Url,
+    position: Position,
+    new_name: &str,
+) -> Result<Option<WorkspaceEdit>> {
+    let Some(token) = document.ident_at(position) else {
+        return Ok(None);
+    };
+    let Some(resolution) = document.resolve(&token) else {
+        return Ok(None);
+    };
+
+    let definition = renamable_definition(document, resolution)?;
+
+    if !is_ident(new_name) {
+        return Err(Error::invalid_params(format!(
+            "`{new_name}` is not a valid identifier"
+        )));
+    }
+
+    if definition.text() != new_name && has_conflict(document, resolution, new_name) {
+        return Err(Error::invalid_params(format!(
+            "`{new_name}` is already defined in the same scope"
+        )));
+    }
+
+    let mut edits = vec![TextEdit {
+        range: document.range(definition.text_range()),
+        new_text: new_name.to_string(),
+    }];
+
+    for reference in document.references_to(resolution) {
+        edits.push(TextEdit {
+            range: document.range(reference.text_range()),
+            new_text: new_name.to_string(),
+        });
+    }
+
+    Ok(Some(WorkspaceEdit {
+        changes: Some(HashMap::from([(uri.clone(), edits)])),
+        ..Default::default()
+    }))
+}
+
+/// Only items defined in this document can be renamed, since the standard library and builtins are fixed.
+fn renamable_definition(document: &Document, resolution: Resolution) -> Result<SyntaxToken> {
+    let definition = document
+        .definition(resolution)
+        .ok_or_else(|| Error::invalid_params("Cannot rename builtin items"))?;
+
+    if !document.is_local(&definition) {
+        return Err(Error::invalid_params(
+            "Cannot rename items from the standard library",
+        ));
+    }
+
+    Ok(definition)
+}
+
+/// Whether the new name is already taken by something else in the scope which defines the item.
+fn has_conflict(document: &Document, resolution: Resolution, new_name: &str) -> bool {
+    let db = &document.analysis.db;
+
+    match resolution {
+        Resolution::Symbol(symbol_id) => {
+            let defined = db
+                .scopes()
+                .filter(|(_, scope)| scope.is_local(symbol_id))
+                .any(|(_, scope)| scope.symbol(new_name).is_some());
+
+            // A reference would resolve to something else if an inner scope already uses the name.
+            let shadowed = document
+                .references_to(resolution)
+                .iter()
+                .filter(|token| document.is_local(token))
+                .any(|token| {
+                    let scope_ids = document.visible_scopes(token.text_range().start());
+
+                    scope_ids
+                        .iter()
+                        .skip_while(|&&scope_id| !db.scope(scope_id).is_local(symbol_id))
+                        .skip(1)
+                        .any(|&scope_id| db.scope(scope_id).symbol(new_name).is_some())
+                });
+
+            defined || shadowed
+        }
+        Resolution::Type(type_id) => {
+            if let Type::Variant(variant) = document.analysis.ty.get_raw(type_id) {
+                if let Type::Enum(enum_type) =
+                    document.analysis.ty.get(variant.original_enum_type_id)
+                {
+                    return enum_type.variants.contains_key(new_name);
+                }
+            }
+
+            db.scopes()
+                .filter(|(_, scope)| scope.local_types().contains(&type_id))
+                .any(|(_, scope)| scope.ty(new_name).is_some())
+        }
+        Resolution::Field(type_id, _) => document
+            .fields(type_id)
+            .iter()
+            .any(|(name, _)| name == new_name),
+    }
+}
+
+fn is_ident(name: &str) -> bool {
+    let tokens: Vec<_> = Lexer::new(name).collect();
+    matches!(tokens.as_slice(), [token] if token.kind() == TokenKind::Ident && token.len() == name.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use indoc::indoc;
+
+    use crate::document::{apply_edits, position_of, test_document};
+
+    use super::*;
+
+    fn uri() -> Url {
+        Url::parse("file:///main.rue").unwrap()
+    }
+
+    #[test]
+    fn test_rename_let() {
+        let source = indoc! {"
+            fun main() -> Int {
+                let value = 42;
+                let other = value + 1;
+                value * other
+            }
+        "};
+
+        let document = test_document(source);
+        let position = position_of(&document, "value +", 2);
+
+        let edit = rename(&document, &uri(), position, "amount")
+            .unwrap()
+            .unwrap();
+        let changes = edit.changes.unwrap();
+
+        assert_eq!(changes[&uri()].len(), 3);
+        assert_eq!(
+            apply_edits(&document, &changes[&uri()]),
+            indoc! {"
+                fun main() -> Int {
+                    let amount = 42;
+                    let other = amount + 1;
+                    amount * other
+                }
+            "}
+        );
+    }
+
+    #[test]
+    fn test_rename_struct_field() {
+        let source = indoc! {"
+            struct Point {
+                x: Int,
+                y: Int,
+            }
+
+            fun main() -> Int {
+                let point = Point { x: 1, y: 2 };
+                point.x + point.y
+            }
+        "};
+
+        let document = test_document(source);
+        let position = position_of(&document, "point.x", 6);
+
+        let edit = rename(&document, &uri(), position, "horizontal")
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(
+            apply_edits(&document, &edit.changes.unwrap()[&uri()]),
+            indoc! {"
+                struct Point {
+                    horizontal: Int,
+                    y: Int,
+                }
+
+                fun main() -> Int {
+                    let point = Point { horizontal: 1, y: 2 };
+                    point.horizontal + point.y
+                }
+            "}
+        );
+    }
+
+    #[test]
+    fn test_rename_conflict() {
+        let source = indoc! {"
+            fun main() -> Int {
+                let value = 42;
+                let other = value + 1;
+                value * other
+            }
+        "};
+
+        let document = test_document(source);
+        let position = position_of(&document, "value", 0);
+
+        assert!(rename(&document, &uri(), position, "other").is_err());
+        assert!(rename(&document, &uri(), position, "not valid").is_err());
+    }
+
+    #[test]
+    fn test_rename_standard_library() {
+        let source = indoc! {"
+            fun main() -> Int {
+                sha256(\"hello\") as Int
+            }
+        "};
+
+        let document = test_document(source);
+        let position = position_of(&document, "sha256", 0);
+
+        assert!(prepare_rename(&document, position).is_err());
+        assert!(rename(&document, &uri(), position, "hash").is_err());
+    }
+}
@@ -0,0 +1,296 @@
+use std::collections::HashSet;
+
+use rowan::TextRange;
+use rue_compiler::{Resolution, Symbol};
+use rue_typing::Type;
+use tower_lsp::lsp_types::{
+    SemanticToken, SemanticTokenModifier, SemanticTokenType, SemanticTokens, SemanticTokensLegend,
+};
+
+use crate::document::Document;
+
+const TOKEN_TYPES: &[SemanticTokenType] = &[
+    SemanticTokenType::FUNCTION,
+    SemanticTokenType::PARAMETER,
+    SemanticTokenType::VARIABLE,
+    SemanticTokenType::TYPE,
+    SemanticTokenType::STRUCT,
+    SemanticTokenType::ENUM,
+    SemanticTokenType::ENUM_MEMBER,
+    SemanticTokenType::PROPERTY,
+    SemanticTokenType::NAMESPACE,
+    SemanticTokenType::TYPE_PARAMETER,
+];
+
+const DECLARATION: u32 = 1 << 0;
+const READONLY: u32 = 1 << 1;
+const INLINE: u32 = 1 << 2;
+const EXPORT: u32 = 1 << 3;
+const UNUSED: u32 = 1 << 4;
+
+pub fn legend() -> SemanticTokensLegend {
+    SemanticTokensLegend {
+        token_types: TOKEN_TYPES.to_vec(),
+        token_modifiers: vec![
+            SemanticTokenModifier::DECLARATION,
+            SemanticTokenModifier::READONLY,
+            SemanticTokenModifier::new("inline"),
+            SemanticTokenModifier::new("export"),
+            SemanticTokenModifier::new("unused"),
+        ],
+    }
+}
+
+/// Classifies every name in the document by what it resolves to, optionally limited to a range.
+pub fn semantic_tokens(document: &Document, range: Option<TextRange>) -> SemanticTokens {
+    let db = &document.analysis.db;
+
+    let mut names = Vec::new();
+
+    for &symbol_id in &db.named_symbols() {
+        names.extend(
+            db.symbol_token(symbol_id)
+                .map(|token| (token, Resolution::Symbol(symbol_id), DECLARATION)),
+        );
+    }
+
+    for &type_id in &db.named_types() {
+        names.extend(
+            db.type_token(type_id)
+                .map(|token| (token, Resolution::Type(type_id), DECLARATION)),
+        );
+    }
+
+    for (type_id, index) in db.named_fields() {
+        names.extend(
+            db.field_token(type_id, index)
+                .map(|token| (token, Resolution::Field(type_id, index), DECLARATION)),
+        );
+    }
+
+    let referenced: HashSet<Resolution> = db
+        .references()
+        .iter()
+        .map(|&(_, resolution)| resolution)
+        .collect();
+
+    names.extend(
+        db.references()
+            .iter()
+            .map(|(token, resolution)| (token, *resolution, 0)),
+    );
+
+    let exported = exported(document);
+
+    let mut classified: Vec<(TextRange, u32, u32)> = names
+        .into_iter()
+        .filter(|(token, _, _)| document.is_local(token))
+        .filter(|(token, _, _)| range.is_none_or(|range| range.contains_range(token.text_range())))
+        .filter_map(|(token, resolution, mut modifiers)| {
+            let (token_type, extra) = classify(document, resolution)?;
+            modifiers |= extra;
+
+            if exported.contains(&resolution) {
+                modifiers |= EXPORT;
+            } else if !referenced.contains(&resolution)
+                && Some(resolution) != document.analysis.main_symbol_id.map(Resolution::Symbol)
+            {
+                modifiers |= UNUSED;
+            }
+
+            Some((token.text_range(), token_type, modifiers))
+        })
+        .collect();
+
+    classified.sort_by_key(|&(range, _, _)| range.start());
+    classified.dedup_by_key(|&mut (range, _, _)| range);
+
+    let mut data = Vec::new();
+    let mut previous_line = 0;
+    let mut previous_start = 0;
+
+    for (text_range, token_type, modifiers) in classified {
+        let range = document.range(text_range);
+        let line = range.start.line;
+        let start = range.start.character;
+
+        let delta_start = if line == previous_line {
+            start - previous_start
+        } else {
+            start
+        };
+
+        data.push(SemanticToken {
+            delta_line: line - previous_line,
+            delta_start,
+            length: range.end.character - start,
+            token_type,
+            token_modifiers_bitset: modifiers,
+        });
+
+        previous_line = line;
+        previous_start = start;
+    }
+
+    SemanticTokens {
+        result_id: None,
+        data,
+    }
+}
+
+/// The index of the token type in the legend, and any modifiers implied by the kind of item.
+fn classify(document: &Document, resolution: Resolution) -> Option<(u32, u32)> {
+    let token_type = |kind: SemanticTokenType| {
+        TOKEN_TYPES
+            .iter()
+            .position(|other| *other == kind)
+            .and_then(|index| u32::try_from(index).ok())
+    };
+
+    Some(match resolution {
+        Resolution::Symbol(symbol_id) => match document.analysis.db.symbol(symbol_id) {
+            Symbol::Unknown => return None,
+            Symbol::Module(..) => (token_type(SemanticTokenType::NAMESPACE)?, 0),
+            Symbol::Function(..) => (token_type(SemanticTokenType::FUNCTION)?, 0),
+            Symbol::InlineFunction(..) => (token_type(SemanticTokenType::FUNCTION)?, INLINE),
+            Symbol::Parameter(..) => (token_type(SemanticTokenType::PARAMETER)?, 0),
+            Symbol::Let(..) => (token_type(SemanticTokenType::VARIABLE)?, 0),
+            Symbol::Const(..) => (token_type(SemanticTokenType::VARIABLE)?, READONLY),
+            Symbol::InlineConst(..) => {
+                (token_type(SemanticTokenType::VARIABLE)?, READONLY | INLINE)
+            }
+        },
+        Resolution::Type(type_id) => match document.analysis.ty.get_raw(type_id) {
+            Type::Struct(..) => (token_type(SemanticTokenType::STRUCT)?, 0),
+            Type::Enum(..) => (token_type(SemanticTokenType::ENUM)?, 0),
+            Type::Variant(..) => (token_type(SemanticTokenType::ENUM_MEMBER)?, 0),
+            Type::Generic => (token_type(SemanticTokenType::TYPE_PARAMETER)?, 0),
+            _ => (token_type(SemanticTokenType::TYPE)?, 0),
+        },
+        Resolution::Field(..) => (token_type(SemanticTokenType::PROPERTY)?, 0),
+    })
+}
+
+/// Every symbol and type which is exported from a module.
+fn exported(document: &Document) -> HashSet<Resolution> {
+    let db = &document.analysis.db;
+
+    let mut exported = HashSet::new();
+
+    for symbol_id in db
+        .named_symbols()
+        .into_iter()
+        .chain([document.analysis.main_module_id])
+    {
+        let Symbol::Module(module) = db.symbol(symbol_id) else {
+            continue;
+        };
+
+        exported.extend(
+            module
+                .exported_symbols
+                .iter()
+                .map(|&symbol_id| Resolution::Symbol(symbol_id)),
+        );
+        exported.extend(
+            module
+                .exported_types
+                .iter()
+                .map(|&type_id| Resolution::Type(type_id)),
+        );
+    }
+
+    exported
+}
+
+#[cfg(test)]
+mod tests {
+    use indoc::indoc;
+    use tower_lsp::lsp_types::Position;
+
+    use crate::document::test_document;
+
+    use super::*;
+
+    /// The tokens with absolute positions, as `(line, start, length, type, modifiers)`.
+    fn decode(tokens: &SemanticTokens) -> Vec<(u32, u32, u32, SemanticTokenType, u32)> {
+        let mut line = 0;
+        let mut start = 0;
+
+        tokens
+            .data
+            .iter()
+            .map(|token| {
+                if token.delta_line > 0 {
+                    start = 0;
+                }
+                line += token.delta_line;
+                start += token.delta_start;
+
+                (
+                    line,
+                    start,
+                    token.length,
+                    TOKEN_TYPES[token.token_type as usize].clone(),
+                    token.token_modifiers_bitset,
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_semantic_tokens() {
+        let source = indoc! {"
+            struct Point {
+                x: Int,
+            }
+
+            const SCALE: Int = 2;
+
+            fun main(point: Point) -> Int {
+                let unused = 1;
+                point.x * SCALE
+            }
+        "};
+
+        let document = test_document(source);
+
+        assert_eq!(
+            decode(&semantic_tokens(&document, None)),
+            [
+                (0, 7, 5, SemanticTokenType::STRUCT, DECLARATION),
+                (1, 4, 1, SemanticTokenType::PROPERTY, DECLARATION),
+                (1, 7, 3, SemanticTokenType::TYPE, 0),
+                (4, 6, 5, SemanticTokenType::VARIABLE, DECLARATION | READONLY),
+                (4, 13, 3, SemanticTokenType::TYPE, 0),
+                (6, 4, 4, SemanticTokenType::FUNCTION, DECLARATION | EXPORT),
+                (6, 9, 5, SemanticTokenType::PARAMETER, DECLARATION),
+                (6, 16, 5, SemanticTokenType::STRUCT, 0),
+                (6, 26, 3, SemanticTokenType::TYPE, 0),
+                (7, 8, 6, SemanticTokenType::VARIABLE, DECLARATION | UNUSED),
+                (8, 4, 5, SemanticTokenType::PARAMETER, 0),
+                (8, 10, 1, SemanticTokenType::PROPERTY, 0),
+                (8, 14, 5, SemanticTokenType::VARIABLE, READONLY),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_semantic_tokens_range() {
+        let source = indoc! {"
+            fun main() -> Int {
+                let value = 42;
+                value
+            }
+        "};
+
+        let document = test_document(source);
+        let start = document.offset(Position::new(2, 0)).unwrap();
+        let end = document.offset(Position::new(3, 0)).unwrap();
+
+        // Tokens are only included if they are entirely within the range, and the first is relative to the start.
+        let tokens = semantic_tokens(&document, Some(TextRange::new(start, end)));
+        assert_eq!(decode(&tokens), [(2, 4, 5, SemanticTokenType::VARIABLE, 0)]);
+        assert_eq!(tokens.data[0].delta_line, 2);
+    }
+}
@@ -0,0 +1,268 @@
+use rowan::TextSize;
+use rue_compiler::Resolution;
+use rue_parser::{
+    AstNode, Expr, FunctionCallExpr, InitializerExpr, PathItem, SyntaxKind, SyntaxNode,
+};
+use tower_lsp::lsp_types::{
+    ParameterInformation, ParameterLabel, Position, SignatureHelp, SignatureInformation,
+};
+
+use crate::document::Document;
+
+pub fn signature_help(document: &Document, position: Position) -> Option<SignatureHelp> {
+    let offset = document.offset(position)?;
+    let token = document
+        .root
+        .syntax()
+        .token_at_offset(offset)
+        .left_biased()?;
+
+    // The innermost call or initializer which is still open at the cursor is used.
+    for node in token.parent_ancestors() {
+        if let Some(call) = FunctionCallExpr::cast(node.clone()) {
+            if is_open(&node, offset, SyntaxKind::OpenParen, SyntaxKind::CloseParen) {
+                return call_help(document, &call, offset);
+            }
+        } else if let Some(initializer) = InitializerExpr::cast(node.clone()) {
+            if is_open(&node, offset, SyntaxKind::OpenBrace, SyntaxKind::CloseBrace) {
+                return initializer_help(document, &initializer, offset);
+            }
+        }
+    }
+
+    None
+}
+
+fn call_help(
+    document: &Document,
+    call: &FunctionCallExpr,
+    offset: TextSize,
+) -> Option<SignatureHelp> {
+    let callee = call.callee()?;
+    let type_id = document.analysis.db.expr_type(callee.syntax())?;
+    let signature = document.signature(type_id)?;
+
+    let name = match &callee {
+        Expr::PathExpr(path) => path
+            .items()
+            .last()
+            .and_then(PathItem::name)
+            .map_or_else(String::new, |name| name.to_string()),
+        _ => String::new(),
+    };
+
+    let len = signature.parameters.len();
+
+    let parameters = signature
+        .parameters
+        .into_iter()
+        .enumerate()
+        .map(|(index, (name, ty))| {
+            let spread = if signature.spread && index + 1 == len {
+                "..."
+            } else {
+                ""
+            };
+            format!("{spread}{name}: {ty}")
+        })
+        .collect();
+
+    // Every argument after the last parameter is part of the spread parameter.
+    let mut active = commas_before(call.syntax(), offset);
+    if signature.spread {
+        active = active.min(len.saturating_sub(1));
+    }
+
+    Some(help(
+        &format!("fun {name}("),
+        parameters,
+        &format!(") -> {}", signature.return_type),
+        active,
+    ))
+}
+
+fn initializer_help(
+    document: &Document,
+    initializer: &InitializerExpr,
+    offset: TextSize,
+) -> Option<SignatureHelp> {
+    let name = initializer.path()?.items().last()?.name()?;
+
+    let Resolution::Type(type_id) = document.resolve(&name)? else {
+        return None;
+    };
+
+    let parameters = document
+        .fields(type_id)
+        .into_iter()
+        .map(|(name, type_id)| format!("{name}: {}", document.type_name(type_id)))
+        .collect();
+
+    Some(help(
+        &format!("{name} {{ "),
+        parameters,
+        " }",
+        commas_before(initializer.syntax(), offset),
+    ))
+}
+
+/// Whether the cursor is after the opening delimiter, and the node hasn't been closed before it.
+fn is_open(node: &SyntaxNode, offset: TextSize, open: SyntaxKind, close: SyntaxKind) -> bool {
+    let mut opened = false;
+
+    for token in node
+        .children_with_tokens()
+        .filter_map(rowan::NodeOrToken::into_token)
+    {
+        if token.kind() == open && token.text_range().end() <= offset {
+            opened = true;
+        } else if token.kind() == close && token.text_range().end() <= offset {
+            return false;
+        }
+    }
+
+    opened
+}
+
+fn commas_before(node: &SyntaxNode, offset: TextSize) -> usize {
+    node.children_with_tokens()
+        .filter(|item| item.kind() == SyntaxKind::Comma && item.text_range().end() <= offset)
+        .count()
+}
+
+/// Builds the signature label, with the offsets of each parameter so that the active one can be highlighted.
+fn help(prefix: &str, parameters: Vec<String>, suffix: &str, active: usize) -> SignatureHelp {
+    let mut label = prefix.to_string();
+    let mut information = Vec::new();
+
+    for (index, parameter) in parameters.into_iter().enumerate() {
+        if index > 0 {
+            label.push_str(", ");
+        }
+
+        let start = utf16_len(&label);
+        label.push_str(&parameter);
+        let end = utf16_len(&label);
+
+        information.push(ParameterInformation {
+            label: ParameterLabel::LabelOffsets([start, end]),
+            documentation: None,
+        });
+    }
+
+    label.push_str(suffix);
+
+    let active = u32::try_from(active).ok();
+
+    SignatureHelp {
+        signatures: vec![SignatureInformation {
+            label,
+            documentation: None,
+            parameters: Some(information),
+            active_parameter: active,
+        }],
+        active_signature: Some(0),
+        active_parameter: active,
+    }
+}
+
+fn utf16_len(text: &str) -> u32 {
+    u32::try_from(text.encode_utf16().count()).unwrap_or(u32::MAX)
+}
+
+#[cfg(test)]
+mod tests {
+    use indoc::indoc;
+
+    use crate::document::{position_of, test_document};
+
+    use super::*;
+
+    const SOURCE: &str = indoc! {"
+        struct Point {
+            x: Int,
+            y: Int,
+        }
+
+        fun add(left: Int, right: Int) -> Int {
+            left + right
+        }
+
+        type Amounts = List<Int>;
+
+        fun sum(...values: Amounts) -> Int {
+            0
+        }
+
+        fun main() -> Int {
+            let point = Point { x: 1, y: 2 };
+            add(point.x, add(1, 2)) + sum(1, 2, 3)
+        }
+    "};
+
+    /// The label and active parameter at the offset into the first occurrence of the text.
+    fn active(needle: &str, offset: usize) -> Option<(String, Option<u32>)> {
+        let document = test_document(SOURCE);
+        let position = position_of(&document, needle, offset);
+        let mut help = signature_help(&document, position)?;
+        let signature = help.signatures.remove(0);
+        Some((signature.label, help.active_parameter))
+    }
+
+    #[test]
+    fn test_call_help() {
+        let label = "fun add(left: Int, right: Int) -> Int".to_string();
+
+        assert_eq!(active("add(point", 4), Some((label.clone(), Some(0))));
+        assert_eq!(active("add(1, 2)", 6), Some((label.clone(), Some(1))));
+
+        // The innermost call is used, and the outer call is used again after it's closed.
+        assert_eq!(active("add(1, 2)", 4), Some((label.clone(), Some(0))));
+        assert_eq!(active("add(1, 2))", 9), Some((label, Some(1))));
+    }
+
+    #[test]
+    fn test_spread_help() {
+        let label = "fun sum(...values: Amounts) -> Int".to_string();
+
+        assert_eq!(active("sum(1", 4), Some((label.clone(), Some(0))));
+        assert_eq!(active("2, 3)", 3), Some((label, Some(0))));
+    }
+
+    #[test]
+    fn test_initializer_help() {
+        let label = "Point { x: Int, y: Int }".to_string();
+
+        assert_eq!(active("Point { x: 1", 8), Some((label.clone(), Some(0))));
+        assert_eq!(active("y: 2 }", 0), Some((label, Some(1))));
+    }
+
+    #[test]
+    fn test_offsets() {
+        let document = test_document(SOURCE);
+        let position = position_of(&document, "add(point", 4);
+        let help = signature_help(&document, position).unwrap();
+
+        let offsets: Vec<_> = help.signatures[0]
+            .parameters
+            .iter()
+            .flatten()
+            .map(|parameter| parameter.label.clone())
+            .collect();
+
+        assert_eq!(
+            offsets,
+            [
+                ParameterLabel::LabelOffsets([8, 17]),
+                ParameterLabel::LabelOffsets([19, 29]),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_outside_call() {
+        assert_eq!(active("add(point", 0), None);
+        assert_eq!(active("sum(1, 2, 3)", 12), None);
+        assert_eq!(active("let point", 0), None);
+    }
+}
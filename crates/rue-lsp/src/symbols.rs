@@ -0,0 +1,218 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use rowan::TextRange;
+use rue_compiler::trimmed_range;
+use rue_parser::{AstNode, Item, Root, StructField, SyntaxToken};
+use tower_lsp::lsp_types::{DocumentSymbol, Location, SymbolInformation, SymbolKind, Url};
+
+use crate::line_index::LineIndex;
+
+/// An item in the outline of a file, which is built from the syntax tree alone.
+struct Outline {
+    name: SyntaxToken,
+    kind: SymbolKind,
+    detail: Option<String>,
+    range: TextRange,
+    children: Vec<Outline>,
+}
+
+pub fn document_symbols(text: &str, root: &Root) -> Vec<DocumentSymbol> {
+    let line_index = LineIndex::new(text);
+
+    outline(&root.items())
+        .into_iter()
+        .map(|outline| document_symbol(text, &line_index, outline))
+        .collect()
+}
+
+#[allow(deprecated)]
+fn document_symbol(text: &str, line_index: &LineIndex, outline: Outline) -> DocumentSymbol {
+    DocumentSymbol {
+        name: outline.name.to_string(),
+        detail: outline.detail,
+        kind: outline.kind,
+        tags: None,
+        deprecated: None,
+        range: line_index.range(text, outline.range),
+        selection_range: line_index.range(text, outline.name.text_range()),
+        children: Some(
+            outline
+                .children
+                .into_iter()
+                .map(|child| document_symbol(text, line_index, child))
+                .collect(),
+        ),
+    }
+}
+
+/// Every item in the file, which is searched by name for workspace symbols.
+pub fn file_symbols(uri: &Url, text: &str, root: &Root) -> Vec<SymbolInformation> {
+    let line_index = LineIndex::new(text);
+
+    let mut symbols = Vec::new();
+
+    let mut stack: Vec<(Option<String>, Outline)> = outline(&root.items())
+        .into_iter()
+        .map(|outline| (None, outline))
+        .collect();
+
+    while let Some((container, outline)) = stack.pop() {
+        let name = outline.name.to_string();
+
+        stack.extend(
+            outline
+                .children
+                .into_iter()
+                .map(|child| (Some(name.clone()), child)),
+        );
+
+        #[allow(deprecated)]
+        symbols.push(SymbolInformation {
+            name,
+            kind: outline.kind,
+            tags: None,
+            deprecated: None,
+            location: Location {
+                uri: uri.clone(),
+                range: line_index.range(text, outline.name.text_range()),
+            },
+            container_name: container,
+        });
+    }
+
+    symbols
+}
+
+/// Finds every `.rue` file in the directory and its subdirectories, skipping hidden and build directories.
+pub fn find_files(dir: &Path, files: &mut Vec<PathBuf>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let name = entry.file_name();
+        let name = name.to_string_lossy();
+
+        if path.is_dir() {
+            if !name.starts_with('.') && name != "target" {
+                find_files(&path, files);
+            }
+        } else if path.extension().is_some_and(|extension| extension == "rue") {
+            files.push(path);
+        }
+    }
+}
+
+fn outline(items: &[Item]) -> Vec<Outline> {
+    let mut outlines = Vec::new();
+
+    for item in items {
+        let range = trimmed_range(item.syntax());
+
+        let (name, kind, detail, children) = match item {
+            Item::ModuleItem(module) => (
+                module.name(),
+                SymbolKind::MODULE,
+                None,
+                outline(&module.items()),
+            ),
+            Item::FunctionItem(function) => {
+                let params: Vec<String> = function
+                    .params()
+                    .iter()
+                    .map(|param| param.syntax().text().to_string().trim().to_string())
+                    .collect();
+
+                let return_type = function.return_type().map_or_else(String::new, |ty| {
+                    format!(" -> {}", ty.syntax().text().to_string().trim())
+                });
+
+                (
+                    function.name(),
+                    SymbolKind::FUNCTION,
+                    Some(format!("({}){return_type}", params.join(", "))),
+                    Vec::new(),
+                )
+            }
+            Item::TypeAliasItem(alias) => (
+                alias.name(),
+                SymbolKind::TYPE_PARAMETER,
+                alias
+                    .ty()
+                    .map(|ty| ty.syntax().text().to_string().trim().to_string()),
+                Vec::new(),
+            ),
+            Item::ConstItem(const_item) => (
+                const_item.name(),
+                SymbolKind::CONSTANT,
+                const_item
+                    .ty()
+                    .map(|ty| ty.syntax().text().to_string().trim().to_string()),
+                Vec::new(),
+            ),
+            Item::StructItem(struct_item) => (
+                struct_item.name(),
+                SymbolKind::STRUCT,
+                None,
+                fields(struct_item.fields()),
+            ),
+            Item::EnumItem(enum_item) => (
+                enum_item.name(),
+                SymbolKind::ENUM,
+                None,
+                enum_item
+                    .variants()
+                    .into_iter()
+                    .filter_map(|variant| {
+                        Some(Outline {
+                            name: variant.name()?,
+                            kind: SymbolKind::ENUM_MEMBER,
+                            detail: None,
+                            range: trimmed_range(variant.syntax()),
+                            children: variant
+                                .fields()
+                                .map(|fields_node| fields(fields_node.fields()))
+                                .unwrap_or_default(),
+                        })
+                    })
+                    .collect(),
+            ),
+            Item::ImportItem(..) => continue,
+        };
+
+        let Some(name) = name else {
+            continue;
+        };
+
+        outlines.push(Outline {
+            name,
+            kind,
+            detail,
+            range,
+            children,
+        });
+    }
+
+    outlines
+}
+
+fn fields(fields: Vec<StructField>) -> Vec<Outline> {
+    fields
+        .into_iter()
+        .filter_map(|field| {
+            Some(Outline {
+                name: field.name()?,
+                kind: SymbolKind::FIELD,
+                detail: field
+                    .ty()
+                    .map(|ty| ty.syntax().text().to_string().trim().to_string()),
+                range: trimmed_range(field.syntax()),
+                children: Vec::new(),
+            })
+        })
+        .collect()
+}
@@ -0,0 +1,409 @@
+use std::{
+    collections::{HashMap, HashSet},
+    fs,
+    panic::{self, AssertUnwindSafe},
+    path::{Path, PathBuf},
+    sync::mpsc,
+    thread,
+    time::SystemTime,
+};
+
+use rue_compiler::{compile_standard_library, find_manifest, CompiledStandardLibrary, LintConfig};
+use rue_parser::{parse, ParserError, Root};
+use tokio::sync::oneshot;
+use tower_lsp::lsp_types::{
+    DocumentSymbol, SymbolInformation, TextDocumentContentChangeEvent, Url,
+};
+
+use crate::{
+    document::Document,
+    line_index::LineIndex,
+    symbols::{document_symbols, file_symbols, find_files},
+};
+
+type Job = Box<dyn FnOnce(&mut Workspace) + Send>;
+
+/// The open documents, along with the standard library they are analyzed against.
+///
+/// Syntax trees can't be sent across threads, so the workspace is owned by a dedicated analysis thread.
+/// Each document is only parsed and analyzed when it's first queried after a change, and the results are cached.
+/// Editing a document invalidates its own results, and changing a manifest invalidates every document it configures.
+pub struct Workspace {
+    stdlib: CompiledStandardLibrary,
+    documents: HashMap<Url, OpenDocument>,
+    manifests: HashMap<PathBuf, LintConfig>,
+    folders: Vec<PathBuf>,
+    files: HashMap<PathBuf, FileSymbols>,
+}
+
+struct OpenDocument {
+    text: String,
+    version: i32,
+    manifest: Option<PathBuf>,
+    parse: Option<(Root, Vec<ParserError>)>,
+    document: Option<Document>,
+    symbols: Option<Vec<SymbolInformation>>,
+}
+
+impl OpenDocument {
+    /// The syntax tree, which is reused until the text changes.
+    fn parse(&mut self) -> &(Root, Vec<ParserError>) {
+        self.parse.get_or_insert_with(|| parse(&self.text))
+    }
+}
+
+/// The symbols of a file in the workspace folders, which are reread when it's modified on disk.
+struct FileSymbols {
+    modified: SystemTime,
+    symbols: Vec<SymbolInformation>,
+}
+
+impl Workspace {
+    fn new() -> Self {
+        Self {
+            stdlib: compile_standard_library(),
+            documents: HashMap::new(),
+            manifests: HashMap::new(),
+            folders: Vec::new(),
+            files: HashMap::new(),
+        }
+    }
+
+    pub fn open(&mut self, uri: Url, text: String, version: i32) {
+        let manifest = manifest_path(&uri);
+
+        self.documents.insert(
+            uri,
+            OpenDocument {
+                text,
+                version,
+                manifest,
+                parse: None,
+                document: None,
+                symbols: None,
+            },
+        );
+    }
+
+    /// Applies the changes in order, and invalidates everything which was cached for the document.
+    pub fn change(
+        &mut self,
+        uri: &Url,
+        changes: Vec<TextDocumentContentChangeEvent>,
+        version: i32,
+    ) {
+        let Some(open) = self.documents.get_mut(uri) else {
+            return;
+        };
+
+        for change in changes {
+            let Some(range) = change.range else {
+                open.text = change.text;
+                continue;
+            };
+
+            let line_index = LineIndex::new(&open.text);
+            let start = line_index.offset(&open.text, range.start);
+            let end = line_index.offset(&open.text, range.end);
+
+            if let (Some(start), Some(end)) = (start, end) {
+                open.text.replace_range(start..end.max(start), &change.text);
+            }
+        }
+
+        open.version = version;
+        open.parse = None;
+        open.document = None;
+        open.symbols = None;
+    }
+
+    /// Reloads a manifest which was created, changed, or deleted on disk.
+    /// Returns the open documents which need to be checked again, along with their versions.
+    pub fn change_manifest(&mut self, path: &Path) -> Vec<(Url, i32)> {
+        let path = canonical_manifest(path);
+
+        if let Some(path) = &path {
+            self.manifests.remove(path);
+        }
+
+        let mut changed = Vec::new();
+
+        for (uri, open) in &mut self.documents {
+            // A manifest which was created or deleted can change which one applies to the document.
+            let manifest = manifest_path(uri);
+
+            if manifest == open.manifest && (path.is_none() || open.manifest != path) {
+                continue;
+            }
+
+            // The syntax tree is kept, since only the lints can have changed.
+            open.manifest = manifest;
+            open.document = None;
+            changed.push((uri.clone(), open.version));
+        }
+
+        changed
+    }
+
+    pub fn close(&mut self, uri: &Url) {
+        self.documents.remove(uri);
+    }
+
+    pub fn version(&self, uri: &Url) -> Option<i32> {
+        self.documents.get(uri).map(|open| open.version)
+    }
+
+    pub fn text(&self, uri: &Url) -> Option<&str> {
+        self.documents.get(uri).map(|open| open.text.as_str())
+    }
+
+    /// The outline of the document, which only needs the syntax tree.
+    pub fn document_symbols(&mut self, uri: &Url) -> Option<Vec<DocumentSymbol>> {
+        let open = self.documents.get_mut(uri)?;
+        let (root, _) = open.parse();
+        let root = root.clone();
+        Some(document_symbols(&open.text, &root))
+    }
+
+    /// The analyzed document, which is reused until the text or its manifest changes.
+    pub fn document(&mut self, uri: &Url) -> Option<&Document> {
+        let Self {
+            stdlib,
+            documents,
+            manifests,
+            ..
+        } = self;
+
+        let open = documents.get_mut(uri)?;
+
+        if open.document.is_none() {
+            // An invalid manifest is reported by the CLI, so the defaults are used here instead.
+            let lints = match &open.manifest {
+                Some(path) => manifests
+                    .entry(path.clone())
+                    .or_insert_with(|| {
+                        fs::read_to_string(path)
+                            .ok()
+                            .and_then(|source| LintConfig::from_manifest(&source).ok())
+                            .unwrap_or_default()
+                    })
+                    .clone(),
+                None => LintConfig::default(),
+            };
+
+            let (root, parser_errors) = open.parse().clone();
+
+            open.document = Some(Document::new(
+                open.text.clone(),
+                root,
+                parser_errors,
+                stdlib,
+                &lints,
+            ));
+        }
+
+        open.document.as_ref()
+    }
+
+    pub fn set_folders(&mut self, folders: Vec<PathBuf>) {
+        self.folders = folders;
+        self.files.clear();
+    }
+
+    /// Searches the items of every file for names containing the query, ignoring case.
+    /// Open documents are searched instead of their files, since they may have unsaved changes.
+    pub fn symbols(&mut self, query: &str) -> Vec<SymbolInformation> {
+        let query = query.to_lowercase();
+        let is_match = |symbol: &&SymbolInformation| symbol.name.to_lowercase().contains(&query);
+
+        let mut paths = Vec::new();
+
+        for folder in &self.folders {
+            find_files(folder, &mut paths);
+        }
+
+        let existing: HashSet<&PathBuf> = paths.iter().collect();
+        self.files.retain(|path, _| existing.contains(path));
+
+        let mut symbols = Vec::new();
+
+        for path in &paths {
+            let Ok(uri) = Url::from_file_path(path) else {
+                continue;
+            };
+
+            if self.documents.contains_key(&uri) {
+                continue;
+            }
+
+            let Ok(modified) = fs::metadata(path).and_then(|metadata| metadata.modified()) else {
+                continue;
+            };
+
+            if self
+                .files
+                .get(path)
+                .is_none_or(|file| file.modified != modified)
+            {
+                let Ok(text) = fs::read_to_string(path) else {
+                    continue;
+                };
+                let (root, _) = parse(&text);
+
+                self.files.insert(
+                    path.clone(),
+                    FileSymbols {
+                        modified,
+                        symbols: file_symbols(&uri, &text, &root),
+                    },
+                );
+            }
+
+            symbols.extend(self.files[path].symbols.iter().filter(is_match).cloned());
+        }
+
+        for (uri, open) in &mut self.documents {
+            if open.symbols.is_none() {
+                let (root, _) = open.parse().clone();
+                open.symbols = Some(file_symbols(uri, &open.text, &root));
+            }
+            let file = open.symbols.as_deref().unwrap_or_default();
+
+            symbols.extend(file.iter().filter(is_match).cloned());
+        }
+
+        symbols
+    }
+}
+
+/// The manifest which configures the lints of the document, if it's a file with one.
+fn manifest_path(uri: &Url) -> Option<PathBuf> {
+    find_manifest(&uri.to_file_path().ok()?)
+}
+
+/// The manifest path as it would be found from a source file, which may no longer exist.
+fn canonical_manifest(path: &Path) -> Option<PathBuf> {
+    Some(path.parent()?.canonicalize().ok()?.join(path.file_name()?))
+}
+
+/// A handle to the analysis thread, which runs jobs against the workspace one at a time.
+#[derive(Debug, Clone)]
+pub struct WorkspaceHandle {
+    sender: mpsc::Sender<Job>,
+}
+
+impl WorkspaceHandle {
+    pub fn spawn() -> Self {
+        let (sender, receiver) = mpsc::channel::<Job>();
+
+        thread::spawn(move || {
+            let mut workspace = Workspace::new();
+
+            // A bug in one request shouldn't take down the rest of the language server.
+            for job in receiver {
+                panic::catch_unwind(AssertUnwindSafe(|| job(&mut workspace))).ok();
+            }
+        });
+
+        Self { sender }
+    }
+
+    pub async fn run<T>(&self, f: impl FnOnce(&mut Workspace) -> T + Send + 'static) -> Option<T>
+    where
+        T: Send + 'static,
+    {
+        let (sender, receiver) = oneshot::channel();
+
+        self.sender
+            .send(Box::new(move |workspace| {
+                sender.send(f(workspace)).ok();
+            }))
+            .ok()?;
+
+        receiver.await.ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rue_compiler::MANIFEST_FILE_NAME;
+
+    use super::*;
+
+    #[test]
+    fn test_open_document_symbols() {
+        let mut workspace = Workspace::new();
+        let uri = Url::parse("file:///main.rue").unwrap();
+
+        workspace.open(uri.clone(), "struct Point { x: Int }".to_string(), 1);
+
+        let names = |workspace: &mut Workspace, query: &str| -> Vec<String> {
+            workspace
+                .symbols(query)
+                .into_iter()
+                .map(|symbol| symbol.name)
+                .collect()
+        };
+
+        assert_eq!(names(&mut workspace, "POI"), ["Point"]);
+
+        workspace.change(
+            &uri,
+            vec![TextDocumentContentChangeEvent {
+                range: None,
+                range_length: None,
+                text: "struct Pair { x: Int }".to_string(),
+            }],
+            2,
+        );
+
+        assert!(names(&mut workspace, "poi").is_empty());
+        assert_eq!(names(&mut workspace, "pa"), ["Pair"]);
+    }
+
+    #[test]
+    fn test_manifest_invalidates_dependents() {
+        let dir = std::env::temp_dir().join(format!("rue-lsp-manifest-{}", std::process::id()));
+        let project = dir.join("project");
+        fs::create_dir_all(&project).unwrap();
+
+        let manifest = project.join(MANIFEST_FILE_NAME);
+        fs::write(&manifest, "[lints]\nunused_let = \"allow\"\n").unwrap();
+
+        let source = "fun main() -> Int {\n    let value = 42;\n    0\n}\n";
+        let inside = project.join("main.rue");
+        let outside = dir.join("main.rue");
+        fs::write(&inside, source).unwrap();
+        fs::write(&outside, source).unwrap();
+
+        let inside = Url::from_file_path(&inside).unwrap();
+        let outside = Url::from_file_path(&outside).unwrap();
+
+        let mut workspace = Workspace::new();
+        workspace.open(inside.clone(), source.to_string(), 1);
+        workspace.open(outside.clone(), source.to_string(), 1);
+
+        let warnings = |workspace: &mut Workspace, uri: &Url| {
+            workspace
+                .document(uri)
+                .unwrap()
+                .analysis
+                .diagnostics()
+                .len()
+        };
+
+        assert_eq!(warnings(&mut workspace, &inside), 0);
+        assert_eq!(warnings(&mut workspace, &outside), 1);
+
+        fs::write(&manifest, "[lints]\nunused_let = \"warn\"\n").unwrap();
+        let changed = workspace.change_manifest(&manifest);
+
+        // The document outside of the project keeps its analysis.
+        assert_eq!(changed, [(inside.clone(), 1)]);
+        assert!(workspace.documents[&outside].document.is_some());
+        assert!(workspace.documents[&inside].parse.is_some());
+        assert_eq!(warnings(&mut workspace, &inside), 1);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -53,6 +53,7 @@ macro_rules! ast_enum {
 }
 
 ast_node!(Root);
+ast_node!(Attribute);
 
 ast_enum!(
     Item,
@@ -97,6 +98,7 @@ ast_enum!(
     IfExpr,
     FunctionCallExpr,
     FieldAccessExpr,
+    ClvmExpr,
 );
 ast_node!(PathExpr);
 ast_node!(InitializerExpr);
@@ -114,6 +116,7 @@ ast_node!(IfExpr);
 ast_node!(FunctionCallExpr);
 ast_node!(FunctionCallArg);
 ast_node!(FieldAccessExpr);
+ast_node!(ClvmExpr);
 
 ast_node!(LambdaExpr);
 ast_node!(LambdaParam);
@@ -152,6 +155,13 @@ impl Root {
 }
 
 impl Item {
+    pub fn attributes(&self) -> Vec<Attribute> {
+        self.syntax()
+            .children()
+            .filter_map(Attribute::cast)
+            .collect()
+    }
+
     pub fn export(&self) -> Option<SyntaxToken> {
         match self {
             Item::ModuleItem(item) => item.export(),
@@ -160,11 +170,30 @@ impl Item {
             Item::StructItem(item) => item.export(),
             Item::EnumItem(item) => item.export(),
             Item::ConstItem(item) => item.export(),
-            Item::ImportItem(_item) => None,
+            Item::ImportItem(item) => item.export(),
         }
     }
 }
 
+impl Attribute {
+    pub fn name(&self) -> Option<SyntaxToken> {
+        self.syntax()
+            .children_with_tokens()
+            .filter_map(SyntaxElement::into_token)
+            .find(|token| token.kind() == SyntaxKind::Ident)
+    }
+
+    /// The names listed in parentheses after the attribute name, such as lints.
+    pub fn args(&self) -> Vec<SyntaxToken> {
+        self.syntax()
+            .children_with_tokens()
+            .filter_map(SyntaxElement::into_token)
+            .filter(|token| token.kind() == SyntaxKind::Ident)
+            .skip(1)
+            .collect()
+    }
+}
+
 impl ModuleItem {
     pub fn name(&self) -> Option<SyntaxToken> {
         self.syntax()
@@ -408,6 +437,13 @@ impl ImportItem {
     pub fn path(&self) -> Option<ImportPath> {
         self.syntax().children().find_map(ImportPath::cast)
     }
+
+    pub fn export(&self) -> Option<SyntaxToken> {
+        self.syntax()
+            .children_with_tokens()
+            .filter_map(SyntaxElement::into_token)
+            .find(|token| token.kind() == SyntaxKind::Export)
+    }
 }
 
 impl ImportPath {
@@ -805,6 +841,23 @@ impl FieldAccessExpr {
     }
 }
 
+impl ClvmExpr {
+    pub fn ty(&self) -> Option<Type> {
+        self.syntax().children().find_map(Type::cast)
+    }
+
+    pub fn assembly(&self) -> Option<SyntaxToken> {
+        self.syntax()
+            .children_with_tokens()
+            .filter_map(SyntaxElement::into_token)
+            .find(|token| token.kind() == SyntaxKind::String)
+    }
+
+    pub fn args(&self) -> Vec<Expr> {
+        self.syntax().children().filter_map(Expr::cast).collect()
+    }
+}
+
 impl LiteralType {
     pub fn value(&self) -> Option<SyntaxToken> {
         self.syntax()
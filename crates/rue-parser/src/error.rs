@@ -31,6 +31,19 @@ pub enum ParserErrorKind {
     MissingHexDigits,
 }
 
+impl ParserErrorKind {
+    /// A stable identifier for the syntax error, which doesn't change between releases.
+    pub fn code(&self) -> &'static str {
+        match self {
+            ParserErrorKind::UnexpectedToken(..) => "P0001",
+            ParserErrorKind::UnknownToken(..) => "P0002",
+            ParserErrorKind::UnterminatedString => "P0003",
+            ParserErrorKind::UnterminatedBlockComment => "P0004",
+            ParserErrorKind::MissingHexDigits => "P0005",
+        }
+    }
+}
+
 impl fmt::Display for ParserErrorKind {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let message = match self {
@@ -20,10 +20,14 @@ fn at_item(p: &mut Parser<'_>) -> bool {
         || p.at(SyntaxKind::Import)
         || p.at(SyntaxKind::Export)
         || p.at(SyntaxKind::Inline)
+        || p.at(SyntaxKind::Pound)
 }
 
 fn item(p: &mut Parser<'_>) {
     let cp = p.checkpoint();
+    while p.at(SyntaxKind::Pound) {
+        attribute(p);
+    }
     p.try_eat(SyntaxKind::Export);
     let inline = p.try_eat(SyntaxKind::Inline);
 
@@ -50,12 +54,30 @@ fn item(p: &mut Parser<'_>) {
     }
 }
 
+fn attribute(p: &mut Parser<'_>) {
+    p.start(SyntaxKind::Attribute);
+    p.expect(SyntaxKind::Pound);
+    p.expect(SyntaxKind::OpenBracket);
+    p.expect(SyntaxKind::Ident);
+    if p.try_eat(SyntaxKind::OpenParen) {
+        while !p.at(SyntaxKind::CloseParen) {
+            p.expect(SyntaxKind::Ident);
+            if !p.try_eat(SyntaxKind::Comma) {
+                break;
+            }
+        }
+        p.expect(SyntaxKind::CloseParen);
+    }
+    p.expect(SyntaxKind::CloseBracket);
+    p.finish();
+}
+
 fn module_item(p: &mut Parser<'_>, cp: Checkpoint) {
     p.start_at(cp, SyntaxKind::ModuleItem);
     p.expect(SyntaxKind::Mod);
     p.expect(SyntaxKind::Ident);
     p.expect(SyntaxKind::OpenBrace);
-    while !p.at(SyntaxKind::CloseBrace) {
+    while !p.at(SyntaxKind::CloseBrace) && !p.at(SyntaxKind::Eof) {
         item(p);
     }
     p.expect(SyntaxKind::CloseBrace);
@@ -400,6 +422,8 @@ fn expr_binding_power(p: &mut Parser<'_>, minimum_binding_power: u8, allow_initi
         if_stmt_maybe_else(p, true);
     } else if p.at(SyntaxKind::Fun) {
         lambda_expr(p);
+    } else if p.at(SyntaxKind::Clvm) {
+        clvm_expr(p);
     } else {
         return p.error(EXPR_RECOVERY_SET);
     }
@@ -537,6 +561,24 @@ fn list_expr(p: &mut Parser<'_>) {
     p.finish();
 }
 
+fn clvm_expr(p: &mut Parser<'_>) {
+    p.start(SyntaxKind::ClvmExpr);
+    p.expect(SyntaxKind::Clvm);
+    p.expect(SyntaxKind::LessThan);
+    ty(p);
+    p.expect(SyntaxKind::GreaterThan);
+    p.expect(SyntaxKind::OpenParen);
+    p.expect(SyntaxKind::String);
+    while p.try_eat(SyntaxKind::Comma) {
+        if p.at(SyntaxKind::CloseParen) {
+            break;
+        }
+        expr(p);
+    }
+    p.expect(SyntaxKind::CloseParen);
+    p.finish();
+}
+
 fn lambda_expr(p: &mut Parser<'_>) {
     p.start(SyntaxKind::LambdaExpr);
     p.expect(SyntaxKind::Fun);
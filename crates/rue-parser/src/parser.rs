@@ -201,6 +201,7 @@ fn convert_tokens<'a>(
             TokenKind::Raise => SyntaxKind::Raise,
             TokenKind::Assert => SyntaxKind::Assert,
             TokenKind::Assume => SyntaxKind::Assume,
+            TokenKind::Clvm => SyntaxKind::Clvm,
             TokenKind::Nil => SyntaxKind::Nil,
             TokenKind::True => SyntaxKind::True,
             TokenKind::False => SyntaxKind::False,
@@ -236,6 +237,7 @@ fn convert_tokens<'a>(
             TokenKind::And => SyntaxKind::And,
             TokenKind::Or => SyntaxKind::Or,
             TokenKind::Question => SyntaxKind::Question,
+            TokenKind::Pound => SyntaxKind::Pound,
             TokenKind::Whitespace => SyntaxKind::Whitespace,
             TokenKind::LineComment => SyntaxKind::LineComment,
             TokenKind::BlockComment { is_terminated } => {
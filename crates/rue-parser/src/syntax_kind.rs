@@ -35,6 +35,7 @@ pub enum SyntaxKind {
     Raise,
     Assert,
     Assume,
+    Clvm,
     Nil,
     True,
     False,
@@ -49,6 +50,7 @@ pub enum SyntaxKind {
     FatArrow,
     Spread,
     Question,
+    Pound,
     Plus,
     Minus,
     Star,
@@ -74,6 +76,7 @@ pub enum SyntaxKind {
     LineComment,
     BlockComment,
     Root,
+    Attribute,
     ModuleItem,
     FunctionItem,
     FunctionParam,
@@ -112,6 +115,7 @@ pub enum SyntaxKind {
     FunctionCallExpr,
     FunctionCallArg,
     FieldAccessExpr,
+    ClvmExpr,
     LiteralType,
     PathType,
     PairType,
@@ -157,6 +161,7 @@ impl fmt::Display for SyntaxKind {
                 Self::Raise => "'raise'",
                 Self::Assert => "'assert'",
                 Self::Assume => "'assume'",
+                Self::Clvm => "'clvm'",
                 Self::Nil => "'nil'",
                 Self::True => "'true'",
                 Self::False => "'false'",
@@ -171,6 +176,7 @@ impl fmt::Display for SyntaxKind {
                 Self::FatArrow => "'=>'",
                 Self::Spread => "'...'",
                 Self::Question => "'?'",
+                Self::Pound => "'#'",
                 Self::Plus => "'+'",
                 Self::Minus => "'-'",
                 Self::Star => "'*'",
@@ -196,6 +202,7 @@ impl fmt::Display for SyntaxKind {
                 Self::LineComment => "line comment",
                 Self::BlockComment => "block comment",
                 Self::Root => "root",
+                Self::Attribute => "attribute",
                 Self::ModuleItem => "module item",
                 Self::FunctionItem => "function item",
                 Self::FunctionParam => "function param",
@@ -234,6 +241,7 @@ impl fmt::Display for SyntaxKind {
                 Self::FunctionCallExpr => "function call expression",
                 Self::FunctionCallArg => "function call argument",
                 Self::FieldAccessExpr => "field access expression",
+                Self::ClvmExpr => "clvm expression",
                 Self::LiteralType => "literal type",
                 Self::PathType => "path type",
                 Self::PairType => "pair type",
@@ -64,7 +64,7 @@ fn iter_tests() -> impl Iterator<Item = PathBuf> {
         .filter(|file| {
             Path::new(file.path().to_str().unwrap())
                 .extension()
-                .map_or(false, |ext| ext.eq_ignore_ascii_case("rue"))
+                .is_some_and(|ext| ext.eq_ignore_ascii_case("rue"))
         })
         .map(DirEntry::into_path)
 }
@@ -334,7 +334,7 @@ fn run_tests(update: bool) -> usize {
                         .unwrap_or("()".to_string()),
                     output: output.output.clone().unwrap_or("()".to_string()),
                     hash: output.hash,
-                    error: output.output.err().map(|error| error.to_string()),
+                    error: output.output.err(),
                 }),
                 Err(errors) => Expected::Errs(errors),
             };
@@ -364,10 +364,586 @@ fn run_tests(update: bool) -> usize {
 
 #[cfg(test)]
 mod tests {
+    use std::ops::Range;
+
+    use clvmr::NodePtr;
+    use indoc::indoc;
+    use proptest::{
+        collection::vec,
+        prelude::*,
+        sample::{select, Index},
+    };
+    use rue_clvm::MerkleTree;
+    use rue_compiler::{
+        compile_raw, explain, Diagnostic, DiagnosticData, LintConfig, LintLevel, DIAGNOSTIC_CODES,
+    };
+
     use super::*;
+    /// The codes reported when compiling the source code, and whether any of them were errors.
+    fn diagnostic_codes(source: &str) -> (Vec<&'static str>, bool) {
+        let (root, parser_errors) = rue_parser::parse(source);
+        let mut allocator = Allocator::new();
+        let output = compile(&mut allocator, &root, parser_errors.is_empty());
+
+        let codes = parser_errors
+            .iter()
+            .map(|error| error.kind().code())
+            .chain(output.diagnostics.iter().map(|error| error.kind().code()))
+            .collect();
+
+        let has_error =
+            !parser_errors.is_empty() || output.diagnostics.iter().any(Diagnostic::is_error);
+
+        (codes, has_error)
+    }
+
+    #[test]
+    fn test_explanations() {
+        // These codes aren't currently reported by the compiler.
+        let reserved = ["E0024", "E0033", "E0052", "E0061"];
+
+        for &code in DIAGNOSTIC_CODES {
+            let explanation = explain(code).unwrap();
+            let snippets: Vec<&str> = explanation
+                .split("```rue\n")
+                .skip(1)
+                .map(|block| block.split("```").next().unwrap())
+                .collect();
+
+            assert_eq!(snippets.len(), 2, "{code} should have two examples");
+
+            let (codes, _) = diagnostic_codes(snippets[0]);
+            assert!(
+                reserved.contains(&code) || codes.contains(&code),
+                "erroneous example for {code} reported {codes:?}"
+            );
+
+            let (codes, has_error) = diagnostic_codes(snippets[1]);
+            assert!(
+                !has_error && !codes.contains(&code),
+                "corrected example for {code} reported {codes:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_suggestions() {
+        let source = indoc! {"
+            struct Point {
+                x: Int,
+                long_name: Int,
+            }
+
+            enum Color {
+                Red,
+                Green,
+            }
+
+            fun main(point: Pont) -> Int {
+                let total = 42;
+                let first = Point { x: 1, long_nam: 2 };
+                let color = Color::Gren;
+                totl + first.lnog_name + unrelated
+            }
+        "};
+
+        let (root, _) = rue_parser::parse(source);
+        let mut allocator = Allocator::new();
+        let output = compile(&mut allocator, &root, false);
+
+        let suggestions: Vec<(String, Option<&str>)> = output
+            .diagnostics
+            .iter()
+            .filter(|diagnostic| diagnostic.is_error())
+            .map(|diagnostic| {
+                (
+                    source[diagnostic.span().clone()].trim().to_string(),
+                    diagnostic.suggestion(),
+                )
+            })
+            .collect();
+
+        assert_eq!(
+            suggestions,
+            [
+                ("Pont".to_string(), Some("Point")),
+                ("long_nam".to_string(), Some("long_name")),
+                ("Point { x: 1, long_nam: 2 }".to_string(), None),
+                ("Gren".to_string(), Some("Green")),
+                ("totl".to_string(), Some("total")),
+                ("lnog_name".to_string(), Some("long_name")),
+                ("unrelated".to_string(), None),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_short_name_suggestions() {
+        let source = indoc! {"
+            fun main(y: Int) -> Int {
+                b + y
+            }
+        "};
+
+        let (root, _) = rue_parser::parse(source);
+        let mut allocator = Allocator::new();
+        let output = compile(&mut allocator, &root, false);
+
+        let suggestions: Vec<(&str, Option<&str>)> = output
+            .diagnostics
+            .iter()
+            .filter(|diagnostic| diagnostic.is_error())
+            .map(|diagnostic| (&source[diagnostic.span().clone()], diagnostic.suggestion()))
+            .collect();
+
+        // A single character typo can't be told apart from an unrelated name of the same length.
+        assert_eq!(suggestions, [("b", None)]);
+    }
+
+    #[test]
+    fn test_return_type_mismatch() {
+        let source = indoc! {"
+            fun main(amount: Int) -> List<Condition> {
+                let fee = Condition::ReserveFee { amount: amount };
+                [fee, amount]
+            }
+        "};
+
+        let (root, _) = rue_parser::parse(source);
+        let mut allocator = Allocator::new();
+        let output = compile(&mut allocator, &root, false);
+
+        let [diagnostic] = output.diagnostics.as_slice() else {
+            panic!("expected one diagnostic, found {:?}", output.diagnostics);
+        };
+
+        let DiagnosticKind::Error(kind) = diagnostic.kind() else {
+            panic!("expected an error, found {:?}", diagnostic.kind());
+        };
+
+        assert_eq!(
+            kind.to_string(),
+            "Expected type `List<Condition>`, but found `(Condition::ReserveFee, (Int, Nil))`"
+        );
+        assert_eq!(&source[diagnostic.span().clone()], "[fee, amount]");
+        assert_eq!(
+            diagnostic.note(),
+            Some("`.rest.first` is `Int`, but `Condition` was expected")
+        );
+
+        // The standard library's `List` can't be pointed to, so the annotation is labeled instead.
+        let labels: Vec<(&str, &str)> = diagnostic
+            .labels()
+            .iter()
+            .map(|label| (&source[label.span.clone()], label.message.as_str()))
+            .collect();
+
+        assert_eq!(
+            labels,
+            [(
+                "List<Condition>",
+                "`List<Condition>` is the declared return type"
+            )]
+        );
+    }
+
+    #[test]
+    fn test_removal_fixes() {
+        let source = indoc! {"
+            fun main(value: Int) -> Int {
+                let unused = 1;
+                // Doubles the input.
+                let doubled = value * 2;
+                assert value is Int; // Keep me.
+                doubled + other()
+            }
+
+            fun helper() -> Int {
+                42
+            }
+
+            // Docs for other.
+            fun other() -> Int {
+                0
+            }
+        "};
+
+        let (root, _) = rue_parser::parse(source);
+        let mut allocator = Allocator::new();
+        let output = compile(&mut allocator, &root, false);
+
+        let mut removals: Vec<Range<usize>> = output
+            .diagnostics
+            .iter()
+            .filter_map(|diagnostic| match diagnostic.data() {
+                Some(DiagnosticData::Remove(range)) => Some(range.clone()),
+                _ => None,
+            })
+            .collect();
+
+        assert_eq!(removals.len(), 3);
+
+        // The removals are applied from the end, so that the earlier ranges are still valid.
+        removals.sort_by_key(|range| range.start);
+
+        let mut fixed = source.to_string();
+
+        for range in removals.into_iter().rev() {
+            fixed.replace_range(range, "");
+        }
+
+        assert_eq!(
+            fixed,
+            indoc! {"
+                fun main(value: Int) -> Int {
+                    // Doubles the input.
+                    let doubled = value * 2;
+                    // Keep me.
+                    doubled + other()
+                }
+
+                // Docs for other.
+                fun other() -> Int {
+                    0
+                }
+            "}
+        );
+    }
+
+    #[test]
+    fn test_lint_levels() {
+        let source = indoc! {"
+            fun helper(value: Int) -> Int {
+                let unused = 1;
+                42
+            }
+
+            fun main(amount: Int) -> Int {
+                helper(amount)
+            }
+        "};
+
+        let manifest = indoc! {r#"
+            [lints]
+            unused_let = "allow"
+            unused_parameter = "deny"
+        "#};
+
+        let (root, _) = rue_parser::parse(source);
+
+        let levels = |lints: &LintConfig| -> Vec<(&str, bool)> {
+            let mut allocator = Allocator::new();
+            let output = compile_raw(&mut allocator, &root, false, true, lints);
+
+            output
+                .diagnostics
+                .iter()
+                .map(|diagnostic| (diagnostic.kind().code(), diagnostic.is_error()))
+                .collect()
+        };
+
+        let mut lints = LintConfig::from_manifest(manifest).unwrap();
+        assert_eq!(levels(&lints), [("W0003", true)]);
+
+        lints.set_level("unused_parameter", LintLevel::Warn);
+        assert_eq!(levels(&lints), [("W0003", false)]);
+
+        lints.set_deny_warnings(true);
+        assert_eq!(levels(&lints), [("W0003", true)]);
+
+        assert!(LintConfig::from_manifest("[lints]\nunused_thing = \"allow\"").is_err());
+        assert!(LintConfig::from_manifest("[lints]\nunused_let = \"ignore\"").is_err());
+    }
+
+    /// Tokens which are combined randomly to produce malformed programs.
+    const TOKENS: &[&str] = &[
+        "fun",
+        "inline",
+        "const",
+        "let",
+        "if",
+        "else",
+        "return",
+        "raise",
+        "assert",
+        "assume",
+        "struct",
+        "enum",
+        "type",
+        "mod",
+        "import",
+        "export",
+        "super",
+        "main",
+        "value",
+        "Int",
+        "Bool",
+        "Bytes",
+        "Bytes32",
+        "PublicKey",
+        "Any",
+        "List",
+        "Condition",
+        "nil",
+        "true",
+        "false",
+        "as",
+        "is",
+        "clvm",
+        "42",
+        "0",
+        "0xff",
+        "\"text\"",
+        "(",
+        ")",
+        "{",
+        "}",
+        "[",
+        "]",
+        "<",
+        ">",
+        ",",
+        ":",
+        ";",
+        ".",
+        "...",
+        "::",
+        "=",
+        "==",
+        "!=",
+        "=>",
+        "->",
+        "+",
+        "-",
+        "*",
+        "/",
+        "%",
+        "!",
+        "&&",
+        "||",
+        "&",
+        "|",
+        "^",
+        "~",
+        "<<",
+        ">>",
+        "#",
+        "?",
+    ];
+
+    /// Parses and compiles the source, generating code if there are no parser errors.
+    fn compile_source(source: &str) {
+        let (root, parser_errors) = rue_parser::parse(source);
+        let mut allocator = Allocator::new();
+        compile_raw(
+            &mut allocator,
+            &root,
+            parser_errors.is_empty(),
+            true,
+            &LintConfig::default(),
+        );
+    }
+
+    fn example_sources() -> Vec<Vec<char>> {
+        iter_tests()
+            .map(|path| fs::read_to_string(path).unwrap().chars().collect())
+            .collect()
+    }
+
+    /// Replaces a random range of the source with one of the tokens.
+    fn mutate(source: &mut Vec<char>, start: Index, length: usize, token: &str) {
+        let start = start.index(source.len() + 1);
+        let end = (start + length).min(source.len());
+        source.splice(start..end, token.chars());
+    }
+
+    /// Ways of introducing a new binding, which are nested to produce valid programs that reach codegen.
+    #[derive(Debug, Clone, Copy)]
+    enum Layer {
+        Let,
+        Lambda,
+        InlineFunction,
+        Function,
+        InlineConst,
+        If,
+    }
+
+    const LAYERS: &[Layer] = &[
+        Layer::Let,
+        Layer::Lambda,
+        Layer::InlineFunction,
+        Layer::Function,
+        Layer::InlineConst,
+        Layer::If,
+    ];
+
+    /// Builds the body of a block which nests the layers, along with the value it evaluates to.
+    /// Each layer except `If` binds `v{index}`, and the block either returns the last binding or the sum of all visible bindings.
+    fn nested_block(
+        layers: &[Layer],
+        index: usize,
+        bindings: &[(String, u64)],
+        sum: bool,
+    ) -> (String, u64) {
+        let (last, value) = bindings.last().cloned().unwrap();
+
+        let Some((&layer, layers)) = layers.split_first() else {
+            if sum {
+                let names: Vec<&str> = bindings.iter().map(|(name, _)| name.as_str()).collect();
+                return (
+                    names.join(" + "),
+                    bindings.iter().map(|(_, value)| value).sum(),
+                );
+            }
+            return (last, value);
+        };
+
+        let name = format!("v{index}");
+
+        let nested = |value: u64, inherit: bool| {
+            let mut bindings = if inherit {
+                bindings.to_vec()
+            } else {
+                Vec::new()
+            };
+            bindings.push((name.clone(), value));
+            nested_block(layers, index + 1, &bindings, sum)
+        };
+
+        match layer {
+            Layer::Let => {
+                let (body, result) = nested(value * 2, true);
+                (format!("let {name} = {last} * 2; {body}"), result)
+            }
+            Layer::Lambda => {
+                let (body, result) = nested(value, true);
+                (
+                    format!("let f{index} = fun({name}: Int) => {{ {body} }}; f{index}({last})"),
+                    result,
+                )
+            }
+            Layer::InlineFunction => {
+                let (body, result) = nested(value, false);
+                (
+                    format!(
+                        "inline fun f{index}({name}: Int) -> Int {{ {body} }} f{index}({last})"
+                    ),
+                    result,
+                )
+            }
+            Layer::Function => {
+                let (body, result) = nested(value, false);
+                (
+                    format!("fun f{index}({name}: Int) -> Int {{ {body} }} f{index}({last})"),
+                    result,
+                )
+            }
+            Layer::InlineConst => {
+                let (body, result) = nested(index as u64, true);
+                (
+                    format!("inline const {name}: Int = {index}; {body}"),
+                    result,
+                )
+            }
+            Layer::If => {
+                let (body, result) = nested_block(layers, index + 1, bindings, sum);
+                (
+                    format!("if {last} > 5 {{ {body} }} else {{ {last} }}"),
+                    if value > 5 { result } else { value },
+                )
+            }
+        }
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(64))]
+
+        #[test]
+        fn test_random_tokens(in_main: bool, tokens in vec(select(TOKENS), 0..64)) {
+            let tokens = tokens.join(" ");
+
+            if in_main {
+                compile_source(&format!("fun main() -> Int {{ {tokens} }}"));
+            } else {
+                compile_source(&tokens);
+            }
+        }
+
+        #[test]
+        fn test_mutated_examples(
+            source in select(example_sources()),
+            mutations in vec((any::<Index>(), 0..16usize, select(TOKENS)), 1..4),
+        ) {
+            let mut source = source;
+
+            for (start, length, token) in mutations {
+                mutate(&mut source, start, length, token);
+            }
+
+            compile_source(&source.into_iter().collect::<String>());
+        }
+
+        #[test]
+        fn test_nested_bindings(layers in vec(select(LAYERS), 0..6), sum: bool) {
+            let (body, expected) = nested_block(&layers, 1, &[("v0".to_string(), 3)], sum);
+            let source = format!("fun main() -> Int {{ let v0 = 3; {body} }}");
+
+            let (root, parser_errors) = rue_parser::parse(&source);
+            prop_assert!(parser_errors.is_empty(), "{source}");
+
+            let mut allocator = Allocator::new();
+            let output = compile_raw(&mut allocator, &root, true, true, &LintConfig::default());
+            prop_assert!(!output.diagnostics.iter().any(Diagnostic::is_error), "{source}");
+
+            let (node_ptr, _) = run_clvm(&mut allocator, output.node_ptr, NodePtr::NIL, u64::MAX).unwrap();
+            prop_assert_eq!(stringify_clvm(&allocator, node_ptr).unwrap(), expected.to_string(), "{}", source);
+        }
+    }
 
     #[test]
     fn test_regressions() {
         assert_eq!(run_tests(false), 0, "one or more tests failed");
     }
+
+    #[test]
+    fn test_merkle_proofs() {
+        let manifest_dir = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap());
+        let source = fs::read_to_string(manifest_dir.join("../../tests/merkle.rue")).unwrap();
+
+        for count in 1..=9 {
+            let leaves: Vec<[u8; 32]> = (0..count).map(|i| [i; 32]).collect();
+            let tree = MerkleTree::new(&leaves).unwrap();
+
+            for (index, leaf) in leaves.iter().enumerate() {
+                let proof = tree.proof(index).unwrap();
+                assert_eq!(proof.root(*leaf), tree.root());
+
+                for (leaf, expected) in [(*leaf, "1"), ([0xff; 32], "()")] {
+                    let mut allocator = Allocator::new();
+                    let leaves: Vec<NodePtr> = leaves
+                        .iter()
+                        .map(|leaf| allocator.new_atom(leaf).unwrap())
+                        .collect();
+                    let leaves = list(&mut allocator, &leaves);
+                    let leaf = allocator.new_atom(&leaf).unwrap();
+                    let proof = proof.to_clvm(&mut allocator).unwrap();
+                    let input = list(&mut allocator, &[leaves, leaf, proof]);
+                    let input = stringify_clvm(&allocator, input).unwrap();
+
+                    let Ok(output) = run_test(&source, &input) else {
+                        panic!("merkle test failed to compile");
+                    };
+                    assert_eq!(output.output.as_deref(), Ok(expected), "{input}");
+                }
+            }
+        }
+    }
+
+    fn list(allocator: &mut Allocator, items: &[NodePtr]) -> NodePtr {
+        let mut result = NodePtr::NIL;
+        for &item in items.iter().rev() {
+            result = allocator.new_pair(item, result).unwrap();
+        }
+        result
+    }
 }
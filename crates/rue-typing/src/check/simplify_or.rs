@@ -36,16 +36,8 @@ pub(crate) fn simplify_or_shallow(items: impl IntoIterator<Item = Check>) -> Che
     for item in items {
         match item {
             Check::True => return Check::True,
-            Check::IsAtom if any_atom => continue,
-            Check::IsPair if any_pair => continue,
-            Check::IsAtom => {
-                any_atom = true;
-                continue;
-            }
-            Check::IsPair => {
-                any_pair = true;
-                continue;
-            }
+            Check::IsAtom => any_atom = true,
+            Check::IsPair => any_pair = true,
             Check::And(children) => {
                 let (shape, checks) = extract_shape_check(children);
                 match shape {
@@ -53,7 +45,6 @@ pub(crate) fn simplify_or_shallow(items: impl IntoIterator<Item = Check>) -> Che
                     Some(Check::IsPair) => pair_checks.push(checks),
                     _ => result.push(checks),
                 }
-                continue;
             }
             item => result.push(item),
         }
@@ -3,7 +3,7 @@ use std::cmp::{max, min};
 use num_bigint::BigInt;
 use num_traits::One;
 
-use crate::{bigint_to_bytes, HashMap, HashSet, Type, TypeId, TypeSystem};
+use crate::{bigint_to_bytes, HashMap, HashSet, Mismatch, MismatchStep, Type, TypeId, TypeSystem};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum Comparison {
@@ -19,6 +19,10 @@ pub(crate) struct ComparisonContext<'a> {
     pub infer_generics: bool,
     pub lhs_substitutions: Vec<HashMap<TypeId, TypeId>>,
     pub rhs_substitutions: Vec<HashMap<TypeId, TypeId>>,
+    /// Whether to record why the comparison failed, which is slower.
+    pub explain: bool,
+    pub path: Vec<MismatchStep>,
+    pub mismatch: Option<Mismatch>,
 }
 
 pub(crate) fn compare_type(
@@ -31,6 +35,9 @@ pub(crate) fn compare_type(
         return Comparison::Assignable;
     }
 
+    // Mismatches found before this comparison take precedence over any found during it.
+    let outer_mismatch = ctx.mismatch.take();
+
     let found_lhs = ctx
         .lhs_substitutions
         .iter()
@@ -94,6 +101,18 @@ pub(crate) fn compare_type(
                 compare_type(db, inferred, rhs, ctx)
             } else if lhs == rhs {
                 Comparison::Equal
+            } else if let Type::Union(items) = db.get(rhs) {
+                // A generic can be assigned to a union which contains it.
+                let items = items.clone();
+                let mut result = Comparison::NotEqual;
+
+                for item in items {
+                    result = min(result, compare_type(db, lhs, item, ctx));
+                }
+
+                ctx.mismatch = None;
+
+                max(result, Comparison::Assignable)
             } else {
                 Comparison::NotEqual
             }
@@ -240,28 +259,43 @@ pub(crate) fn compare_type(
 
         // A comparison of pairs is done by using whichever comparison is the most restrictive.
         (Type::Pair(lhs_first, lhs_rest), Type::Pair(rhs_first, rhs_rest)) => {
-            let first = compare_type(db, *lhs_first, *rhs_first, ctx);
-            let rest = compare_type(db, *lhs_rest, *rhs_rest, ctx);
+            let first = compare_step(db, *lhs_first, *rhs_first, MismatchStep::First, ctx);
+            let rest = compare_step(db, *lhs_rest, *rhs_rest, MismatchStep::Rest, ctx);
             max(first, rest)
         }
 
         // Unions can be assigned to anything so long as each of the items in the union are also.
         (Type::Union(items), _) => {
             let items = items.clone();
-            let mut result = Comparison::Assignable;
 
-            for item in items {
-                let cmp = compare_type(db, item, rhs, ctx);
-                result = max(result, cmp);
-            }
+            // Each item is at best assignable to the union as a whole,
+            // so identical unions are compared item by item instead.
+            let identical = matches!(db.get(rhs), Type::Union(rhs_items) if *rhs_items == items)
+                && items
+                    .iter()
+                    .all(|&item| compare_type(db, item, item, ctx) == Comparison::Equal);
+
+            if identical {
+                Comparison::Equal
+            } else {
+                ctx.mismatch = None;
 
-            result
+                let mut result = Comparison::Assignable;
+
+                for item in items {
+                    let cmp = compare_step(db, item, rhs, MismatchStep::UnionItem(item), ctx);
+                    result = max(result, cmp);
+                }
+
+                result
+            }
         }
 
         // Anything can be assigned to a union so long as it's assignable to at least one of the items.
         (_, Type::Union(items)) => {
             let items = items.clone();
             let mut result = Comparison::NotEqual;
+            let mut mismatches = Vec::new();
 
             for item in &items {
                 if matches!(db.get_recursive(*item), Type::Never) {
@@ -270,6 +304,14 @@ pub(crate) fn compare_type(
 
                 let cmp = compare_type(db, lhs, *item, ctx);
                 result = min(result, cmp);
+
+                if ctx.explain {
+                    mismatches.push((*item, ctx.mismatch.take()));
+                }
+            }
+
+            if result > Comparison::Assignable {
+                ctx.mismatch = closest_mismatch(db, lhs, mismatches);
             }
 
             max(result, Comparison::Assignable)
@@ -281,14 +323,27 @@ pub(crate) fn compare_type(
 
         // Structs are at best castable to other types, since they have different semantics.
         (Type::Struct(lhs), Type::Struct(rhs)) if lhs.original_type_id == rhs.original_type_id => {
-            compare_type(db, lhs.type_id, rhs.type_id, ctx)
+            let step = MismatchStep::Struct(rhs.original_type_id);
+            compare_step(db, lhs.type_id, rhs.type_id, step, ctx)
         }
         (Type::Struct(lhs), _) => max(
-            compare_type(db, lhs.type_id, rhs, ctx),
+            compare_step(
+                db,
+                lhs.type_id,
+                rhs,
+                MismatchStep::Struct(lhs.original_type_id),
+                ctx,
+            ),
             Comparison::Castable,
         ),
         (_, Type::Struct(rhs)) => max(
-            compare_type(db, lhs, rhs.type_id, ctx),
+            compare_step(
+                db,
+                lhs,
+                rhs.type_id,
+                MismatchStep::Struct(rhs.original_type_id),
+                ctx,
+            ),
             Comparison::Castable,
         ),
 
@@ -324,36 +379,133 @@ pub(crate) fn compare_type(
         (Type::Variant(lhs), Type::Variant(rhs))
             if lhs.original_type_id == rhs.original_type_id =>
         {
-            compare_type(db, lhs.type_id, rhs.type_id, ctx)
+            let step = MismatchStep::Variant(rhs.original_type_id);
+            compare_step(db, lhs.type_id, rhs.type_id, step, ctx)
         }
         (Type::Variant(lhs), _) => max(
-            compare_type(db, lhs.type_id, rhs, ctx),
+            compare_step(
+                db,
+                lhs.type_id,
+                rhs,
+                MismatchStep::Variant(lhs.original_type_id),
+                ctx,
+            ),
             Comparison::Castable,
         ),
         (_, Type::Variant(rhs)) => max(
-            compare_type(db, lhs, rhs.type_id, ctx),
+            compare_step(
+                db,
+                lhs,
+                rhs.type_id,
+                MismatchStep::Variant(rhs.original_type_id),
+                ctx,
+            ),
             Comparison::Castable,
         ),
 
         // Functions can be assigned to other functions if the parameters and return type are assignable.
         // They're treated like Never on the right hand side and Any on the left hand side.
         (Type::Callable(lhs), Type::Callable(rhs)) => max(
-            compare_type(db, lhs.parameters, rhs.parameters, ctx),
-            compare_type(db, lhs.return_type, rhs.return_type, ctx),
+            compare_step(
+                db,
+                lhs.parameters,
+                rhs.parameters,
+                MismatchStep::Parameters(rhs.original_type_id),
+                ctx,
+            ),
+            compare_step(
+                db,
+                lhs.return_type,
+                rhs.return_type,
+                MismatchStep::ReturnType,
+                ctx,
+            ),
         ),
         (Type::Callable(..), _) => compare_type(db, lhs, db.std().any, ctx),
     };
 
     ctx.visited.remove(&(lhs, rhs));
 
+    if ctx.explain {
+        let inner_mismatch = ctx.mismatch.take();
+
+        let mismatch = if comparison > Comparison::Assignable {
+            // The outermost types at a given position are the ones that were written,
+            // rather than the aliases and structures they resolve to.
+            match inner_mismatch {
+                Some(inner) if !is_same_position(&inner.path, &ctx.path) => Some(inner),
+                _ => Some(Mismatch {
+                    path: ctx.path.clone(),
+                    found: lhs,
+                    expected: rhs,
+                }),
+            }
+        } else {
+            None
+        };
+
+        ctx.mismatch = outer_mismatch.or(mismatch);
+    }
+
     comparison
 }
 
+/// Compares a part of the types, keeping track of the path to it if the comparison is being explained.
+fn compare_step(
+    db: &TypeSystem,
+    lhs: TypeId,
+    rhs: TypeId,
+    step: MismatchStep,
+    ctx: &mut ComparisonContext<'_>,
+) -> Comparison {
+    if !ctx.explain {
+        return compare_type(db, lhs, rhs, ctx);
+    }
+
+    ctx.path.push(step);
+    let comparison = compare_type(db, lhs, rhs, ctx);
+    ctx.path.pop().unwrap();
+    comparison
+}
+
+fn is_same_position(lhs: &[MismatchStep], rhs: &[MismatchStep]) -> bool {
+    lhs.iter()
+        .filter(|step| !step.is_marker())
+        .eq(rhs.iter().filter(|step| !step.is_marker()))
+}
+
+/// When a type isn't assignable to any item of a union, the item it was most likely meant to be
+/// is the one that is the same variant, or otherwise the only one with the same shape.
+fn closest_mismatch(
+    db: &TypeSystem,
+    lhs: TypeId,
+    mismatches: Vec<(TypeId, Option<Mismatch>)>,
+) -> Option<Mismatch> {
+    if let Type::Variant(variant) = db.get(lhs) {
+        if let Some((_, mismatch)) = mismatches.iter().find(|(item, _)| {
+            matches!(db.get(*item), Type::Variant(item) if item.original_type_id == variant.original_type_id)
+        }) {
+            return mismatch.clone();
+        }
+    }
+
+    let is_pair = |type_id| matches!(db.get_recursive(type_id), Type::Pair(..));
+
+    let mut same_shape = mismatches
+        .into_iter()
+        .filter(|(item, _)| is_pair(*item) == is_pair(lhs));
+
+    match (same_shape.next(), same_shape.next()) {
+        (Some((_, mismatch)), None) => mismatch,
+        _ => None,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use indexmap::indexmap;
 
-    use crate::{alloc_list, alloc_struct, alloc_tuple_of, Enum, Struct, Variant};
+    use crate::{alloc_list, alloc_struct, alloc_tuple_of, Enum, MismatchSegment, Struct, Variant};
 
     use super::*;
 
@@ -602,6 +754,19 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_compare_generic_union() {
+        let mut db = TypeSystem::new();
+        let types = db.std();
+
+        let generic = db.alloc(Type::Generic);
+        let optional = db.alloc(Type::Union(vec![generic, types.nil]));
+
+        assert_eq!(db.compare(generic, optional), Comparison::Assignable);
+        assert_eq!(db.compare(optional, optional), Comparison::Equal);
+        assert_eq!(db.compare(generic, types.nil), Comparison::NotEqual);
+    }
+
     #[test]
     fn test_compare_enum_generic_inference() {
         let mut db = TypeSystem::new();
@@ -682,6 +847,52 @@ mod tests {
         assert_eq!(db.compare(types.bytes, union), Comparison::NotEqual);
     }
 
+    #[test]
+    fn test_compare_union_to_identical_union() {
+        let mut db = TypeSystem::new();
+        let types = db.std();
+
+        let union = db.alloc(Type::Union(vec![types.bytes32, types.nil]));
+        let other = db.alloc(Type::Union(vec![types.bytes32, types.nil]));
+        assert_eq!(db.compare(union, union), Comparison::Equal);
+        assert_eq!(db.compare(union, other), Comparison::Equal);
+    }
+
+    #[test]
+    fn test_compare_union_to_different_union() {
+        let mut db = TypeSystem::new();
+        let types = db.std();
+
+        let union = db.alloc(Type::Union(vec![types.bytes32, types.nil]));
+        let reordered = db.alloc(Type::Union(vec![types.nil, types.bytes32]));
+        let superset = db.alloc(Type::Union(vec![types.bytes32, types.int, types.nil]));
+        let subset = db.alloc(Type::Union(vec![types.bytes32, types.int]));
+        assert_eq!(db.compare(union, reordered), Comparison::Assignable);
+        assert_eq!(db.compare(union, superset), Comparison::Assignable);
+        assert_eq!(db.compare(union, subset), Comparison::Castable);
+    }
+
+    #[test]
+    fn test_compare_union_to_generic_union() {
+        let mut db = TypeSystem::new();
+        let types = db.std();
+
+        let generic = db.alloc(Type::Generic);
+        let optional = db.alloc(Type::Union(vec![generic, types.nil]));
+        let optional_int = db.alloc(Type::Union(vec![types.int, types.nil]));
+
+        let mut stack = vec![HashMap::new()];
+
+        assert_eq!(
+            db.compare_with_generics(optional_int, optional, &mut stack, true),
+            Comparison::Assignable
+        );
+        assert_eq!(stack[0].get(&generic), Some(&types.int));
+
+        assert_eq!(db.compare(optional_int, optional), Comparison::NotEqual);
+        assert_eq!(db.compare(optional, optional_int), Comparison::NotEqual);
+    }
+
     #[test]
     fn test_compare_same_derivative_struct() {
         let mut db = TypeSystem::new();
@@ -863,4 +1074,99 @@ mod tests {
         );
         assert_eq!(stack, vec![[(generic, types.int)].into()]);
     }
+
+    #[test]
+    fn test_explain_nested_struct_field() {
+        let mut db = TypeSystem::new();
+        let types = db.std();
+
+        let payout = alloc_struct(
+            &mut db,
+            &indexmap! {
+                "puzzle_hash".to_string() => types.bytes32,
+                "amount".to_string() => types.int,
+            },
+            true,
+        );
+        let found_payout = alloc_struct(
+            &mut db,
+            &indexmap! {
+                "puzzle_hash".to_string() => types.bytes32,
+                "amount".to_string() => types.bytes,
+            },
+            true,
+        );
+        let expected = alloc_struct(
+            &mut db,
+            &indexmap! {
+                "payout".to_string() => payout,
+            },
+            true,
+        );
+        let found = alloc_struct(
+            &mut db,
+            &indexmap! {
+                "payout".to_string() => found_payout,
+            },
+            true,
+        );
+
+        let mismatch = db.explain_mismatch(found, expected, &[], false).unwrap();
+
+        assert_eq!(mismatch.found, types.bytes);
+        assert_eq!(mismatch.expected, types.int);
+        assert_eq!(
+            mismatch.segments(&db),
+            vec![
+                MismatchSegment::Field("payout".to_string()),
+                MismatchSegment::Field("amount".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_explain_union_item() {
+        let mut db = TypeSystem::new();
+        let types = db.std();
+
+        let pair = db.alloc(Type::Pair(types.int, types.bytes));
+        let expected = db.alloc(Type::Pair(types.int, types.int));
+        let union = db.alloc(Type::Union(vec![expected, pair]));
+
+        let mismatch = db.explain_mismatch(union, expected, &[], false).unwrap();
+
+        assert_eq!(mismatch.found, types.bytes);
+        assert_eq!(mismatch.expected, types.int);
+        assert_eq!(
+            mismatch.segments(&db),
+            vec![MismatchSegment::UnionItem(pair), MismatchSegment::Rest]
+        );
+    }
+
+    #[test]
+    fn test_explain_closest_union_item() {
+        let mut db = TypeSystem::new();
+        let types = db.std();
+
+        let pair = db.alloc(Type::Pair(types.bytes, types.int));
+        let expected_pair = db.alloc(Type::Pair(types.bytes, types.bytes32));
+        let union = db.alloc(Type::Union(vec![types.nil, expected_pair]));
+
+        let mismatch = db.explain_mismatch(pair, union, &[], false).unwrap();
+
+        assert_eq!(mismatch.found, types.int);
+        assert_eq!(mismatch.expected, types.bytes32);
+        assert_eq!(mismatch.segments(&db), vec![MismatchSegment::Rest]);
+    }
+
+    #[test]
+    fn test_explain_assignable() {
+        let db = TypeSystem::new();
+        let types = db.std();
+
+        assert_eq!(
+            db.explain_mismatch(types.bytes32, types.bytes, &[], false),
+            None
+        );
+    }
 }
@@ -1,3 +1,5 @@
+use std::fmt::Write;
+
 use crate::HashSet;
 
 use crate::{Type, TypeId, TypeSystem};
@@ -16,7 +18,7 @@ pub(crate) fn debug_type(
     }
 
     result.push_str(prefix);
-    result.push_str(&format!("({}) ", type_id.index()));
+    write!(result, "({}) ", type_id.index()).unwrap();
 
     if !visited.insert(type_id) {
         result.push_str("...");
@@ -35,30 +37,30 @@ pub(crate) fn debug_type(
         Type::True => result.push_str("True"),
         Type::False => result.push_str("False"),
         Type::Generic => result.push_str("Generic"),
-        Type::Value(value) => result.push_str(&format!("Literal {value}")),
+        Type::Value(value) => write!(result, "Literal {value}").unwrap(),
         Type::Pair(first, rest) => {
             let first = debug_type(ty, "First", *first, indent + 1, visited);
             let rest = debug_type(ty, "Rest", *rest, indent + 1, visited);
-            result.push_str(&format!("Pair\n{first}\n{rest}"));
+            write!(result, "Pair\n{first}\n{rest}").unwrap();
         }
         Type::Union(types) => {
             result.push_str("Union");
             for type_id in types {
                 let type_str = debug_type(ty, "", *type_id, indent + 1, visited);
-                result.push_str(&format!("\n{type_str}"));
+                write!(result, "\n{type_str}").unwrap();
             }
         }
         Type::Ref(inner) => {
             let inner = debug_type(ty, "", *inner, indent + 1, visited);
-            result.push_str(&format!("Ref\n{inner}"));
+            write!(result, "Ref\n{inner}").unwrap();
         }
         Type::Alias(alias) => {
-            result.push_str(&format!("Alias {}", alias.original_type_id.index()));
+            write!(result, "Alias {}", alias.original_type_id.index()).unwrap();
             if !alias.generic_types.is_empty() {
                 generics(&mut result, &alias.generic_types);
             }
             let inner = debug_type(ty, "", alias.type_id, indent + 1, visited);
-            result.push_str(&format!("\n{inner}"));
+            write!(result, "\n{inner}").unwrap();
         }
         Type::Lazy(lazy) => {
             result.push_str("Lazy");
@@ -68,12 +70,12 @@ pub(crate) fn debug_type(
                     if i != 0 {
                         result.push_str(", ");
                     }
-                    result.push_str(&format!("{} = {}", from.index(), to.index()));
+                    write!(result, "{} = {}", from.index(), to.index()).unwrap();
                 }
                 result.push('>');
             }
             let inner = debug_type(ty, "", lazy.type_id, indent + 1, visited);
-            result.push_str(&format!("\n{inner}"));
+            write!(result, "\n{inner}").unwrap();
         }
         Type::Struct(struct_type) => {
             result.push_str("Struct");
@@ -81,17 +83,17 @@ pub(crate) fn debug_type(
                 generics(&mut result, &struct_type.generic_types);
             }
             let inner = debug_type(ty, "", struct_type.type_id, indent + 1, visited);
-            result.push_str(&format!("\n{inner}"));
+            write!(result, "\n{inner}").unwrap();
         }
         Type::Enum(enum_type) => {
             result.push_str("Enum");
             let inner = debug_type(ty, "", enum_type.type_id, indent + 1, visited);
-            result.push_str(&format!("\n{inner}"));
+            write!(result, "\n{inner}").unwrap();
         }
         Type::Variant(variant) => {
             result.push_str("Variant");
             let inner = debug_type(ty, "", variant.type_id, indent + 1, visited);
-            result.push_str(&format!("\n{inner}"));
+            write!(result, "\n{inner}").unwrap();
         }
         Type::Callable(callable) => {
             result.push_str("Callable");
@@ -99,9 +101,9 @@ pub(crate) fn debug_type(
                 generics(&mut result, &callable.generic_types);
             }
             let inner = debug_type(ty, "Parameters", callable.parameters, indent + 1, visited);
-            result.push_str(&format!("\n{inner}"));
+            write!(result, "\n{inner}").unwrap();
             let inner = debug_type(ty, "Return", callable.return_type, indent + 1, visited);
-            result.push_str(&format!("\n{inner}"));
+            write!(result, "\n{inner}").unwrap();
         }
     }
 
@@ -116,7 +118,7 @@ fn generics(result: &mut String, generics: &[TypeId]) {
             if i != 0 {
                 result.push_str(", ");
             }
-            result.push_str(&format!("{}", type_id.index()));
+            write!(result, "{}", type_id.index()).unwrap();
         }
         result.push('>');
     }
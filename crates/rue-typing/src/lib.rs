@@ -4,6 +4,7 @@ mod compare_type;
 mod debug_type;
 mod difference_type;
 mod map;
+mod mismatch;
 mod replace_type;
 mod semantic_types;
 mod standard_types;
@@ -17,6 +18,7 @@ pub use bigint::*;
 pub use check::*;
 pub use compare_type::*;
 pub use map::*;
+pub use mismatch::*;
 pub use semantic_types::*;
 pub use standard_types::*;
 pub use ty::*;
@@ -0,0 +1,180 @@
+use crate::{Type, TypeId, TypeSystem};
+
+/// A step taken into a pair of types while comparing them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MismatchStep {
+    First,
+    Rest,
+    /// An item of a union on the left hand side, all of which must be assignable.
+    UnionItem(TypeId),
+    /// Marks the structure of a struct, whose fields the following steps refer to.
+    Struct(TypeId),
+    /// Marks the structure of an enum variant, whose fields the following steps refer to.
+    Variant(TypeId),
+    /// The parameters of a callable, whose names the following steps refer to.
+    Parameters(TypeId),
+    ReturnType,
+}
+
+impl MismatchStep {
+    /// Markers don't change which value is being compared, only how it's described.
+    pub fn is_marker(self) -> bool {
+        matches!(self, Self::Struct(..) | Self::Variant(..))
+    }
+}
+
+/// The innermost pair of types which caused a comparison to fail, and how they were reached.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mismatch {
+    pub path: Vec<MismatchStep>,
+    pub found: TypeId,
+    pub expected: TypeId,
+}
+
+/// A readable part of the path to a mismatch.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MismatchSegment {
+    Field(String),
+    First,
+    Rest,
+    UnionItem(TypeId),
+    Parameters,
+    ReturnType,
+}
+
+impl Mismatch {
+    /// Describes the path in terms of field and parameter names where they're known.
+    pub fn segments(&self, db: &TypeSystem) -> Vec<MismatchSegment> {
+        let mut segments = Vec::new();
+        let mut fields: Option<Fields> = None;
+
+        for &step in &self.path {
+            match step {
+                MismatchStep::First => {
+                    let fields = fields.take();
+
+                    if let Some(name) = fields.as_ref().and_then(Fields::first) {
+                        segments.push(MismatchSegment::Field(name));
+                        continue;
+                    }
+
+                    flush(&mut segments, fields);
+                    segments.push(MismatchSegment::First);
+                }
+                MismatchStep::Rest => {
+                    if let Some(current) = fields.as_mut() {
+                        current.index += 1;
+
+                        if let Some(name) = current.rest() {
+                            segments.push(MismatchSegment::Field(name));
+                            fields = None;
+                        }
+                        continue;
+                    }
+                    segments.push(MismatchSegment::Rest);
+                }
+                MismatchStep::UnionItem(type_id) => {
+                    flush(&mut segments, fields.take());
+                    segments.push(MismatchSegment::UnionItem(type_id));
+                }
+                MismatchStep::Struct(type_id) => {
+                    flush(&mut segments, fields.take());
+
+                    if let Type::Struct(ty) = db.get(type_id) {
+                        fields = Some(Fields::new(
+                            ty.field_names.iter().cloned().collect(),
+                            ty.nil_terminated,
+                            0,
+                        ));
+                    }
+                }
+                MismatchStep::Variant(type_id) => {
+                    flush(&mut segments, fields.take());
+
+                    if let Type::Variant(ty) = db.get(type_id) {
+                        let has_fields = matches!(
+                            db.get(ty.original_enum_type_id),
+                            Type::Enum(ty) if ty.has_fields
+                        );
+
+                        // The discriminant comes before the fields when the enum has fields.
+                        fields = ty.field_names.as_ref().map(|names| {
+                            Fields::new(
+                                names.iter().cloned().collect(),
+                                ty.nil_terminated,
+                                usize::from(has_fields),
+                            )
+                        });
+                    }
+                }
+                MismatchStep::Parameters(type_id) => {
+                    flush(&mut segments, fields.take());
+                    segments.push(MismatchSegment::Parameters);
+
+                    if let Type::Callable(ty) = db.get(type_id) {
+                        fields = Some(Fields::new(
+                            ty.parameter_names.iter().cloned().collect(),
+                            ty.nil_terminated,
+                            0,
+                        ));
+                    }
+                }
+                MismatchStep::ReturnType => {
+                    flush(&mut segments, fields.take());
+                    segments.push(MismatchSegment::ReturnType);
+                }
+            }
+        }
+
+        flush(&mut segments, fields);
+
+        segments
+    }
+}
+
+/// The names of the items in a chain of pairs, and how far into the chain the path is.
+struct Fields {
+    names: Vec<String>,
+    nil_terminated: bool,
+    skip: usize,
+    index: usize,
+}
+
+impl Fields {
+    fn new(names: Vec<String>, nil_terminated: bool, skip: usize) -> Self {
+        Self {
+            names,
+            nil_terminated,
+            skip,
+            index: 0,
+        }
+    }
+
+    /// The name of the item in the first position of the current pair.
+    fn first(&self) -> Option<String> {
+        let index = self.index.checked_sub(self.skip)?;
+        self.names.get(index).cloned()
+    }
+
+    /// The name of the last item, if it's in the rest position of the current pair.
+    fn rest(&self) -> Option<String> {
+        if self.nil_terminated {
+            return None;
+        }
+
+        let index = self.index.checked_sub(self.skip)?;
+
+        if index + 1 == self.names.len() {
+            self.names.last().cloned()
+        } else {
+            None
+        }
+    }
+}
+
+/// Steps into a chain of pairs which don't lead to a field are kept as is.
+fn flush(segments: &mut Vec<MismatchSegment>, fields: Option<Fields>) {
+    if let Some(fields) = fields {
+        segments.extend((0..fields.index).map(|_| MismatchSegment::Rest));
+    }
+}
@@ -1,15 +1,15 @@
-use crate::{HashMap, HashSet};
+use crate::HashSet;
 
 use crate::{Callable, Enum, Struct, Type, TypeId, TypeSystem, Variant};
 
 pub(crate) fn stringify_type(
     types: &TypeSystem,
     type_id: TypeId,
-    names: &HashMap<TypeId, String>,
+    names: &dyn Fn(TypeId) -> Option<String>,
     visited: &mut HashSet<TypeId>,
 ) -> String {
-    if let Some(name) = names.get(&type_id) {
-        return name.clone();
+    if let Some(name) = names(type_id) {
+        return name;
     }
 
     if !visited.insert(type_id) {
@@ -62,23 +62,28 @@ pub(crate) fn stringify_type(
             name + &generics
         }
         Type::Alias(alias) => stringify_type(types, alias.type_id, names, visited),
+        // Derived types are named after the type they were derived from, if it has a name.
         Type::Struct(Struct {
-            type_id,
+            type_id: structure,
             original_type_id,
             ..
         })
         | Type::Variant(Variant {
-            type_id,
+            type_id: structure,
+            original_type_id,
+            ..
+        })
+        | Type::Enum(Enum {
+            type_id: structure,
             original_type_id,
             ..
         }) => {
-            if type_id == original_type_id {
-                stringify_type(types, *type_id, names, visited)
+            if *original_type_id == type_id {
+                stringify_type(types, *structure, names, visited)
             } else {
                 stringify_type(types, *original_type_id, names, visited)
             }
         }
-        Type::Enum(Enum { type_id, .. }) => stringify_type(types, *type_id, names, visited),
         Type::Callable(Callable {
             parameters,
             return_type,
@@ -101,7 +106,7 @@ pub(crate) fn stringify_type(
 mod tests {
     use indexmap::indexmap;
 
-    use crate::alloc_callable;
+    use crate::{alloc_callable, HashMap};
 
     use super::*;
 
@@ -132,6 +137,18 @@ mod tests {
         assert_eq!(db.stringify_named(types.any, names), "CustomAny");
     }
 
+    #[test]
+    fn stringify_with() {
+        let mut db = TypeSystem::new();
+        let types = db.std();
+
+        let pair = db.alloc(Type::Pair(types.int, types.any));
+
+        let names = |type_id| (type_id == types.int).then(|| "CustomInt".to_string());
+
+        assert_eq!(db.stringify_with(pair, &names), "(CustomInt, Any)");
+    }
+
     #[test]
     fn test_stringify_callable() {
         let mut db = TypeSystem::new();
@@ -3,7 +3,7 @@ use id_arena::{Arena, Id};
 use crate::{
     check_type, compare_type, debug_type, difference_type, replace_type, simplify_check,
     stringify_type, substitute_type, Alias, Callable, Check, CheckError, Comparison,
-    ComparisonContext, HashMap, HashSet, StandardTypes, Type, TypePath,
+    ComparisonContext, HashMap, HashSet, Mismatch, StandardTypes, Type, TypePath,
 };
 
 pub type TypeId = Id<Type>;
@@ -162,6 +162,22 @@ impl TypeSystem {
         for (id, name) in &self.names {
             names.entry(*id).or_insert_with(|| name.clone());
         }
+        stringify_type(
+            self,
+            type_id,
+            &|id| names.get(&id).cloned(),
+            &mut HashSet::new(),
+        )
+    }
+
+    /// Stringifies a type, looking up names only for the types which are reached.
+    /// Built-in types are named if the lookup doesn't name them.
+    pub fn stringify_with(
+        &self,
+        type_id: TypeId,
+        names: &dyn Fn(TypeId) -> Option<String>,
+    ) -> String {
+        let names = |id| names(id).or_else(|| self.names.get(&id).cloned());
         stringify_type(self, type_id, &names, &mut HashSet::new())
     }
 
@@ -194,10 +210,38 @@ impl TypeSystem {
                 rhs_substitutions: Vec::new(),
                 inferred: substitution_stack,
                 infer_generics,
+                explain: false,
+                path: Vec::new(),
+                mismatch: None,
             },
         )
     }
 
+    /// Compares the types again to find the innermost types that caused the comparison to fail.
+    /// The substitution stack isn't modified, since any generics were already inferred.
+    pub fn explain_mismatch(
+        &self,
+        lhs: TypeId,
+        rhs: TypeId,
+        substitution_stack: &[HashMap<TypeId, TypeId>],
+        infer_generics: bool,
+    ) -> Option<Mismatch> {
+        let mut ctx = ComparisonContext {
+            visited: HashSet::new(),
+            lhs_substitutions: Vec::new(),
+            rhs_substitutions: Vec::new(),
+            inferred: &mut substitution_stack.to_vec(),
+            infer_generics,
+            explain: true,
+            path: Vec::new(),
+            mismatch: None,
+        };
+
+        compare_type(self, lhs, rhs, &mut ctx);
+
+        ctx.mismatch
+    }
+
     pub fn substitute(
         &mut self,
         type_id: TypeId,